@@ -1,4 +1,7 @@
 fn main() {
-    println!("cargo:rustc-link-lib=framework=UserNotifications");
+    // Bildirim çerçevesi yalnızca macOS'ta var
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        println!("cargo:rustc-link-lib=framework=UserNotifications");
+    }
     tauri_build::build()
 }
@@ -0,0 +1,440 @@
+// Millow — AI Pano İşlemleri
+// Panodaki ya da seçili metni OpenAI uyumlu bir LLM uç noktasıyla çevirir, yeniden yazar, özetler, koda dönüştürür
+// veya sesli talimata göre düzenler
+
+use crate::commander::{CommandHandler, CommandInfo, Executor, Invocation, Params};
+use crate::config::{self, MillowConfig};
+use crate::desktop;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Modele gönderilecek en uzun pano/seçim metni (karakter)
+const MAX_INPUT_CHARS: usize = 20_000;
+
+/// Panodaki metne uygulanacak işlem
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiAction {
+    Translate,
+    Rewrite,
+    Summarize,
+    GenerateCode,
+}
+
+impl AiAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Translate => "translate_clipboard",
+            Self::Rewrite => "rewrite_clipboard",
+            Self::Summarize => "summarize_clipboard",
+            Self::GenerateCode => "generate_code",
+        }
+    }
+
+    /// Sistem istemi — model yalnızca sonucu döndürmeli
+    fn prompt(&self, target_lang: &str) -> String {
+        match self {
+            Self::Translate => format!(
+                "Kullanıcının metnini {} diline çevir. Biçimlendirmeyi koru. SADECE çeviriyi döndür.",
+                target_lang
+            ),
+            Self::Rewrite => "Kullanıcının metnini aynı dilde daha açık, akıcı ve yazım hatasız olacak şekilde yeniden yaz. Anlamı değiştirme. SADECE yeni metni döndür.".into(),
+            Self::Summarize => "Kullanıcının metnini aynı dilde kısa ve öz şekilde özetle; gerekirse madde işaretleri kullan. SADECE özeti döndür.".into(),
+            Self::GenerateCode => "Kullanıcının açıklamasına uyan kodu yaz. Açıklama ya da Markdown çiti ekleme, SADECE kodu döndür.".into(),
+        }
+    }
+
+    /// İşlem sürerken gösterilen mesaj
+    fn progress(&self) -> &'static str {
+        match self {
+            Self::Translate => "Pano çevriliyor…",
+            Self::Rewrite => "Pano yeniden yazılıyor…",
+            Self::Summarize => "Pano özetleniyor…",
+            Self::GenerateCode => "Kod üretiliyor…",
+        }
+    }
+
+    /// Bitince gösterilen mesaj
+    fn done(&self) -> &'static str {
+        match self {
+            Self::Translate => "Çeviri",
+            Self::Rewrite => "Yeniden yazılan metin",
+            Self::Summarize => "Özet",
+            Self::GenerateCode => "Kod",
+        }
+    }
+}
+
+/// LLM uç noktası ve sonucun nereye gideceği
+#[derive(Debug, Clone)]
+pub struct AiSettings {
+    /// OpenAI uyumlu API kökü (".../v1")
+    pub endpoint: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    /// "clipboard" (panoya koy) veya "paste" (panoya koy ve yapıştır)
+    pub output: String,
+    pub target_lang: String,
+}
+
+impl AiSettings {
+    pub fn from_config(config: &MillowConfig) -> Self {
+        let endpoint = config.ai_endpoint.trim_end_matches('/').to_string();
+        // Groq anahtarı yalnızca Groq'a gönderilir — başka bir uç noktaya sızmasın
+        let groq_key = config
+            .groq_api_key
+            .clone()
+            .filter(|_| endpoint == config::default_groq_endpoint());
+        Self {
+            api_key: config
+                .ai_api_key
+                .clone()
+                .or(groq_key)
+                .filter(|key| !key.is_empty()),
+            endpoint,
+            model: config.ai_model.clone(),
+            output: config.ai_output.clone(),
+            target_lang: config.translation_target.clone(),
+        }
+    }
+}
+
+/// Pano metnini LLM ile işleyen komut
+pub struct AiHandler {
+    info: CommandInfo,
+    action: AiAction,
+    settings: AiSettings,
+}
+
+impl AiHandler {
+    fn new(action: AiAction, description: &str, triggers: &[&str], settings: &AiSettings) -> Self {
+        Self {
+            info: CommandInfo {
+                name: action.name().into(),
+                description: description.into(),
+                triggers: triggers.iter().map(|t| t.to_string()).collect(),
+                platforms: desktop::PLATFORMS.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            },
+            action,
+            settings: settings.clone(),
+        }
+    }
+}
+
+impl AiSettings {
+    /// Chat completions isteği
+    fn request(&self, system: &str, user: &str) -> Invocation {
+        let body = serde_json::json!({
+            "model": self.model,
+            "temperature": 0.3,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": user },
+            ],
+        });
+        let mut headers = BTreeMap::from([("Content-Type".into(), "application/json".into())]);
+        if let Some(key) = &self.api_key {
+            headers.insert("Authorization".into(), format!("Bearer {}", key));
+        }
+        Invocation::Http {
+            method: "POST".into(),
+            url: format!("{}/chat/completions", self.endpoint),
+            headers,
+            body: Some(body.to_string()),
+        }
+    }
+
+    /// İsteği gönder ve modelin cevabını döndür
+    fn complete(&self, exec: &dyn Executor, system: &str, user: &str) -> Result<String, String> {
+        let response = exec.execute(&self.request(system, user))?;
+        parse_completion(&response)
+    }
+}
+
+impl CommandHandler for AiHandler {
+    fn info(&self) -> &CommandInfo {
+        &self.info
+    }
+
+    fn execute(&self, _: &Params, exec: &dyn Executor) -> Result<String, String> {
+        let desktop = exec.desktop();
+        let input = desktop
+            .read_clipboard(exec)
+            .map_err(|e| format!("Pano okunamadı: {}", e))?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Pano boş".into());
+        }
+        check_length(input)?;
+
+        exec.progress(self.action.progress());
+        let prompt = self.action.prompt(&self.settings.target_lang);
+        let output = self.settings.complete(exec, &prompt, input)?;
+        let output = match self.action {
+            AiAction::GenerateCode => strip_fence(&output),
+            _ => output,
+        };
+
+        desktop
+            .write_clipboard(exec, &output)
+            .map_err(|e| format!("Panoya yazılamadı: {}", e))?;
+        if self.settings.output == "paste" {
+            desktop.shortcut(exec, 'v')?;
+            Ok(format!("{} yapıştırıldı", self.action.done()))
+        } else {
+            Ok(format!("{} panoya kopyalandı", self.action.done()))
+        }
+    }
+}
+
+/// AI pano komutları
+pub fn handlers(config: &MillowConfig) -> Vec<AiHandler> {
+    let settings = AiSettings::from_config(config);
+    vec![
+        AiHandler::new(
+            AiAction::Translate,
+            "Panodaki metni çevir",
+            &["panoyu çevir", "translate clipboard"],
+            &settings,
+        ),
+        AiHandler::new(
+            AiAction::Rewrite,
+            "Panodaki metni yeniden yaz",
+            &["panoyu yeniden yaz", "rewrite clipboard"],
+            &settings,
+        ),
+        AiHandler::new(
+            AiAction::Summarize,
+            "Panodaki metni özetle",
+            &["panoyu özetle", "summarize clipboard"],
+            &settings,
+        ),
+        AiHandler::new(
+            AiAction::GenerateCode,
+            "Panodaki açıklamadan kod üret",
+            &["kod üret", "generate code"],
+            &settings,
+        ),
+    ]
+}
+
+/// Seçili metne sesli talimatı uygula ("daha resmi yap", "İngilizceye çevir", "madde işaretlerine dönüştür")
+pub fn edit_text(
+    settings: &AiSettings,
+    exec: &dyn Executor,
+    instruction: &str,
+    selection: &str,
+) -> Result<String, String> {
+    let instruction = instruction.trim();
+    if instruction.is_empty() {
+        return Err("Talimat anlaşılamadı".into());
+    }
+    if selection.trim().is_empty() {
+        return Err("Seçili metin yok".into());
+    }
+    check_length(selection)?;
+
+    let system = "Kullanıcı bir metin ve o metin için bir talimat verecek. Talimatı metne uygula. \
+                  Talimat aksini söylemedikçe metnin dilini ve biçimini koru. \
+                  SADECE düzenlenmiş metni döndür; açıklama, tırnak veya Markdown çiti ekleme.";
+    let user = format!("Talimat: {}\n\nMetin:\n{}", instruction, selection);
+    let edited = settings.complete(exec, system, &user)?;
+    // Seçimin baştaki/sondaki boşlukları (satır sonu dahil) korunur
+    let leading = &selection[..selection.len() - selection.trim_start().len()];
+    let trailing = &selection[selection.trim_end().len()..];
+    Ok(format!("{}{}{}", leading, edited, trailing))
+}
+
+fn check_length(text: &str) -> Result<(), String> {
+    if text.chars().count() > MAX_INPUT_CHARS {
+        Err(format!(
+            "Metin çok uzun (en fazla {} karakter)",
+            MAX_INPUT_CHARS
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct Completion {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    content: Option<String>,
+}
+
+/// Chat completions yanıtından ilk cevabın metni
+fn parse_completion(response: &str) -> Result<String, String> {
+    let completion: Completion =
+        serde_json::from_str(response).map_err(|e| format!("LLM yanıtı okunamadı: {}", e))?;
+    completion
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.message.content)
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+        .ok_or_else(|| "LLM boş yanıt döndürdü".into())
+}
+
+/// Modelin yine de eklediği ```dil … ``` çitini kaldır
+fn strip_fence(text: &str) -> String {
+    let Some(rest) = text.strip_prefix("```") else {
+        return text.to_string();
+    };
+    let body = rest.split_once('\n').map(|(_, body)| body).unwrap_or("");
+    body.trim_end()
+        .strip_suffix("```")
+        .unwrap_or(body)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::{RecordingExecutor, SystemExecutor};
+    use crate::desktop::MacDesktop;
+    use crate::stub::StubServer;
+
+    fn completion(content: &str) -> String {
+        serde_json::json!({ "choices": [{ "message": { "content": content } }] }).to_string()
+    }
+
+    fn settings(endpoint: &str) -> AiSettings {
+        AiSettings {
+            endpoint: endpoint.into(),
+            api_key: Some("test-key".into()),
+            model: "test-model".into(),
+            output: "clipboard".into(),
+            target_lang: "en".into(),
+        }
+    }
+
+    fn stub_settings(server: &StubServer) -> AiSettings {
+        settings(&format!("{}/v1", server.url))
+    }
+
+    #[test]
+    fn groq_key_only_goes_to_groq() {
+        let groq = MillowConfig {
+            groq_api_key: Some("gsk".into()),
+            ai_endpoint: "https://api.groq.com/openai/v1/".into(),
+            ..MillowConfig::default()
+        };
+        let settings = AiSettings::from_config(&groq);
+        assert_eq!(settings.endpoint, "https://api.groq.com/openai/v1");
+        assert_eq!(settings.api_key.as_deref(), Some("gsk"));
+
+        let local = MillowConfig {
+            ai_endpoint: "http://localhost:11434/v1".into(),
+            ..groq.clone()
+        };
+        assert_eq!(AiSettings::from_config(&local).api_key, None);
+
+        let own = MillowConfig {
+            ai_api_key: Some("sk".into()),
+            ..local
+        };
+        assert_eq!(AiSettings::from_config(&own).api_key.as_deref(), Some("sk"));
+    }
+
+    #[test]
+    fn edit_text_posts_chat_completion() {
+        let server = StubServer::reply(200, &completion(" Dear Sir, "));
+        let edited = edit_text(
+            &stub_settings(&server),
+            &SystemExecutor,
+            "daha resmi yap",
+            "\n  selam\n",
+        )
+        .unwrap();
+        assert_eq!(edited, "\n  Dear Sir,\n");
+
+        let request = server.next_request().unwrap();
+        assert!(
+            request.starts_with("POST /v1/chat/completions "),
+            "{}",
+            request
+        );
+        assert!(request
+            .to_lowercase()
+            .contains("authorization: bearer test-key"));
+        let body: serde_json::Value =
+            serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["model"], "test-model");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(
+            body["messages"][1]["content"],
+            "Talimat: daha resmi yap\n\nMetin:\n\n  selam\n"
+        );
+    }
+
+    #[test]
+    fn endpoint_errors_surface() {
+        let server = StubServer::reply(401, r#"{"error":"bad key"}"#);
+        let err =
+            edit_text(&stub_settings(&server), &SystemExecutor, "kısalt", "metin").unwrap_err();
+        assert!(err.contains("401") && err.contains("bad key"), "{}", err);
+
+        let server = StubServer::reply(200, &completion("   "));
+        let err =
+            edit_text(&stub_settings(&server), &SystemExecutor, "kısalt", "metin").unwrap_err();
+        assert_eq!(err, "LLM boş yanıt döndürdü");
+    }
+
+    #[test]
+    fn generated_code_lands_on_clipboard_without_fence() {
+        let handler = AiHandler::new(
+            AiAction::GenerateCode,
+            "",
+            &["kod üret"],
+            &settings("https://llm.example/v1"),
+        );
+        let exec = RecordingExecutor::default()
+            .with_desktop(MacDesktop)
+            .answer("pbpaste", "iki sayıyı topla")
+            .answer(
+                "/chat/completions",
+                &completion("```rust\nfn add() {}\n```"),
+            );
+        assert_eq!(
+            handler.execute(&Params::new(), &exec).unwrap(),
+            "Kod panoya kopyalandı"
+        );
+        let calls = exec.take();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], Invocation::run("pbpaste", &[]));
+        assert!(calls[1].to_string().contains("iki sayıyı topla"));
+        assert!(calls[2].to_string().contains("fn add() {}"));
+        assert!(!calls[2].to_string().contains("```"));
+    }
+
+    #[test]
+    fn parses_completions() {
+        assert_eq!(
+            parse_completion(&completion(" merhaba \n")),
+            Ok("merhaba".into())
+        );
+        assert!(parse_completion(r#"{"choices":[]}"#).is_err());
+        assert!(parse_completion("<html>")
+            .unwrap_err()
+            .starts_with("LLM yanıtı okunamadı"));
+    }
+
+    #[test]
+    fn strips_code_fences() {
+        assert_eq!(strip_fence("```py\nprint(1)\n```\n"), "print(1)");
+        assert_eq!(strip_fence("```\na\nb\n```"), "a\nb");
+        assert_eq!(strip_fence("```\nyarım"), "yarım");
+        assert_eq!(strip_fence("düz metin"), "düz metin");
+    }
+}
@@ -4,6 +4,8 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::Stream;
 use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// Ses kayıt motoru durumu
@@ -13,6 +15,103 @@ pub enum RecordingState {
     Recording,
 }
 
+/// Anlık giriş seviyesi (~50ms pencere, değerler 0-1 aralığında)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct InputLevel {
+    pub rms: f32,
+    pub peak: f32,
+    pub clipping: bool,
+    /// false ise kayıt bitti — UI ve tray sıfırlanır
+    pub recording: bool,
+}
+
+/// Seviye değişimini dinleyen geri çağırma (ses thread'inden çağrılır)
+pub type LevelListener = Arc<dyn Fn(InputLevel) + Send + Sync>;
+
+/// Mikrofon testi raporu
+#[derive(Debug, Clone, Serialize)]
+pub struct MicTestReport {
+    pub device: String,
+    pub sample_rate: u32,
+    pub duration: f32,
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// Kırpılan örneklerin oranı (0-1)
+    pub clipped_ratio: f32,
+    pub clipping: bool,
+    /// "silent", "low", "clipping" veya "ok"
+    pub verdict: String,
+    pub message: String,
+}
+
+/// ~50ms'lik ölçüm penceresi
+struct LevelWindow {
+    size: usize,
+    sum_sq: f64,
+    peak: u16,
+    count: usize,
+}
+
+/// Gelen örnekleri pencerelere bölüp seviye hesaplayan ölçer
+#[derive(Clone)]
+struct LevelTap {
+    window: Arc<Mutex<LevelWindow>>,
+    listener: Arc<Mutex<Option<LevelListener>>>,
+}
+
+impl LevelTap {
+    fn new() -> Self {
+        Self {
+            window: Arc::new(Mutex::new(LevelWindow { size: 800, sum_sq: 0.0, peak: 0, count: 0 })),
+            listener: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Yeni kayıt için pencereyi cihaz hızına göre ayarla (~50ms)
+    fn reset(&self, sample_rate: u32) {
+        *self.window.lock() = LevelWindow {
+            size: (sample_rate as usize / 20).max(1),
+            sum_sq: 0.0,
+            peak: 0,
+            count: 0,
+        };
+    }
+
+    fn feed(&self, mono: &[i16]) {
+        let mut completed = None;
+        {
+            let mut w = self.window.lock();
+            for &s in mono {
+                w.sum_sq += (s as f64) * (s as f64);
+                w.peak = w.peak.max(s.unsigned_abs());
+                w.count += 1;
+                if w.count >= w.size {
+                    let peak = w.peak as f32 / 32768.0;
+                    completed = Some(InputLevel {
+                        rms: (w.sum_sq / w.count as f64).sqrt() as f32 / 32768.0,
+                        peak,
+                        clipping: peak >= 0.99,
+                        recording: true,
+                    });
+                    w.sum_sq = 0.0;
+                    w.peak = 0;
+                    w.count = 0;
+                }
+            }
+        }
+        if let Some(level) = completed {
+            self.publish(level);
+        }
+    }
+
+    fn publish(&self, level: InputLevel) {
+        let listener = self.listener.lock().clone();
+        if let Some(listener) = listener {
+            listener(level);
+        }
+    }
+}
+
 // Stream'i thread-safe tutmak için wrapper
 struct StreamHolder(Stream);
 unsafe impl Send for StreamHolder {}
@@ -29,6 +128,46 @@ pub struct AudioEngine {
     last_voice_activity: Arc<Mutex<std::time::Instant>>,
     /// Ortam gürültüsü toleransı
     noise_tolerance: Arc<Mutex<f32>>,
+    /// Kayıt başından beri yakalanan toplam örnek sayısı (drain'den etkilenmez)
+    total_samples: Arc<AtomicUsize>,
+    /// Süre veya bellek limitine ulaşıldı mı
+    limit_reached: Arc<AtomicBool>,
+    /// Giriş seviyesi ölçer
+    level_tap: LevelTap,
+    /// Kullanılan mikrofonun adı
+    device_name: Arc<Mutex<String>>,
+}
+
+/// Kayıt limitleri (örnek sayısı cinsinden, mono)
+#[derive(Debug, Clone, Copy)]
+struct CaptureLimits {
+    /// Buffer'da aynı anda tutulabilecek maksimum örnek (bellek sınırı)
+    max_buffered: usize,
+    /// Kayıt boyunca yakalanabilecek maksimum örnek (süre sınırı)
+    max_total: usize,
+}
+
+/// Mono örnekleri limitlere uyarak buffer'a ekle, limit aşılırsa fazlasını at
+fn append_capped(
+    samples: &Mutex<Vec<i16>>,
+    mono: &[i16],
+    total: &AtomicUsize,
+    limit_reached: &AtomicBool,
+    limits: CaptureLimits,
+) {
+    if limit_reached.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut buf = samples.lock();
+    let room_total = limits.max_total.saturating_sub(total.load(Ordering::Relaxed));
+    let room_buffer = limits.max_buffered.saturating_sub(buf.len());
+    let take = mono.len().min(room_total).min(room_buffer);
+    buf.extend_from_slice(&mono[..take]);
+    total.fetch_add(take, Ordering::Relaxed);
+    if take < mono.len() {
+        limit_reached.store(true, Ordering::Relaxed);
+        println!("⛔ Kayıt limiti doldu ({} örnek), yeni ses alınmıyor", buf.len());
+    }
 }
 
 impl AudioEngine {
@@ -40,6 +179,10 @@ impl AudioEngine {
             active_stream: Mutex::new(None),
             last_voice_activity: Arc::new(Mutex::new(std::time::Instant::now())),
             noise_tolerance: Arc::new(Mutex::new(0.15)),
+            total_samples: Arc::new(AtomicUsize::new(0)),
+            limit_reached: Arc::new(AtomicBool::new(false)),
+            level_tap: LevelTap::new(),
+            device_name: Arc::new(Mutex::new(String::new())),
         }
     }
 
@@ -47,25 +190,50 @@ impl AudioEngine {
         *self.actual_sample_rate.lock()
     }
 
-    /// Kaydı başlat
+    pub fn get_device_name(&self) -> String {
+        self.device_name.lock().clone()
+    }
+
+    /// Seviye dinleyicisini ayarla — her ~50ms pencerede çağrılır
+    pub fn set_level_listener<F>(&self, listener: F)
+    where
+        F: Fn(InputLevel) + Send + Sync + 'static,
+    {
+        *self.level_tap.listener.lock() = Some(Arc::new(listener));
+    }
+
+    /// Kaydı başlat — kayıt sürüyorsa (ör. mikrofon testi) akışa dokunmadan hata döner
     pub fn start_recording(&self) -> Result<(), String> {
+        let mut state = self.state.lock();
+        if *state == RecordingState::Recording {
+            return Err("Zaten kayıt yapılıyor".into());
+        }
+
         // Önceki stream varsa temizle
         {
             let mut stream_guard = self.active_stream.lock();
             *stream_guard = None;
         }
 
-        let mut state = self.state.lock();
-        if *state == RecordingState::Recording {
-            return Err("Zaten kayıt yapılıyor".into());
-        }
-
         self.samples.lock().clear();
-        *self.noise_tolerance.lock() = crate::config::MillowConfig::load().noise_tolerance;
+        let cfg = crate::config::MillowConfig::load();
+        *self.noise_tolerance.lock() = cfg.noise_tolerance * cfg.vad_scale();
+        self.total_samples.store(0, Ordering::Relaxed);
+        self.limit_reached.store(false, Ordering::Relaxed);
         *self.last_voice_activity.lock() = std::time::Instant::now();
         *state = RecordingState::Recording;
         drop(state); // Lock'u serbest bırak
 
+        let result = self.open_stream(&cfg);
+        if result.is_err() {
+            // Açılamayan kayıt sonraki denemeleri "zaten kayıt yapılıyor" ile kilitlemesin
+            *self.state.lock() = RecordingState::Idle;
+        }
+        result
+    }
+
+    /// Varsayılan mikrofonu aç ve örnekleri toplayan akışı başlat
+    fn open_stream(&self, cfg: &crate::config::MillowConfig) -> Result<(), String> {
         let host = cpal::default_host();
         let device = host
             .default_input_device()
@@ -85,6 +253,14 @@ impl AudioEngine {
         );
 
         *self.actual_sample_rate.lock() = device_sample_rate;
+        *self.device_name.lock() = device.name().unwrap_or_default();
+        self.level_tap.reset(device_sample_rate);
+
+        // Süre ve bellek limitlerini örnek sayısına çevir (i16 = 2 byte)
+        let limits = CaptureLimits {
+            max_buffered: (cfg.max_recording_memory_mb.max(1.0) as f64 * 1024.0 * 1024.0 / 2.0) as usize,
+            max_total: (cfg.max_recording_duration.max(1.0) as f64 * device_sample_rate as f64) as usize,
+        };
 
         let config = cpal::StreamConfig {
             channels: device_channels,
@@ -96,6 +272,9 @@ impl AudioEngine {
         let state_clone = self.state.clone();
         let channels = device_channels as usize;
         let voice_ts = self.last_voice_activity.clone();
+        let total = self.total_samples.clone();
+        let limit_hit = self.limit_reached.clone();
+        let level_tap = self.level_tap.clone();
         let noise_tol = *self.noise_tolerance.lock();
         let silence_threshold: i16 = (noise_tol * 32767.0) as i16; // ~1.5% of max
 
@@ -115,9 +294,11 @@ impl AudioEngine {
                                     .chunks(channels)
                                     .map(|frame| frame[0])
                                     .collect();
-                                samples.lock().extend_from_slice(&mono);
+                                level_tap.feed(&mono);
+                                append_capped(&samples, &mono, &total, &limit_hit, limits);
                             } else {
-                                samples.lock().extend_from_slice(data);
+                                level_tap.feed(data);
+                                append_capped(&samples, data, &total, &limit_hit, limits);
                             }
                         }
                     },
@@ -129,6 +310,9 @@ impl AudioEngine {
                 let samples2 = self.samples.clone();
                 let state_clone2 = self.state.clone();
                 let voice_ts2 = self.last_voice_activity.clone();
+                let total2 = self.total_samples.clone();
+                let limit_hit2 = self.limit_reached.clone();
+                let level_tap2 = self.level_tap.clone();
                 let silence_threshold_f: f32 = *self.noise_tolerance.lock();
                 device.build_input_stream(
                     &config,
@@ -147,7 +331,8 @@ impl AudioEngine {
                                     .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
                                     .collect()
                             };
-                            samples2.lock().extend_from_slice(&mono);
+                            level_tap2.feed(&mono);
+                            append_capped(&samples2, &mono, &total2, &limit_hit2, limits);
                         }
                     },
                     |err| eprintln!("Ses akışı hatası: {}", err),
@@ -178,6 +363,7 @@ impl AudioEngine {
             *stream_guard = None;
             println!("🛑 Audio stream durduruldu");
         }
+        self.level_tap.publish(InputLevel::default());
 
         self.samples.lock().clone()
     }
@@ -198,11 +384,91 @@ impl AudioEngine {
         *self.state.lock() == RecordingState::Recording
     }
 
+    /// Maksimum süre veya bellek limitine ulaşıldı mı
+    pub fn limit_reached(&self) -> bool {
+        self.limit_reached.load(Ordering::Relaxed)
+    }
+
     /// Son ses aktivitesinden bu yana geçen süre (saniye)
     pub fn seconds_since_voice(&self) -> f64 {
         self.last_voice_activity.lock().elapsed().as_secs_f64()
     }
 
+    /// Örneklerin RMS seviyesi
+    pub fn rms(samples: &[i16]) -> f64 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        (samples.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>() / samples.len() as f64).sqrt()
+    }
+
+    /// Mikrofon testi kaydını analiz et: seviye, kırpılma ve teşhis
+    pub fn analyze_levels(samples: &[i16], source_rate: u32, device: &str) -> MicTestReport {
+        let to_db = |v: f64| if v > 0.0 { (20.0 * (v / 32768.0).log10()) as f32 } else { -100.0 };
+        let rms = Self::rms(samples);
+        let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+        let clipped = samples.iter().filter(|s| s.unsigned_abs() >= 32440).count();
+        let clipped_ratio = if samples.is_empty() { 0.0 } else { clipped as f32 / samples.len() as f32 };
+
+        let rms_db = to_db(rms).max(-100.0);
+        let peak_db = to_db(peak as f64).max(-100.0);
+        let clipping = clipped_ratio > 0.001;
+
+        let (verdict, message) = if samples.is_empty() || peak_db < -60.0 {
+            ("silent", "Mikrofondan ses gelmiyor — sessize alınmış veya yanlış cihaz seçilmiş olabilir")
+        } else if clipping {
+            ("clipping", "Ses çok yüksek, kırpılma var — mikrofon kazancını düşürün")
+        } else if rms_db < -45.0 {
+            ("low", "Ses çok düşük — mikrofona yaklaşın veya kazancı artırın")
+        } else {
+            ("ok", "Mikrofon düzgün çalışıyor")
+        };
+
+        MicTestReport {
+            device: device.to_string(),
+            sample_rate: source_rate,
+            duration: if source_rate > 0 { samples.len() as f32 / source_rate as f32 } else { 0.0 },
+            rms_db,
+            peak_db,
+            clipped_ratio,
+            clipping,
+            verdict: verdict.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Uzun kaydı sessizlik noktalarından en fazla `max_chunk_secs` uzunluğunda parçalara böl.
+    /// Kesim, her parçanın son %30'luk bölümündeki en sessiz 20ms pencereye yapılır.
+    pub fn pause_ranges(samples: &[i16], source_rate: u32, max_chunk_secs: f32) -> Vec<std::ops::Range<usize>> {
+        let max_len = (source_rate as f64 * max_chunk_secs.max(1.0) as f64) as usize;
+        if max_len == 0 || samples.len() <= max_len {
+            return std::iter::once(0..samples.len()).collect();
+        }
+
+        let window = (source_rate as usize / 50).max(1);
+        let mut ranges = Vec::new();
+        let mut start = 0;
+
+        while samples.len() - start > max_len {
+            let search_to = start + max_len;
+            let mut cut = search_to;
+            let mut quietest = f64::MAX;
+            let mut pos = start + max_len * 7 / 10;
+            while pos + window <= search_to {
+                let level = Self::rms(&samples[pos..pos + window]);
+                if level < quietest {
+                    quietest = level;
+                    cut = pos + window / 2;
+                }
+                pos += window;
+            }
+            ranges.push(start..cut);
+            start = cut;
+        }
+        ranges.push(start..samples.len());
+        ranges
+    }
+
     /// PCM örneklerini WAV bytes'a çevir (16kHz mono çıktı)
     pub fn samples_to_wav(samples: &[i16], source_rate: u32) -> Result<Vec<u8>, String> {
         let target_rate: u32 = 16000;
@@ -252,3 +518,235 @@ impl AudioEngine {
         Ok(cursor.into_inner())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `rate` Hz'de `secs` saniyelik sinüs — genlik i16 aralığına kırpılır
+    fn sine(freq: f32, amplitude: f32, rate: u32, secs: f32) -> Vec<i16> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32;
+                (amplitude * phase.sin()).round().clamp(-32768.0, 32767.0) as i16
+            })
+            .collect()
+    }
+
+    fn tap_with_log(rate: u32) -> (LevelTap, Arc<Mutex<Vec<InputLevel>>>) {
+        let tap = LevelTap::new();
+        tap.reset(rate);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&log);
+        *tap.listener.lock() = Some(Arc::new(move |level| sink.lock().push(level)));
+        (tap, log)
+    }
+
+    #[test]
+    fn level_tap_measures_full_windows() {
+        // 1000 Hz'de pencere 50 örnek
+        let (tap, log) = tap_with_log(1000);
+        let tone = sine(125.0, 16384.0, 1000, 1.0);
+
+        tap.feed(&tone[..49]);
+        assert!(log.lock().is_empty());
+        tap.feed(&tone[49..50]);
+        let level = log.lock()[0];
+        assert!((level.rms - 0.3536).abs() < 0.01, "{:?}", level);
+        assert!((level.peak - 0.5).abs() < 0.001, "{:?}", level);
+        assert!(!level.clipping);
+        assert!(level.recording);
+
+        // Tek çağrıda biten pencerelerden yalnızca sonuncusu yayınlanır, artan örnekler sonrakine kalır
+        tap.feed(&tone[50..170]);
+        assert_eq!(log.lock().len(), 2);
+        tap.feed(&[0; 29]);
+        assert_eq!(log.lock().len(), 2);
+        tap.feed(&[0]);
+        let level = log.lock()[2];
+        assert_eq!(log.lock().len(), 3);
+        assert!(level.rms > 0.0 && level.rms < 0.3, "{:?}", level);
+    }
+
+    #[test]
+    fn level_tap_flags_silence_and_clipping() {
+        let (tap, log) = tap_with_log(1000);
+        tap.feed(&[0; 50]);
+        tap.feed(&sine(125.0, 40_000.0, 1000, 0.05));
+        let levels = log.lock().clone();
+        assert_eq!(levels.len(), 2);
+        assert_eq!((levels[0].rms, levels[0].peak), (0.0, 0.0));
+        assert!(!levels[0].clipping);
+        assert!(levels[1].clipping, "{:?}", levels[1]);
+        assert!(levels[1].peak >= 0.99);
+    }
+
+    #[test]
+    fn analyzes_clean_tone() {
+        let report =
+            AudioEngine::analyze_levels(&sine(1000.0, 16384.0, 8000, 1.0), 8000, "Mikrofon");
+        assert_eq!(report.device, "Mikrofon");
+        assert_eq!(report.sample_rate, 8000);
+        assert!((report.duration - 1.0).abs() < 1e-6);
+        assert!((report.rms_db + 9.03).abs() < 0.1, "{:?}", report);
+        assert!((report.peak_db + 6.02).abs() < 0.01, "{:?}", report);
+        assert_eq!(report.clipped_ratio, 0.0);
+        assert!(!report.clipping);
+        assert_eq!(report.verdict, "ok");
+    }
+
+    #[test]
+    fn analyzes_silence_and_quiet_input() {
+        let silent = AudioEngine::analyze_levels(&[0; 8000], 8000, "");
+        assert_eq!((silent.rms_db, silent.peak_db), (-100.0, -100.0));
+        assert_eq!(silent.verdict, "silent");
+
+        let empty = AudioEngine::analyze_levels(&[], 0, "");
+        assert_eq!(empty.duration, 0.0);
+        assert_eq!(empty.clipped_ratio, 0.0);
+        assert_eq!(empty.verdict, "silent");
+
+        // -64 dB tepe: sessiz sayılır
+        let hum = AudioEngine::analyze_levels(&sine(1000.0, 20.0, 8000, 1.0), 8000, "");
+        assert_eq!(hum.verdict, "silent", "{:?}", hum);
+
+        // -50 dB tepe, -53 dB RMS: çok düşük
+        let quiet = AudioEngine::analyze_levels(&sine(1000.0, 100.0, 8000, 1.0), 8000, "");
+        assert!(quiet.peak_db > -60.0 && quiet.rms_db < -45.0, "{:?}", quiet);
+        assert_eq!(quiet.verdict, "low");
+    }
+
+    #[test]
+    fn analyzes_clipping() {
+        // 45°'lik adımlarla her 8 örnekten 2'si tepede kırpılır
+        let report = AudioEngine::analyze_levels(&sine(1000.0, 40_000.0, 8000, 1.0), 8000, "");
+        assert!((report.clipped_ratio - 0.25).abs() < 1e-6, "{:?}", report);
+        assert!(report.clipping);
+        assert!(report.peak_db > -0.01);
+        assert_eq!(report.verdict, "clipping");
+
+        // Tek tük kırpılma (%0.1 altı) uyarı vermez
+        let mut tone = sine(1000.0, 16384.0, 8000, 1.0);
+        tone[100] = i16::MAX;
+        let report = AudioEngine::analyze_levels(&tone, 8000, "");
+        assert!(!report.clipping);
+        assert_eq!(report.verdict, "ok");
+    }
+
+    /// Tekrarlanabilir gürültü (LCG), ±3000 aralığında
+    fn noise(len: usize) -> Vec<i16> {
+        let mut state: u32 = 0x1234_5678;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                ((state >> 16) % 6001) as i16 - 3000
+            })
+            .collect()
+    }
+
+    fn assert_covers(ranges: &[std::ops::Range<usize>], len: usize, max_len: usize) {
+        assert_eq!(ranges.first().unwrap().start, 0);
+        assert_eq!(ranges.last().unwrap().end, len);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start, "{:?}", ranges);
+        }
+        for range in ranges {
+            assert!(!range.is_empty() && range.len() <= max_len, "{:?}", ranges);
+        }
+    }
+
+    #[test]
+    fn short_buffer_is_one_range() {
+        let single = |samples: &[i16], secs: f32, len: usize| {
+            let ranges = AudioEngine::pause_ranges(samples, 1000, secs);
+            assert_eq!(ranges.len(), 1, "{:?}", ranges);
+            assert_eq!(ranges[0], 0..len);
+        };
+        single(&noise(10_000), 10.0, 10_000);
+        single(&[], 10.0, 0);
+        // Bir saniyenin altındaki parça süresi bir saniyeye yükseltilir
+        single(&noise(900), 0.1, 900);
+    }
+
+    #[test]
+    fn ranges_cover_every_sample() {
+        let samples = noise(47_321);
+        let ranges = AudioEngine::pause_ranges(&samples, 1000, 10.0);
+        // Kesimler parçanın en erken %70'inde olduğundan en fazla 7 parça
+        assert!((5..=7).contains(&ranges.len()), "{:?}", ranges);
+        assert_covers(&ranges, samples.len(), 10_000);
+
+        let ranges = AudioEngine::pause_ranges(&samples, 16_000, 1.0);
+        assert_covers(&ranges, samples.len(), 16_000);
+    }
+
+    #[test]
+    fn cuts_at_pause_in_last_stretch() {
+        let mut samples = noise(25_000);
+        // Son %30'luk bölümde (7000-10000) bir sessizlik, öncesinde daha uzun bir tane
+        samples[3000..4000].fill(0);
+        samples[8500..8600].fill(0);
+        let ranges = AudioEngine::pause_ranges(&samples, 1000, 10.0);
+        assert_covers(&ranges, samples.len(), 10_000);
+        assert!((8500..=8600).contains(&ranges[0].end), "{:?}", ranges);
+
+        // Sessizlik yoksa kesim yine son %30'da kalır
+        let ranges = AudioEngine::pause_ranges(&noise(25_000), 1000, 10.0);
+        assert!((7000..=10_000).contains(&ranges[0].end), "{:?}", ranges);
+    }
+
+    #[test]
+    fn append_stops_at_buffer_cap() {
+        let samples = Mutex::new(Vec::new());
+        let total = AtomicUsize::new(0);
+        let limit = AtomicBool::new(false);
+        let limits = CaptureLimits {
+            max_buffered: 10,
+            max_total: 100,
+        };
+
+        append_capped(&samples, &[1; 6], &total, &limit, limits);
+        assert_eq!(samples.lock().len(), 6);
+        assert!(!limit.load(Ordering::Relaxed));
+
+        append_capped(&samples, &[2; 6], &total, &limit, limits);
+        assert_eq!(*samples.lock(), [1, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+        assert_eq!(total.load(Ordering::Relaxed), 10);
+        assert!(limit.load(Ordering::Relaxed));
+
+        // Limit dolduktan sonra buffer boşalsa da yeni ses alınmaz
+        samples.lock().clear();
+        append_capped(&samples, &[3; 2], &total, &limit, limits);
+        assert!(samples.lock().is_empty());
+        assert_eq!(total.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn append_stops_at_total_cap() {
+        let samples = Mutex::new(Vec::new());
+        let total = AtomicUsize::new(0);
+        let limit = AtomicBool::new(false);
+        let limits = CaptureLimits {
+            max_buffered: 100,
+            max_total: 8,
+        };
+
+        append_capped(&samples, &[1; 5], &total, &limit, limits);
+        // Segment teslimi buffer'ı boşaltır, toplam sayaç sürer
+        samples.lock().clear();
+        append_capped(&samples, &[2; 5], &total, &limit, limits);
+        assert_eq!(*samples.lock(), [2, 2, 2]);
+        assert_eq!(total.load(Ordering::Relaxed), 8);
+        assert!(limit.load(Ordering::Relaxed));
+
+        // Tam sınıra oturan ekleme limiti tetiklemez
+        let (samples, total, limit) = (
+            Mutex::new(Vec::new()),
+            AtomicUsize::new(0),
+            AtomicBool::new(false),
+        );
+        append_capped(&samples, &[1; 8], &total, &limit, limits);
+        assert_eq!(samples.lock().len(), 8);
+        assert!(!limit.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,608 @@
+// Millow — Yerleşik Komutlar
+// macOS ve Linux'ta çalışan hazır sesli komutlar; her biri meta verisi olan bir CommandHandler
+
+use crate::commander::{CommandHandler, CommandInfo, Executor, Params, SlotKind, SlotRule};
+use crate::desktop;
+use crate::media::{self, MediaKey};
+
+/// Yerleşik komut gövdesi
+type BuiltinFn = fn(&Params, &dyn Executor) -> Result<String, String>;
+
+/// Fonksiyonla tanımlanan yerleşik komut
+pub struct BuiltinHandler {
+    info: CommandInfo,
+    run: BuiltinFn,
+}
+
+impl BuiltinHandler {
+    fn new(name: &str, description: &str, triggers: &[&str], run: BuiltinFn) -> Self {
+        Self {
+            info: CommandInfo {
+                name: name.into(),
+                description: description.into(),
+                triggers: triggers.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            },
+            run,
+        }
+    }
+
+    fn slot(mut self, name: &str, rule: SlotRule) -> Self {
+        self.info.params.insert(name.into(), rule);
+        self
+    }
+
+    fn platforms(mut self, platforms: &[&str]) -> Self {
+        self.info.platforms = platforms.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Ekran kilidi, ağ bağlantısı, sekme kapatma gibi geri alınması zor etkiler
+    fn destructive(mut self) -> Self {
+        self.info.destructive = true;
+        self
+    }
+}
+
+impl CommandHandler for BuiltinHandler {
+    fn info(&self) -> &CommandInfo {
+        &self.info
+    }
+
+    fn execute(&self, params: &Params, exec: &dyn Executor) -> Result<String, String> {
+        (self.run)(params, exec)
+    }
+}
+
+/// Adıyla yerleşik komut (makro adımları için)
+pub fn find(name: &str) -> Option<BuiltinHandler> {
+    handlers()
+        .into_iter()
+        .find(|handler| handler.info.name == name)
+}
+
+/// Tüm yerleşik komutlar
+pub fn handlers() -> Vec<BuiltinHandler> {
+    let text = |default: &str| SlotRule {
+        default: Some(default.into()),
+        ..Default::default()
+    };
+    let url = SlotRule {
+        kind: SlotKind::Url,
+        default: Some("https://google.com".into()),
+        ..Default::default()
+    };
+
+    vec![
+        // ── Uygulama Yönetimi ──
+        BuiltinHandler::new(
+            "open_app",
+            "Uygulama aç",
+            &["{app} aç", "{app} uygulamasını aç", "open {app}"],
+            open_app,
+        )
+        .slot("app", text("Finder"))
+        .platforms(desktop::PLATFORMS),
+        // ── Ekran Görüntüsü ──
+        BuiltinHandler::new(
+            "screenshot",
+            "Ekran görüntüsünü masaüstüne kaydet",
+            &["ekran görüntüsü al", "take a screenshot"],
+            screenshot,
+        )
+        .platforms(desktop::PLATFORMS),
+        // ── Ses Kontrolleri ──
+        BuiltinHandler::new(
+            "volume_up",
+            "Sesi artır",
+            &["sesi artır", "sesi aç", "volume up"],
+            volume_up,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "volume_down",
+            "Sesi azalt",
+            &["sesi azalt", "sesi kıs", "volume down"],
+            volume_down,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "mute",
+            "Sessiz modu aç/kapat",
+            &["sessize al", "mute"],
+            mute,
+        )
+        .platforms(desktop::PLATFORMS),
+        // ── Parlaklık ──
+        BuiltinHandler::new(
+            "brightness_up",
+            "Parlaklığı artır",
+            &["parlaklığı artır", "brightness up"],
+            brightness_up,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "brightness_down",
+            "Parlaklığı azalt",
+            &["parlaklığı azalt", "brightness down"],
+            brightness_down,
+        )
+        .platforms(desktop::PLATFORMS),
+        // ── Sistem ──
+        BuiltinHandler::new(
+            "dark_mode",
+            "Karanlık modu aç/kapat",
+            &["karanlık modu değiştir", "karanlık mod", "toggle dark mode"],
+            dark_mode,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "lock_screen",
+            "Ekranı kilitle",
+            &["ekranı kilitle", "lock screen"],
+            lock_screen,
+        )
+        .platforms(desktop::PLATFORMS)
+        .destructive(),
+        BuiltinHandler::new(
+            "wifi_toggle",
+            "Wi-Fi'ı aç/kapat",
+            &["wi-fi'ı aç kapat", "wifi'ı değiştir", "toggle wifi"],
+            wifi_toggle,
+        )
+        .platforms(desktop::PLATFORMS)
+        .destructive(),
+        BuiltinHandler::new(
+            "bluetooth_toggle",
+            "Bluetooth ayarlarını aç",
+            &["bluetooth ayarları", "bluetooth settings"],
+            bluetooth_settings,
+        )
+        .platforms(desktop::PLATFORMS),
+        // ── Medya Kontrol ──
+        BuiltinHandler::new(
+            "play_pause",
+            "Oynat/duraklat",
+            &["oynat", "duraklat", "play", "pause"],
+            play_pause,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "next_track",
+            "Sonraki şarkı",
+            &["sonraki şarkı", "next track"],
+            next_track,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "prev_track",
+            "Önceki şarkı",
+            &["önceki şarkı", "previous track"],
+            prev_track,
+        )
+        .platforms(desktop::PLATFORMS),
+        // ── Tarayıcı ──
+        BuiltinHandler::new(
+            "new_tab",
+            "Yeni sekme aç",
+            &["yeni sekme", "new tab"],
+            new_tab,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new(
+            "close_tab",
+            "Geçerli sekmeyi kapat",
+            &["sekmeyi kapat", "close tab"],
+            close_tab,
+        )
+        .platforms(desktop::PLATFORMS)
+        .destructive(),
+        BuiltinHandler::new(
+            "open_url",
+            "Adresi varsayılan tarayıcıda aç",
+            &["{url} adresini aç", "{url} sitesini aç", "go to {url}"],
+            open_url,
+        )
+        .slot("url", url)
+        .platforms(desktop::PLATFORMS),
+        // ── Metin İşlemleri (Kısayol Simülasyonu) ──
+        BuiltinHandler::new(
+            "select_all",
+            "Tümünü seç",
+            &["tümünü seç", "select all"],
+            select_all,
+        )
+        .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new("copy", "Seçimi kopyala", &["kopyala", "copy"], copy)
+            .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new("paste", "Panoyu yapıştır", &["yapıştır", "paste"], paste)
+            .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new("undo", "Son işlemi geri al", &["geri al", "undo"], undo)
+            .platforms(desktop::PLATFORMS),
+        BuiltinHandler::new("save", "Belgeyi kaydet", &["kaydet", "save"], save)
+            .platforms(desktop::PLATFORMS),
+    ]
+}
+
+/// Yuva değeri (varsayılanlar kayıt tarafından doldurulur)
+fn param<'a>(params: &'a Params, name: &str) -> &'a str {
+    params.get(name).map(String::as_str).unwrap_or_default()
+}
+
+fn open_app(params: &Params, exec: &dyn Executor) -> Result<String, String> {
+    let app_name = param(params, "app");
+    exec.desktop()
+        .open_app(exec, app_name)
+        .map_err(|e| format!("{} açılamadı: {}", app_name, e))?;
+    Ok(format!("{} açıldı", app_name))
+}
+
+fn screenshot(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    let home = std::path::PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".into()));
+    let desktop_dir = home.join("Desktop");
+    let dir = if desktop_dir.is_dir() {
+        desktop_dir
+    } else {
+        home
+    };
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let path = dir.join(format!("millow_screenshot_{}.png", timestamp));
+    let path = path.to_string_lossy();
+    exec.desktop()
+        .screenshot(exec, &path)
+        .map_err(|e| format!("Ekran görüntüsü alınamadı: {}", e))?;
+    Ok(format!("Ekran görüntüsü kaydedildi: {}", path))
+}
+
+fn volume_up(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().change_volume(exec, 10)?;
+    Ok("Ses artırıldı".into())
+}
+
+fn volume_down(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().change_volume(exec, -10)?;
+    Ok("Ses azaltıldı".into())
+}
+
+fn mute(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().toggle_mute(exec)?;
+    Ok("Sessiz modu değiştirildi".into())
+}
+
+fn brightness_up(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().change_brightness(exec, 10)?;
+    Ok("Parlaklık artırıldı".into())
+}
+
+fn brightness_down(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().change_brightness(exec, -10)?;
+    Ok("Parlaklık azaltıldı".into())
+}
+
+fn dark_mode(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().toggle_dark_mode(exec)
+}
+
+fn lock_screen(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop()
+        .lock_screen(exec)
+        .map_err(|e| format!("Ekran kilitlenemedi: {}", e))?;
+    Ok("Ekran kilitlendi".into())
+}
+
+fn wifi_toggle(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop()
+        .toggle_wifi(exec)
+        .map_err(|e| format!("Wi-Fi değiştirilemedi: {}", e))
+}
+
+fn bluetooth_settings(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().bluetooth_settings(exec)?;
+    Ok("Bluetooth ayarları açıldı".into())
+}
+
+fn play_pause(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    media::control(MediaKey::PlayPause, exec)
+}
+
+fn next_track(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    media::control(MediaKey::Next, exec)
+}
+
+fn prev_track(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    media::control(MediaKey::Previous, exec)
+}
+
+fn new_tab(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 't')?;
+    Ok("Yeni sekme açıldı".into())
+}
+
+fn close_tab(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 'w')?;
+    Ok("Sekme kapatıldı".into())
+}
+
+fn open_url(params: &Params, exec: &dyn Executor) -> Result<String, String> {
+    let url = param(params, "url");
+    exec.check_url(url)?;
+    exec.desktop()
+        .open_url(exec, url)
+        .map_err(|e| format!("URL açılamadı: {}", e))?;
+    Ok(format!("{} açıldı", url))
+}
+
+fn select_all(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 'a')?;
+    Ok("Tümü seçildi".into())
+}
+
+fn copy(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 'c')?;
+    Ok("Kopyalandı".into())
+}
+
+fn paste(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 'v')?;
+    Ok("Yapıştırıldı".into())
+}
+
+fn undo(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 'z')?;
+    Ok("Geri alındı".into())
+}
+
+fn save(_: &Params, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().shortcut(exec, 's')?;
+    Ok("Kaydedildi".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::{Invocation, RecordingExecutor};
+    use crate::desktop::{Desktop, LinuxDesktop, MacDesktop, UnsupportedDesktop};
+
+    /// Komutu verilen masaüstünde çalıştır, mesajı ve yapılan çağrıları döndür
+    fn run(
+        name: &str,
+        params: &[(&str, &str)],
+        desktop: impl Desktop + 'static,
+    ) -> (Result<String, String>, Vec<Invocation>) {
+        let handler = find(name).unwrap_or_else(|| panic!("yerleşik komut yok: {}", name));
+        let params: Params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let exec = RecordingExecutor::dry_run().with_desktop(desktop);
+        let result = handler.execute(&params, &exec);
+        (result, exec.take())
+    }
+
+    fn mac(name: &str, params: &[(&str, &str)]) -> Vec<Invocation> {
+        let (result, calls) = run(name, params, MacDesktop);
+        result.unwrap();
+        calls
+    }
+
+    fn linux(name: &str, params: &[(&str, &str)]) -> Vec<Invocation> {
+        let (result, calls) = run(name, params, LinuxDesktop { wayland: false });
+        result.unwrap();
+        calls
+    }
+
+    fn osascript(script: &str) -> Invocation {
+        Invocation::run("osascript", &["-e", script])
+    }
+
+    fn keys(combo: &str) -> Invocation {
+        Invocation::Keys {
+            keys: vec![combo.into()],
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names: Vec<String> = handlers().into_iter().map(|h| h.info.name).collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn mac_invocations() {
+        assert_eq!(
+            mac("open_app", &[("app", "Safari")]),
+            [Invocation::spawn("open", &["-a", "Safari"])]
+        );
+        assert_eq!(
+            mac("open_url", &[("url", "https://example.com")]),
+            [Invocation::spawn("open", &["https://example.com"])]
+        );
+        assert_eq!(
+            mac("volume_up", &[]),
+            [osascript(
+                "set volume output volume ((output volume of (get volume settings)) + 10)"
+            )]
+        );
+        assert_eq!(
+            mac("volume_down", &[]),
+            [osascript(
+                "set volume output volume ((output volume of (get volume settings)) + -10)"
+            )]
+        );
+        assert_eq!(
+            mac("mute", &[]),
+            [osascript(
+                "set volume output muted not (output muted of (get volume settings))"
+            )]
+        );
+        assert_eq!(
+            mac("brightness_up", &[]),
+            [osascript(
+                "tell application \"System Events\" to key code 144"
+            )]
+        );
+        assert_eq!(
+            mac("brightness_down", &[]),
+            [osascript(
+                "tell application \"System Events\" to key code 145"
+            )]
+        );
+        assert_eq!(
+            mac("lock_screen", &[]),
+            [Invocation::spawn("pmset", &["displaysleepnow"])]
+        );
+        // Durum boş okunursa Wi-Fi kapalı sayılır → açılır
+        assert_eq!(
+            mac("wifi_toggle", &[]),
+            [
+                Invocation::run("networksetup", &["-getairportpower", "en0"]),
+                Invocation::spawn("networksetup", &["-setairportpower", "en0", "on"]),
+            ]
+        );
+        assert_eq!(
+            mac("new_tab", &[]),
+            [osascript(
+                "tell application \"System Events\" to keystroke \"t\" using command down"
+            )]
+        );
+        assert_eq!(
+            mac("play_pause", &[]),
+            [Invocation::MediaKey {
+                key: MediaKey::PlayPause
+            }]
+        );
+        assert_eq!(
+            mac("prev_track", &[]),
+            [Invocation::MediaKey {
+                key: MediaKey::Previous
+            }]
+        );
+    }
+
+    #[test]
+    fn linux_invocations() {
+        // Kaydı olmayan ad program olarak çalıştırılmaz, yalnızca gtk-launch'a sorulur
+        assert_eq!(
+            linux("open_app", &[("app", "millow-yok; rm -rf ~")]),
+            [Invocation::run("gtk-launch", &["millow-yok; rm -rf ~"])]
+        );
+        assert_eq!(
+            linux("open_url", &[("url", "https://example.com")]),
+            [Invocation::spawn("xdg-open", &["https://example.com"])]
+        );
+        // İlk aday başarılıysa yedeğe geçilmez
+        assert_eq!(
+            linux("volume_up", &[]),
+            [Invocation::run(
+                "wpctl",
+                &["set-volume", "-l", "1.0", "@DEFAULT_AUDIO_SINK@", "10%+"]
+            )]
+        );
+        assert_eq!(
+            linux("volume_down", &[]),
+            [Invocation::run(
+                "wpctl",
+                &["set-volume", "-l", "1.0", "@DEFAULT_AUDIO_SINK@", "10%-"]
+            )]
+        );
+        assert_eq!(
+            linux("mute", &[]),
+            [Invocation::run(
+                "wpctl",
+                &["set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"]
+            )]
+        );
+        assert_eq!(
+            linux("brightness_down", &[]),
+            [Invocation::run("brightnessctl", &["set", "10%-"])]
+        );
+        assert_eq!(
+            linux("lock_screen", &[]),
+            [Invocation::run("loginctl", &["lock-session"])]
+        );
+        assert_eq!(
+            linux("wifi_toggle", &[]),
+            [
+                Invocation::run("nmcli", &["radio", "wifi"]),
+                Invocation::run("nmcli", &["radio", "wifi", "on"]),
+            ]
+        );
+        assert_eq!(
+            linux("bluetooth_toggle", &[]),
+            [Invocation::spawn("gnome-control-center", &["bluetooth"])]
+        );
+        assert_eq!(linux("close_tab", &[]), [keys("ctrl+w")]);
+        assert_eq!(linux("save", &[]), [keys("ctrl+s")]);
+
+        let dark = linux("dark_mode", &[]);
+        assert_eq!(dark.len(), 2);
+        assert_eq!(
+            dark[1],
+            Invocation::run(
+                "gsettings",
+                &[
+                    "set",
+                    "org.gnome.desktop.interface",
+                    "color-scheme",
+                    "prefer-dark"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn screenshot_backend_follows_session() {
+        let program = |calls: Vec<Invocation>| match &calls[..] {
+            [Invocation::Run { program, args }] => {
+                assert!(args.last().unwrap().ends_with(".png"));
+                program.clone()
+            }
+            [Invocation::Spawn { program, args }] => {
+                assert_eq!(args[0], "-x");
+                program.clone()
+            }
+            other => panic!("beklenmeyen çağrılar: {:?}", other),
+        };
+        assert_eq!(program(mac("screenshot", &[])), "screencapture");
+        assert_eq!(program(linux("screenshot", &[])), "gnome-screenshot");
+        let (result, calls) = run("screenshot", &[], LinuxDesktop { wayland: true });
+        assert!(result.is_ok());
+        assert_eq!(program(calls), "grim");
+    }
+
+    #[test]
+    fn every_builtin_runs_on_both_desktops() {
+        for handler in handlers() {
+            let name = handler.info.name.clone();
+            let params: Vec<(String, String)> = handler
+                .info
+                .params
+                .iter()
+                .map(|(slot, rule)| (slot.clone(), rule.default.clone().unwrap_or_default()))
+                .collect();
+            let params: Vec<(&str, &str)> = params
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            let (result, calls) = run(&name, &params, MacDesktop);
+            assert!(result.is_ok(), "macOS {}: {:?}", name, result);
+            assert!(!calls.is_empty(), "macOS {}", name);
+            let (result, calls) = run(&name, &params, LinuxDesktop { wayland: true });
+            assert!(result.is_ok(), "Linux {}: {:?}", name, result);
+            assert!(!calls.is_empty(), "Linux {}", name);
+        }
+    }
+
+    #[test]
+    fn unsupported_platform_reports_error() {
+        let (result, calls) = run("volume_up", &[], UnsupportedDesktop);
+        assert!(result.unwrap_err().contains("desteklenmiyor"));
+        assert!(calls.is_empty());
+    }
+}
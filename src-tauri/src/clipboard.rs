@@ -0,0 +1,144 @@
+// Millow — Pano (NSPasteboard) Yönetimi
+// Yapıştırma öncesi panodaki tüm öğe/türleri (resim, dosya, zengin metin…) yedekler, sonra geri yükler
+
+use cocoa::base::{id, nil, BOOL};
+use cocoa::foundation::{NSAutoreleasePool, NSInteger, NSString, NSUInteger};
+use objc::{class, msg_send, sel, sel_impl};
+use std::ffi::CStr;
+
+/// Düz metin türü
+const TYPE_STRING: &str = "public.utf8-plain-text";
+/// Pano yöneticilerine "geçici, geçmişe ekleme" işareti (nspasteboard.org)
+const TYPE_TRANSIENT: &str = "org.nspasteboard.TransientType";
+
+/// Panodaki bir öğe: (tür, ham veri) listesi
+type PasteboardItem = Vec<(String, Vec<u8>)>;
+
+/// Panonun tam anlık görüntüsü
+pub struct ClipboardSnapshot {
+    items: Vec<PasteboardItem>,
+}
+
+/// Panonun değişim sayacı — her yazmada artar
+pub fn change_count() -> i64 {
+    unsafe {
+        let pb: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let count: NSInteger = msg_send![pb, changeCount];
+        count as i64
+    }
+}
+
+/// Panodaki tüm öğeleri ve türlerini yedekle
+pub fn snapshot() -> ClipboardSnapshot {
+    let mut items = Vec::new();
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+        let pb: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let pb_items: id = msg_send![pb, pasteboardItems];
+        if pb_items != nil {
+            let item_count: NSUInteger = msg_send![pb_items, count];
+            for i in 0..item_count {
+                let item: id = msg_send![pb_items, objectAtIndex: i];
+                let types: id = msg_send![item, types];
+                let type_count: NSUInteger = msg_send![types, count];
+                let mut entries = Vec::new();
+                for j in 0..type_count {
+                    let ty: id = msg_send![types, objectAtIndex: j];
+                    let data: id = msg_send![item, dataForType: ty];
+                    if data == nil {
+                        continue;
+                    }
+                    let len: NSUInteger = msg_send![data, length];
+                    let bytes: *const u8 = msg_send![data, bytes];
+                    let buf = if len == 0 || bytes.is_null() {
+                        Vec::new()
+                    } else {
+                        std::slice::from_raw_parts(bytes, len as usize).to_vec()
+                    };
+                    entries.push((ns_to_string(ty), buf));
+                }
+                if !entries.is_empty() {
+                    items.push(entries);
+                }
+            }
+        }
+        pool.drain();
+    }
+    ClipboardSnapshot { items }
+}
+
+/// Panodaki düz metin (yoksa None)
+pub fn text() -> Option<String> {
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+        let pb: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let value: id = msg_send![pb, stringForType: ns_string(TYPE_STRING)];
+        let text = (value != nil).then(|| ns_to_string(value));
+        pool.drain();
+        text
+    }
+}
+
+/// Panoya düz metin yaz, yeni değişim sayacını döndür.
+/// `transient` ise pano yöneticilerine geçmişe eklememeleri söylenir (yapıştırma için geçici yazım).
+pub fn set_text(text: &str, transient: bool) -> Result<i64, String> {
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+        let pb: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let _: NSInteger = msg_send![pb, clearContents];
+        let value = ns_string(text);
+        let ok: BOOL = msg_send![pb, setString: value forType: ns_string(TYPE_STRING)];
+        if transient {
+            let _: BOOL = msg_send![pb, setString: ns_string("") forType: ns_string(TYPE_TRANSIENT)];
+        }
+        pool.drain();
+        if ok == cocoa::base::NO {
+            return Err("Panoya yazılamadı".into());
+        }
+    }
+    Ok(change_count())
+}
+
+/// Yedeği geri yükle. Pano `expected_change_count`'tan sonra değiştiyse
+/// (kullanıcı bu arada yeni bir şey kopyaladıysa) dokunma — false döner.
+pub fn restore(snapshot: &ClipboardSnapshot, expected_change_count: i64) -> bool {
+    if change_count() != expected_change_count {
+        println!("📋 Pano yapıştırma sırasında değişti, geri yükleme atlandı");
+        return false;
+    }
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+        let pb: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let _: NSInteger = msg_send![pb, clearContents];
+
+        if !snapshot.items.is_empty() {
+            let objects: id = msg_send![class!(NSMutableArray), array];
+            for entries in &snapshot.items {
+                let item: id = msg_send![class!(NSPasteboardItem), new];
+                let item: id = msg_send![item, autorelease];
+                for (ty, bytes) in entries {
+                    let data: id = msg_send![class!(NSData), dataWithBytes: bytes.as_ptr() length: bytes.len() as NSUInteger];
+                    let _: BOOL = msg_send![item, setData: data forType: ns_string(ty)];
+                }
+                let _: () = msg_send![objects, addObject: item];
+            }
+            let _: BOOL = msg_send![pb, writeObjects: objects];
+        }
+        pool.drain();
+    }
+    true
+}
+
+/// Autorelease edilmiş NSString (aktif bir havuz içinde çağrılmalı)
+unsafe fn ns_string(s: &str) -> id {
+    let value = NSString::alloc(nil).init_str(s);
+    msg_send![value, autorelease]
+}
+
+unsafe fn ns_to_string(value: id) -> String {
+    let ptr = NSString::UTF8String(value);
+    if ptr.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(ptr).to_string_lossy().into_owned()
+}
@@ -1,204 +1,2228 @@
 // Millow — Sesli Komut Yöneticisi
-// Gemini'den gelen komutları macOS'ta çalıştırır
+// Komut işleyicilerini (yerleşik ve kullanıcı tanımlı) tek kayıtta toplar, tetikleyici ifadeleri eşler ve çalıştırır
 
+use crate::config::MillowConfig;
+use crate::desktop::{self, Desktop};
+use crate::media::{self, MediaKey};
+use crate::policy::{self, CommandPolicy, PendingCommand, Verdict};
+use crate::timers::{self, TimerOp};
+use crate::{assistant, builtins};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
 
-/// Komut çalıştır ve sonucu döndür
-pub fn execute_command(action: &str, params: Option<&str>) -> Result<String, String> {
+/// HTTP komutlarının ve LLM isteklerinin zaman aşımı
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tuş dizisindeki adımlar arası bekleme
+const KEY_STEP_DELAY: Duration = Duration::from_millis(30);
+
+/// Uygulamanın öne gelmesini beklerken kontroller arası süre
+const APP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Eşleştirmeden önce metnin başından/sonundan atılan noktalama
+const TRIM_PUNCT: &[char] = &['.', ',', '!', '?', ';', ':', '…', '"', '\'', '“', '”'];
+
+// ── Komut Arayüzü ──
+
+/// Doldurulmuş ve doğrulanmış yuva değerleri
+pub type Params = HashMap<String, String>;
+
+/// Komut meta verisi — ön yüz ve niyet eşleştirici komutları buradan listeler
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandInfo {
+    /// Komut adı (modelin döndürdüğü `action`)
+    pub name: String,
+    pub description: String,
+    /// Tetikleyici ifadeler — "{dakika}" gibi yuvalar içerebilir
+    pub triggers: Vec<String>,
+    /// Parametre şeması: yuva adı → doğrulama kuralı (kuralı olmayan yuva serbest metindir)
+    pub params: BTreeMap<String, SlotRule>,
+    /// Çalıştığı işletim sistemleri ("macos", "linux"…); boşsa hepsi
+    pub platforms: Vec<String>,
+    /// Geri alınması zor bir etkisi var mı (ekran kilidi, Wi-Fi, sekme kapatma…)
+    pub destructive: bool,
+    /// config'te kullanıcı tarafından tanımlandı mı
+    pub custom: bool,
+    /// Bu platformda çalışabilir mi (listelerken doldurulur)
+    pub available: bool,
+}
+
+/// Sesli komut işleyicisi
+pub trait CommandHandler: Send + Sync {
+    fn info(&self) -> &CommandInfo;
+
+    /// Komutu çalıştır — dış dünyaya yapılan tüm çağrılar `exec` üzerinden geçer
+    fn execute(&self, params: &Params, exec: &dyn Executor) -> Result<String, String>;
+}
+
+impl CommandInfo {
+    /// Geçerli işletim sisteminde çalışabilir mi
+    pub fn supported(&self) -> bool {
+        self.platforms.is_empty() || self.platforms.iter().any(|p| p == std::env::consts::OS)
+    }
+
+    /// Tetikleyicilerde ve şemada geçen yuva adları (sıralı)
+    pub fn slot_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .triggers
+            .iter()
+            .filter_map(|trigger| parse_trigger(trigger).ok())
+            .flatten()
+            .filter_map(|part| match part {
+                Part::Slot(name) => Some(name),
+                Part::Literal(_) => None,
+            })
+            .collect();
+        names.extend(self.params.keys().cloned());
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Modelin `params` alanından yuva değerleri: JSON nesnesi ya da tek yuvanın değeri
+    fn slots_from_params(&self, params: Option<&str>) -> Params {
+        let Some(params) = params.map(str::trim).filter(|p| !p.is_empty()) else {
+            return Params::new();
+        };
+        if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(params) {
+            return map
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => (key, s),
+                    other => (key, other.to_string()),
+                })
+                .collect();
+        }
+        match self.slot_names().as_slice() {
+            [only] => Params::from([(only.clone(), params.to_string())]),
+            _ => Params::new(),
+        }
+    }
+
+    /// Eksik yuvaları varsayılanla doldur, tümünü kurallarla doğrula
+    fn resolve(&self, mut values: Params) -> Result<Params, String> {
+        let mut resolved = Params::new();
+        for name in self.slot_names() {
+            let rule = self.params.get(&name).cloned().unwrap_or_default();
+            let Some(value) = values.remove(&name).or_else(|| rule.default.clone()) else {
+                return Err(format!("{}: '{}' parametresi eksik", self.name, name));
+            };
+            let value = rule
+                .check(&name, &value)
+                .map_err(|e| format!("{}: {}", self.name, e))?;
+            resolved.insert(name, value);
+        }
+        Ok(resolved)
+    }
+}
+
+// ── Yürütücü ──
+
+/// Komutun dış dünyaya yaptığı tek çağrı
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Invocation {
+    /// Programı çalıştır ve bitmesini bekle
+    Run { program: String, args: Vec<String> },
+    /// Programı arka planda başlat
+    Spawn { program: String, args: Vec<String> },
+    /// Tuş dizisi gönder ("cmd+t", "enter", "text:…")
+    Keys { keys: Vec<String> },
+    /// HTTP isteği
+    Http {
+        method: String,
+        url: String,
+        headers: BTreeMap<String, String>,
+        body: Option<String>,
+    },
+    /// Bekle
+    Sleep { ms: u64 },
+    /// Uygulama öne gelene kadar bekle
+    WaitForApp { app: String, timeout_ms: u64 },
+    /// Uygulamanın durumunu sor: "frontmost", "running" ya da "stopped"
+    AppState { app: String },
+    /// Zamanlayıcı kaydını değiştir ya da listele
+    Timer { op: TimerOp },
+    /// Sistem medya tuşuna bas
+    MediaKey { key: MediaKey },
+}
+
+impl Invocation {
+    pub fn run(program: &str, args: &[&str]) -> Self {
+        Self::Run {
+            program: program.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    pub fn spawn(program: &str, args: &[&str]) -> Self {
+        Self::Spawn {
+            program: program.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Invocation {
+    /// Kabukta yazılacağı biçimde: `osascript -e 'set volume …'`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Run { program, args } => f.write_str(&shell_line(program, args)),
+            Self::Spawn { program, args } => write!(f, "{} &", shell_line(program, args)),
+            Self::Keys { keys } => write!(f, "tuşlar: {}", keys.join(", ")),
+            Self::Http {
+                method, url, body, ..
+            } => {
+                write!(f, "{} {}", method, url)?;
+                if let Some(body) = body {
+                    write!(f, " {}", body)?;
+                }
+                Ok(())
+            }
+            Self::Sleep { ms } => write!(f, "bekle {} ms", ms),
+            Self::WaitForApp { app, timeout_ms } => {
+                write!(
+                    f,
+                    "'{}' öne gelene kadar bekle (en fazla {} ms)",
+                    app, timeout_ms
+                )
+            }
+            Self::AppState { app } => write!(f, "'{}' durumu", app),
+            Self::Timer { op } => write!(f, "{}", op),
+            Self::MediaKey { key } => write!(f, "medya tuşu: {}", key.label()),
+        }
+    }
+}
+
+/// Komutların çağrılarını yapan yürütücü — testlerde çağrıları kaydeden bir sahtesiyle değiştirilebilir
+pub trait Executor: Send + Sync {
+    /// Çağrıyı yap; `Run` için standart çıktıyı, `Http` için yanıt gövdesini döndür
+    fn execute(&self, invocation: &Invocation) -> Result<String, String>;
+
+    /// Uzun süren adımlarda kullanıcıya ilerleme bildir
+    fn progress(&self, message: &str) {
+        println!("⏳ {}", message);
+    }
+
+    /// Çağrıları üretecek masaüstü arka ucu
+    fn desktop(&self) -> Arc<dyn Desktop> {
+        desktop::current().into()
+    }
+
+    /// Açılacak ya da istek yapılacak URL izinli mi — politikasız yürütücüde hepsi
+    fn check_url(&self, _url: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Başka bir komutun içinden çağrılan yerleşik komut izinli mi
+    fn check_command(&self, _info: &CommandInfo, _params: &Params) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Yürütücüyü komut politikasıyla saran katman — makro adımları ve şablondan doldurulan URL'ler de denetlenir
+struct Guarded<'a> {
+    inner: &'a dyn Executor,
+    policy: &'a CommandPolicy,
+}
+
+impl Executor for Guarded<'_> {
+    fn execute(&self, invocation: &Invocation) -> Result<String, String> {
+        self.inner.execute(invocation)
+    }
+
+    fn progress(&self, message: &str) {
+        self.inner.progress(message)
+    }
+
+    fn desktop(&self) -> Arc<dyn Desktop> {
+        self.inner.desktop()
+    }
+
+    fn check_url(&self, url: &str) -> Result<(), String> {
+        self.policy.check_url(url)
+    }
+
+    /// Onay üst komutta istenir (yıkıcılık iç içe komutlardan devralınır); burada yalnızca yasaklar
+    fn check_command(&self, info: &CommandInfo, params: &Params) -> Result<(), String> {
+        match self.policy.check(info, params) {
+            Verdict::Deny(reason) => Err(reason),
+            Verdict::Allow | Verdict::Confirm => Ok(()),
+        }
+    }
+}
+
+/// Süreçleri, tuşları ve HTTP isteklerini gerçekten çalıştıran yürütücü
+pub struct SystemExecutor;
+
+impl Executor for SystemExecutor {
+    fn progress(&self, message: &str) {
+        println!("⏳ {}", message);
+        crate::notify("⏳ Millow", message);
+    }
+
+    fn execute(&self, invocation: &Invocation) -> Result<String, String> {
+        println!("⚙️  {}", invocation);
+        match invocation {
+            Invocation::Run { program, args } => {
+                let output = Command::new(program)
+                    .args(args)
+                    .output()
+                    .map_err(|e| e.to_string())?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(format!(
+                        "{} başarısız ({}): {}",
+                        program,
+                        output.status,
+                        stderr.trim()
+                    ));
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            }
+            Invocation::Spawn { program, args } => {
+                Command::new(program)
+                    .args(args)
+                    .spawn()
+                    .map_err(|e| e.to_string())?;
+                Ok(String::new())
+            }
+            Invocation::Keys { keys } => send_keys(keys).map(|_| String::new()),
+            Invocation::Http {
+                method,
+                url,
+                headers,
+                body,
+            } => http_request(method, url, headers, body.as_deref()),
+            Invocation::Sleep { ms } => {
+                std::thread::sleep(Duration::from_millis(*ms));
+                Ok(String::new())
+            }
+            Invocation::WaitForApp { app, timeout_ms } => {
+                wait_for_app(app, Duration::from_millis(*timeout_ms)).map(|_| String::new())
+            }
+            Invocation::AppState { app } => app_state(app),
+            Invocation::Timer { op } => timers::apply(op),
+            Invocation::MediaKey { key } => media::post_key(*key).map(|_| String::new()),
+        }
+    }
+}
+
+/// Deneme modunda pano okuyan komutlara verilen örnek metin
+const DRY_RUN_CLIPBOARD: &str = "(deneme: pano içeriği)";
+
+/// Deneme modunda LLM isteklerine verilen örnek cevap
+const DRY_RUN_COMPLETION: &str =
+    r#"{"choices":[{"message":{"content":"(deneme: model cevabı)"}}]}"#;
+
+/// Çağrıları çalıştırmadan kaydeden yürütücü (deneme modu) — hazır cevabı olmayan çağrı boş çıktıyla başarılı sayılır
+#[derive(Default)]
+pub struct RecordingExecutor {
+    invocations: Mutex<Vec<Invocation>>,
+    /// Boşsa bu platformun arka ucu
+    desktop: Option<Arc<dyn Desktop>>,
+    /// (çağrının kabuk yazımında geçen parça, çıktı) — ilk eşleşen kullanılır
+    answers: Vec<(String, String)>,
+}
+
+impl RecordingExecutor {
+    /// Deneme modu: pano okumaları, LLM istekleri ve MPRIS sorguları örnek cevap alır
+    pub fn dry_run() -> Self {
+        let mut exec = Self::default();
+        for reader in [
+            "pbpaste",
+            "wl-paste",
+            "xclip -selection clipboard -o",
+            "xsel --clipboard --output",
+        ] {
+            exec = exec.answer(reader, DRY_RUN_CLIPBOARD);
+        }
+        exec.answer("/chat/completions", DRY_RUN_COMPLETION)
+            .answer(
+                "org.freedesktop.DBus.ListNames",
+                "array [\n   string \"org.mpris.MediaPlayer2.deneme\"\n]",
+            )
+            .answer("string:PlaybackStatus", "variant string \"Playing\"")
+    }
+
+    /// Kabuk yazımında `pattern` geçen çağrılar `output` döndürsün
+    pub fn answer(mut self, pattern: &str, output: &str) -> Self {
+        self.answers.push((pattern.into(), output.into()));
+        self
+    }
+
+    /// Çağrıları başka bir platformun arka ucuyla üret (ör. Linux'ta macOS çağrıları)
+    #[cfg(test)]
+    pub fn with_desktop(mut self, desktop: impl Desktop + 'static) -> Self {
+        self.desktop = Some(Arc::new(desktop));
+        self
+    }
+
+    /// Kaydedilen çağrıları al ve kaydı boşalt
+    pub fn take(&self) -> Vec<Invocation> {
+        std::mem::take(&mut *self.invocations.lock())
+    }
+}
+
+impl Executor for RecordingExecutor {
+    fn execute(&self, invocation: &Invocation) -> Result<String, String> {
+        self.invocations.lock().push(invocation.clone());
+        let line = invocation.to_string();
+        Ok(self
+            .answers
+            .iter()
+            .find(|(pattern, _)| line.contains(pattern.as_str()))
+            .map(|(_, output)| output.clone())
+            .unwrap_or_default())
+    }
+
+    fn desktop(&self) -> Arc<dyn Desktop> {
+        match &self.desktop {
+            Some(desktop) => Arc::clone(desktop),
+            None => desktop::current().into(),
+        }
+    }
+}
+
+// ── Kullanıcı Komutları ──
+
+/// config'teki kullanıcı komutu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoiceCommand {
+    /// Komut adı (modelin döndürdüğü `action`)
+    pub name: String,
+    /// Listelerde gösterilen kısa açıklama
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Tetikleyici ifadeler — "{dakika}" gibi yuvalar içerebilir
+    #[serde(default)]
+    pub triggers: Vec<String>,
+    /// Çalıştırılacak eylem
+    pub action: CommandAction,
+    /// Yuva doğrulama kuralları (kuralı olmayan yuva serbest metindir)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub slots: HashMap<String, SlotRule>,
+    /// Geri alınması zor bir etkisi var mı
+    #[serde(default)]
+    pub destructive: bool,
+}
+
+/// Kullanıcı komutu eylemi — şablonlardaki "{yuva}" değerleri eylem türüne göre kaçışlanarak yerleştirilir
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommandAction {
+    /// `sh -c` ile kabuk komutu (yuvalar tek tırnağa alınır)
+    Shell { command: String },
+    /// URL şablonunu aç (yuvalar URL kodlanır)
+    OpenUrl { url: String },
+    /// Tuş dizisi: "cmd+shift+t", "enter", "text:merhaba"
+    Keystrokes { keys: Vec<String> },
+    /// AppleScript (yalnızca macOS)
+    #[serde(rename = "applescript")]
+    AppleScript { script: String },
+    /// HTTP isteği (gövde JSON ise yuvalar JSON kaçışlanır)
+    Http {
+        #[serde(default = "default_http_method")]
+        method: String,
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: Option<String>,
+    },
+    /// Uygulamayı aç
+    OpenApp { app: String },
+    /// Uygulama öne gelene kadar bekle
+    WaitForApp {
+        app: String,
+        #[serde(default = "default_wait_timeout")]
+        timeout_ms: u64,
+    },
+    /// Metni tuş vuruşlarıyla yaz
+    TypeText { text: String },
+    /// Bekle
+    Wait { ms: u64 },
+    /// Yerleşik bir komutu çalıştır ("volume_mute", "open_url"…)
+    Command {
+        name: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        params: HashMap<String, String>,
+    },
+    /// Sırayla çalışan adımlar
+    Macro { steps: Vec<MacroStep> },
+}
+
+fn default_http_method() -> String {
+    "POST".into()
+}
+
+fn default_wait_timeout() -> u64 {
+    5000
+}
+
+/// Makro adımı: bir eylem, öncesinde bekleme ve koşul
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MacroStep {
+    #[serde(flatten)]
+    pub action: CommandAction,
+    /// Adımdan önce beklenecek süre (ms)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub delay_ms: u64,
+    /// Sağlanmazsa adım atlanır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<StepCondition>,
+    /// Adım hata verirse makro durmadan devam eder
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub continue_on_error: bool,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// Adım koşulu — `{"running": "zoom.us"}` gibi; uygulama adı yuva içerebilir
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepCondition {
+    Frontmost(String),
+    NotFrontmost(String),
+    Running(String),
+    NotRunning(String),
+}
+
+impl StepCondition {
+    fn app(&self) -> &str {
+        match self {
+            Self::Frontmost(app)
+            | Self::NotFrontmost(app)
+            | Self::Running(app)
+            | Self::NotRunning(app) => app,
+        }
+    }
+
+    /// Koşul sağlanıyor mu — durum bilinmiyorsa (deneme modu) sağlanmış sayılır
+    fn holds(&self, params: &Params, exec: &dyn Executor) -> Result<bool, String> {
+        let app = fill(self.app(), params, str::to_string);
+        let state = exec.execute(&Invocation::AppState { app })?;
+        if state.is_empty() {
+            return Ok(true);
+        }
+        let frontmost = state == "frontmost";
+        let running = frontmost || state == "running";
+        Ok(match self {
+            Self::Frontmost(_) => frontmost,
+            Self::NotFrontmost(_) => !frontmost,
+            Self::Running(_) => running,
+            Self::NotRunning(_) => !running,
+        })
+    }
+}
+
+/// Yuva değer türü
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotKind {
+    #[default]
+    Text,
+    Number,
+    Integer,
+    Url,
+}
+
+/// Yuva doğrulama kuralı
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SlotRule {
+    #[serde(default)]
+    pub kind: SlotKind,
+    /// Sayısal alt sınır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Sayısal üst sınır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// İzin verilen değerler (boşsa serbest)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    /// Söylenmezse kullanılacak değer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl SlotRule {
+    /// Değeri doğrula ve normalleştir ("5,5" → "5.5")
+    pub fn check(&self, slot: &str, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(format!("'{}' boş olamaz", slot));
+        }
+        if !self.choices.is_empty() {
+            let lower = value.to_lowercase();
+            return self
+                .choices
+                .iter()
+                .find(|choice| choice.to_lowercase() == lower)
+                .cloned()
+                .ok_or_else(|| {
+                    format!(
+                        "'{}' için geçersiz değer: {} (seçenekler: {})",
+                        slot,
+                        value,
+                        self.choices.join(", ")
+                    )
+                });
+        }
+
+        match self.kind {
+            SlotKind::Text => Ok(value.to_string()),
+            SlotKind::Number | SlotKind::Integer => {
+                let number: f64 = value
+                    .replace(',', ".")
+                    .parse()
+                    .map_err(|_| format!("'{}' sayı olmalı: {}", slot, value))?;
+                if self.kind == SlotKind::Integer && number.fract() != 0.0 {
+                    return Err(format!("'{}' tam sayı olmalı: {}", slot, value));
+                }
+                if self.min.is_some_and(|min| number < min)
+                    || self.max.is_some_and(|max| number > max)
+                {
+                    let bound =
+                        |b: Option<f64>| b.map(|v| v.to_string()).unwrap_or_else(|| "…".into());
+                    return Err(format!(
+                        "'{}' {}–{} aralığında olmalı: {}",
+                        slot,
+                        bound(self.min),
+                        bound(self.max),
+                        value
+                    ));
+                }
+                Ok(number.to_string())
+            }
+            SlotKind::Url => {
+                // "google.com" gibi şemasız adresler https sayılır; şema kuralları politikada
+                let url = if value.contains("://") {
+                    value.to_string()
+                } else {
+                    format!("https://{}", value)
+                };
+                reqwest::Url::parse(&url)
+                    .map_err(|e| format!("'{}' geçerli bir URL değil: {}", slot, e))?;
+                Ok(url)
+            }
+        }
+    }
+}
+
+impl VoiceCommand {
+    /// İşleyici meta verisi
+    pub fn info(&self) -> CommandInfo {
+        CommandInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            triggers: self.triggers.clone(),
+            params: self.slots.clone().into_iter().collect(),
+            platforms: self.action.platforms(),
+            destructive: self.destructive || self.action.destructive(),
+            custom: true,
+            available: false,
+        }
+    }
+
+    /// Kullanıcı komutunu doğrula: ad, tetikleyiciler, kurallar ve şablonlardaki yuvalar
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.contains(char::is_whitespace) {
+            return Err(format!(
+                "Komut adı boş olamaz ve boşluk içeremez: '{}'",
+                self.name
+            ));
+        }
+        let fail = |e: String| format!("{}: {}", self.name, e);
+        if self.triggers.is_empty() {
+            return Err(fail("en az bir tetikleyici ifade gerekli".into()));
+        }
+        for trigger in &self.triggers {
+            parse_trigger(trigger).map_err(fail)?;
+        }
+        for (slot, rule) in &self.slots {
+            if let (Some(min), Some(max)) = (rule.min, rule.max) {
+                if min > max {
+                    return Err(fail(format!("'{}' için min, max'tan büyük", slot)));
+                }
+            }
+            if let Some(default) = &rule.default {
+                rule.check(slot, default).map_err(fail)?;
+            }
+        }
+
+        let known = self.info().slot_names();
+        for template in self.action.templates() {
+            if let Some(unknown) = placeholders(template)
+                .into_iter()
+                .find(|p| !known.contains(p))
+            {
+                return Err(fail(format!(
+                    "şablondaki {{{}}} yuvası hiçbir tetikleyicide yok",
+                    unknown
+                )));
+            }
+        }
+
+        self.action.validate().map_err(fail)
+    }
+}
+
+impl CommandAction {
+    /// Eylemin kendi alanlarını doğrula
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Shell { command } if command.trim().is_empty() => Err("kabuk komutu boş".into()),
+            Self::OpenUrl { url } if url.trim().is_empty() => Err("URL boş".into()),
+            Self::AppleScript { script } if script.trim().is_empty() => {
+                Err("AppleScript boş".into())
+            }
+            Self::Keystrokes { keys } => {
+                if keys.is_empty() {
+                    return Err("tuş dizisi boş".into());
+                }
+                for key in keys {
+                    parse_key_step(key)?;
+                }
+                Ok(())
+            }
+            Self::Http { method, url, .. } => {
+                reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| format!("geçersiz HTTP yöntemi: {}", method))?;
+                if url.trim().is_empty() {
+                    return Err("URL boş".into());
+                }
+                Ok(())
+            }
+            Self::OpenApp { app } | Self::WaitForApp { app, .. } if app.trim().is_empty() => {
+                Err("uygulama adı boş".into())
+            }
+            Self::TypeText { text } if text.is_empty() => Err("yazılacak metin boş".into()),
+            Self::Command { name, .. } => match builtins::find(name) {
+                Some(_) => Ok(()),
+                None => Err(format!("'{}' yerleşik bir komut değil", name)),
+            },
+            Self::Macro { steps } => {
+                if steps.is_empty() {
+                    return Err("makroda en az bir adım gerekli".into());
+                }
+                for (i, step) in steps.iter().enumerate() {
+                    if matches!(step.action, Self::Macro { .. }) {
+                        return Err(format!("{}. adım: makro içinde makro olamaz", i + 1));
+                    }
+                    step.action
+                        .validate()
+                        .map_err(|e| format!("{}. adım: {}", i + 1, e))?;
+                }
+                let platforms = steps.iter().map(|step| step.action.platforms());
+                if common_platforms(platforms).is_some_and(|common| common.is_empty()) {
+                    return Err("adımların hepsinin çalışabildiği bir işletim sistemi yok".into());
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Yuva içerebilen şablonlar
+    fn templates(&self) -> Vec<&str> {
+        match self {
+            Self::Shell { command } => vec![command],
+            Self::OpenUrl { url } => vec![url],
+            Self::Keystrokes { keys } => keys.iter().map(String::as_str).collect(),
+            Self::AppleScript { script } => vec![script],
+            Self::Http {
+                url, headers, body, ..
+            } => {
+                let mut templates: Vec<&str> = vec![url];
+                templates.extend(headers.values().map(String::as_str));
+                templates.extend(body.as_deref());
+                templates
+            }
+            Self::OpenApp { app } | Self::WaitForApp { app, .. } => vec![app],
+            Self::TypeText { text } => vec![text],
+            Self::Wait { .. } => Vec::new(),
+            Self::Command { params, .. } => params.values().map(String::as_str).collect(),
+            Self::Macro { steps } => steps
+                .iter()
+                .flat_map(|step| {
+                    let mut templates = step.action.templates();
+                    templates.extend(step.when.as_ref().map(StepCondition::app));
+                    templates
+                })
+                .collect(),
+        }
+    }
+
+    /// Çalıştığı işletim sistemleri (boşsa hepsi)
+    fn platforms(&self) -> Vec<String> {
+        match self {
+            Self::AppleScript { .. } => vec!["macos".into()],
+            Self::OpenUrl { .. } | Self::OpenApp { .. } => {
+                desktop::PLATFORMS.iter().map(|p| p.to_string()).collect()
+            }
+            Self::Command { name, .. } => builtins::find(name)
+                .map(|builtin| builtin.info().platforms.clone())
+                .unwrap_or_default(),
+            // Boş kesişim doğrulamada reddedilir — burada boş liste yalnızca "kısıtsız" demektir
+            Self::Macro { steps } => {
+                common_platforms(steps.iter().map(|step| step.action.platforms()))
+                    .unwrap_or_default()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Geri alınması zor bir yerleşik komutu çalıştırıyor mu
+    fn destructive(&self) -> bool {
+        match self {
+            Self::Command { name, .. } => {
+                builtins::find(name).is_some_and(|builtin| builtin.info().destructive)
+            }
+            Self::Macro { steps } => steps.iter().any(|step| step.action.destructive()),
+            _ => false,
+        }
+    }
+}
+
+/// Kısıtlı platform listelerinin kesişimi: hepsinde çalışan sistemler.
+/// Hiçbir liste kısıtlı değilse None (her yerde çalışır), Some(boş) hiçbir yerde çalışmaz.
+fn common_platforms(lists: impl IntoIterator<Item = Vec<String>>) -> Option<Vec<String>> {
+    lists
+        .into_iter()
+        .filter(|platforms| !platforms.is_empty())
+        .reduce(|a, b| a.into_iter().filter(|p| b.contains(p)).collect())
+}
+
+/// Kullanıcı komutunu çalıştıran işleyici
+pub struct CustomHandler {
+    command: VoiceCommand,
+    info: CommandInfo,
+}
+
+impl CustomHandler {
+    pub fn new(command: VoiceCommand) -> Self {
+        let info = command.info();
+        Self { command, info }
+    }
+}
+
+impl CommandHandler for CustomHandler {
+    fn info(&self) -> &CommandInfo {
+        &self.info
+    }
+
+    fn execute(&self, params: &Params, exec: &dyn Executor) -> Result<String, String> {
+        run_action(&self.info.name, &self.command.action, params, exec)
+    }
+}
+
+/// Kullanıcı komutunun eylemini yuvaları doldurarak çalıştır
+fn run_action(
+    name: &str,
+    action: &CommandAction,
+    params: &Params,
+    exec: &dyn Executor,
+) -> Result<String, String> {
     match action {
-        // ── Uygulama Yönetimi ──
-        "open_app" => {
-            let app_name = params.unwrap_or("Finder");
-            Command::new("open")
-                .args(["-a", app_name])
-                .spawn()
-                .map_err(|e| format!("{} açılamadı: {}", app_name, e))?;
-            Ok(format!("{} açıldı", app_name))
-        }
-
-        // ── Ekran Görüntüsü ──
-        "screenshot" => {
-            let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            let path = format!("{}/Desktop/millow_screenshot_{}.png", home, timestamp);
-            Command::new("screencapture")
-                .args(["-x", &path])
-                .spawn()
-                .map_err(|e| format!("Ekran görüntüsü alınamadı: {}", e))?;
-            Ok(format!("Ekran görüntüsü kaydedildi: {}", path))
-        }
-
-        // ── Ses Kontrolleri ──
-        "volume_up" => {
-            run_osascript("set volume output volume ((output volume of (get volume settings)) + 10)")?;
-            Ok("Ses artırıldı".into())
-        }
-        "volume_down" => {
-            run_osascript("set volume output volume ((output volume of (get volume settings)) - 10)")?;
-            Ok("Ses azaltıldı".into())
-        }
-        "mute" => {
-            run_osascript("set volume output muted not (output muted of (get volume settings))")?;
-            Ok("Sessiz modu değiştirildi".into())
-        }
-
-        // ── Parlaklık ──
-        "brightness_up" => {
-            // macOS'ta parlaklık kontrolü için brightness komutu
-            run_osascript("tell application \"System Events\" to key code 144")?;
-            Ok("Parlaklık artırıldı".into())
-        }
-        "brightness_down" => {
-            run_osascript("tell application \"System Events\" to key code 145")?;
-            Ok("Parlaklık azaltıldı".into())
-        }
-
-        // ── Sistem ──
-        "dark_mode" => {
-            run_osascript(
-                "tell application \"System Events\" to tell appearance preferences to set dark mode to not dark mode",
-            )?;
-            Ok("Karanlık mod değiştirildi".into())
-        }
-        "lock_screen" => {
-            Command::new("pmset")
-                .arg("displaysleepnow")
-                .spawn()
-                .map_err(|e| format!("Ekran kilitlenemedi: {}", e))?;
-            Ok("Ekran kilitlendi".into())
-        }
-        "wifi_toggle" => {
-            // Wi-Fi durumunu kontrol et ve değiştir
-            let output = Command::new("networksetup")
-                .args(["-getairportpower", "en0"])
-                .output()
-                .map_err(|e| format!("Wi-Fi kontrolü başarısız: {}", e))?;
-            let status = String::from_utf8_lossy(&output.stdout);
-            let new_state = if status.contains("On") { "off" } else { "on" };
-            Command::new("networksetup")
-                .args(["-setairportpower", "en0", new_state])
-                .spawn()
-                .map_err(|e| format!("Wi-Fi değiştirilemedi: {}", e))?;
-            Ok(format!("Wi-Fi {}", if new_state == "on" { "açıldı" } else { "kapatıldı" }))
-        }
-        "bluetooth_toggle" => {
-            run_osascript(
-                r#"tell application "System Preferences" to reveal pane id "com.apple.preferences.Bluetooth""#,
-            )?;
-            Ok("Bluetooth ayarları açıldı".into())
-        }
-
-        // ── Medya Kontrol ──
-        "play_pause" => {
-            run_osascript("tell application \"System Events\" to key code 49 using {}")?;
-            // Alternatif: medya tuşu simülasyonu
-            Command::new("osascript")
-                .args(["-e", "tell application \"Music\" to playpause"])
-                .spawn()
-                .ok();
-            Ok("Oynat/Duraklat".into())
-        }
-        "next_track" => {
-            Command::new("osascript")
-                .args(["-e", "tell application \"Music\" to next track"])
-                .spawn()
-                .map_err(|e| format!("Sonraki şarkı başarısız: {}", e))?;
-            Ok("Sonraki şarkı".into())
-        }
-        "prev_track" => {
-            Command::new("osascript")
-                .args(["-e", "tell application \"Music\" to previous track"])
-                .spawn()
-                .map_err(|e| format!("Önceki şarkı başarısız: {}", e))?;
-            Ok("Önceki şarkı".into())
-        }
-
-        // ── Tarayıcı ──
-        "new_tab" => {
-            run_osascript(
-                r#"tell application "System Events" to keystroke "t" using command down"#,
-            )?;
-            Ok("Yeni sekme açıldı".into())
-        }
-        "close_tab" => {
-            run_osascript(
-                r#"tell application "System Events" to keystroke "w" using command down"#,
-            )?;
-            Ok("Sekme kapatıldı".into())
-        }
-        "open_url" => {
-            let url = params.unwrap_or("https://google.com");
-            Command::new("open")
-                .arg(url)
-                .spawn()
+        CommandAction::Shell { command } => {
+            let (script, values) = shell_script(command, params);
+            let mut args = vec!["-c", script.as_str(), "millow"];
+            args.extend(values.iter().map(String::as_str));
+            let stdout = exec
+                .execute(&Invocation::run("sh", &args))
+                .map_err(|e| format!("{}: {}", name, e))?;
+            Ok(if stdout.is_empty() {
+                format!("{} çalıştırıldı", name)
+            } else {
+                stdout
+            })
+        }
+        CommandAction::OpenUrl { url } => {
+            let url = fill(url, params, encode_url_component);
+            reqwest::Url::parse(&url).map_err(|e| format!("Geçersiz URL ({}): {}", url, e))?;
+            exec.check_url(&url)
+                .map_err(|e| format!("{}: {}", name, e))?;
+            exec.desktop()
+                .open_url(exec, &url)
                 .map_err(|e| format!("URL açılamadı: {}", e))?;
             Ok(format!("{} açıldı", url))
         }
+        CommandAction::Keystrokes { keys } => {
+            let keys = keys
+                .iter()
+                .map(|key| fill(key, params, str::to_string))
+                .collect();
+            exec.execute(&Invocation::Keys { keys })?;
+            Ok(format!("{} tuşları gönderildi", name))
+        }
+        CommandAction::AppleScript { script } => {
+            let script = fill(script, params, applescript_escape);
+            exec.execute(&Invocation::run("osascript", &["-e", &script]))
+                .map_err(|e| format!("AppleScript hatası: {}", e))?;
+            Ok(format!("{} çalıştırıldı", name))
+        }
+        CommandAction::Http {
+            method,
+            url,
+            headers,
+            body,
+        } => {
+            let url = fill(url, params, encode_url_component);
+            exec.check_url(&url)
+                .map_err(|e| format!("{}: {}", name, e))?;
+            let mut headers: BTreeMap<String, String> = headers
+                .iter()
+                .map(|(key, value)| (key.clone(), fill(value, params, str::to_string)))
+                .collect();
+            let body = body.as_deref().map(|body| {
+                if body.trim_start().starts_with(['{', '[']) {
+                    if !headers
+                        .keys()
+                        .any(|key| key.eq_ignore_ascii_case("content-type"))
+                    {
+                        headers.insert("Content-Type".into(), "application/json".into());
+                    }
+                    fill(body, params, json_escape)
+                } else {
+                    fill(body, params, str::to_string)
+                }
+            });
+            exec.execute(&Invocation::Http {
+                method: method.to_uppercase(),
+                url: url.clone(),
+                headers,
+                body,
+            })?;
+            Ok(format!("HTTP isteği gönderildi → {}", url))
+        }
+        CommandAction::OpenApp { app } => {
+            let app = fill(app, params, str::to_string);
+            exec.desktop()
+                .open_app(exec, &app)
+                .map_err(|e| format!("{} açılamadı: {}", app, e))?;
+            Ok(format!("{} açıldı", app))
+        }
+        CommandAction::WaitForApp { app, timeout_ms } => {
+            let app = fill(app, params, str::to_string);
+            exec.execute(&Invocation::WaitForApp {
+                app: app.clone(),
+                timeout_ms: *timeout_ms,
+            })?;
+            Ok(format!("{} önde", app))
+        }
+        CommandAction::TypeText { text } => {
+            let text = fill(text, params, str::to_string);
+            exec.execute(&Invocation::Keys {
+                keys: vec![format!("text:{}", text)],
+            })?;
+            Ok(format!("{} karakter yazıldı", text.chars().count()))
+        }
+        CommandAction::Wait { ms } => {
+            exec.execute(&Invocation::Sleep { ms: *ms })?;
+            Ok(format!("{} ms beklendi", ms))
+        }
+        CommandAction::Command {
+            name: builtin,
+            params: values,
+        } => {
+            let handler = builtins::find(builtin)
+                .ok_or_else(|| format!("'{}' yerleşik bir komut değil", builtin))?;
+            let values = values
+                .iter()
+                .map(|(key, value)| (key.clone(), fill(value, params, str::to_string)))
+                .collect();
+            let values = handler.info().resolve(values)?;
+            exec.check_command(handler.info(), &values)
+                .map_err(|e| format!("{}: {}", name, e))?;
+            handler.execute(&values, exec)
+        }
+        CommandAction::Macro { steps } => run_macro(name, steps, params, exec),
+    }
+}
 
-        // ── Metin İşlemleri (Kısayol Simülasyonu) ──
-        "select_all" => {
-            run_osascript(r#"tell application "System Events" to keystroke "a" using command down"#)?;
-            Ok("Tümü seçildi".into())
-        }
-        "copy" => {
-            run_osascript(r#"tell application "System Events" to keystroke "c" using command down"#)?;
-            Ok("Kopyalandı".into())
-        }
-        "paste" => {
-            run_osascript(r#"tell application "System Events" to keystroke "v" using command down"#)?;
-            Ok("Yapıştırıldı".into())
-        }
-        "undo" => {
-            run_osascript(r#"tell application "System Events" to keystroke "z" using command down"#)?;
-            Ok("Geri alındı".into())
-        }
-        "save" => {
-            run_osascript(r#"tell application "System Events" to keystroke "s" using command down"#)?;
-            Ok("Kaydedildi".into())
-        }
-
-        // ── Zamanlayıcı ──
-        "set_timer" => {
-            let minutes = params.unwrap_or("5");
-            let secs: u64 = minutes.parse::<u64>().unwrap_or(5) * 60;
-            std::thread::spawn(move || {
-                std::thread::sleep(std::time::Duration::from_secs(secs));
-                let _ = Command::new("osascript")
-                    .args([
-                        "-e",
-                        &format!(
-                            r#"display notification "⏰ {} dakika zamanlayıcı doldu!" with title "Millow""#,
-                            secs / 60
-                        ),
-                    ])
-                    .output();
-                // Ses çal
-                let _ = Command::new("afplay")
-                    .arg("/System/Library/Sounds/Glass.aiff")
-                    .output();
+/// Adımları sırayla çalıştır — koşulu tutmayan adım atlanır, `continue_on_error` olmayan hata makroyu durdurur
+fn run_macro(
+    name: &str,
+    steps: &[MacroStep],
+    params: &Params,
+    exec: &dyn Executor,
+) -> Result<String, String> {
+    let (mut done, mut skipped, mut failed) = (0, 0, 0);
+    for (i, step) in steps.iter().enumerate() {
+        if let Some(condition) = &step.when {
+            if !condition.holds(params, exec)? {
+                println!("⏭️  {}: {}. adım atlandı ({:?})", name, i + 1, condition);
+                skipped += 1;
+                continue;
+            }
+        }
+        if step.delay_ms > 0 {
+            exec.execute(&Invocation::Sleep { ms: step.delay_ms })?;
+        }
+        match run_action(name, &step.action, params, exec) {
+            Ok(_) => done += 1,
+            Err(e) if step.continue_on_error => {
+                println!(
+                    "⚠️  {}: {}. adım başarısız, devam ediliyor: {}",
+                    name,
+                    i + 1,
+                    e
+                );
+                failed += 1;
+            }
+            Err(e) => return Err(format!("{}: {}. adım başarısız: {}", name, i + 1, e)),
+        }
+    }
+
+    let mut summary = format!("{}: {} adım çalıştı", name, done);
+    if skipped > 0 {
+        summary.push_str(&format!(", {} atlandı", skipped));
+    }
+    if failed > 0 {
+        summary.push_str(&format!(", {} başarısız", failed));
+    }
+    Ok(summary)
+}
+
+// ── Komut Kaydı ──
+
+/// Komut işleyicilerinin kaydı — tetikleyicilerde kullanıcı komutları önce aranır
+pub struct CommandRegistry {
+    handlers: Vec<Box<dyn CommandHandler>>,
+    executor: Arc<dyn Executor>,
+    policy: CommandPolicy,
+    /// Komutlar çalıştırılmaz, yalnızca ne yapacakları bildirilir
+    dry_run: bool,
+}
+
+/// Komut çalıştırma sonucu
+#[derive(Debug)]
+pub enum Outcome {
+    /// Çalıştı — kullanıcıya gösterilecek mesaj
+    Done(String),
+    /// Geri alınması zor komut, kullanıcı onayı bekliyor
+    Confirm(PendingCommand),
+}
+
+/// Deneme modunda komutun yapacakları
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRun {
+    pub command: String,
+    /// Doldurulmuş ve doğrulanmış parametreler
+    pub params: BTreeMap<String, String>,
+    /// Dış dünyaya yapılacak çağrılar, sırasıyla
+    pub invocations: Vec<Invocation>,
+    /// Aynı çağrılar kabukta yazılacağı biçimde
+    pub commands: Vec<String>,
+    /// Komutun döndüreceği mesaj (çağrılar boş ya da örnek çıktı verdiğinde)
+    pub message: String,
+    /// Çalıştırılsaydı kullanıcı onayı beklenecek miydi
+    pub needs_confirmation: bool,
+}
+
+impl DryRun {
+    /// Bildirim için tek satırlık özet
+    pub fn summary(&self) -> String {
+        if self.commands.is_empty() {
+            format!("{}: çağrı yok → {}", self.command, self.message)
+        } else {
+            format!("{}: {}", self.command, self.commands.join("; "))
+        }
+    }
+}
+
+/// Deneme sonucunda gizli başlıkları (API anahtarları) gösterme
+fn redact(invocation: Invocation) -> Invocation {
+    match invocation {
+        Invocation::Http {
+            method,
+            url,
+            mut headers,
+            body,
+        } => {
+            for (name, value) in headers.iter_mut() {
+                if name.eq_ignore_ascii_case("authorization") {
+                    *value = "***".into();
+                }
+            }
+            Invocation::Http {
+                method,
+                url,
+                headers,
+                body,
+            }
+        }
+        other => other,
+    }
+}
+
+/// Tetikleyici eşleşmesi
+pub struct CommandMatch<'a> {
+    pub handler: &'a dyn CommandHandler,
+    /// Yuva değerleri (söylendiği yazımla)
+    pub slots: Params,
+}
+
+impl CommandRegistry {
+    /// Yerleşik komutlar ve config'teki kullanıcı komutları — geçersiz veya çakışanlar atlanır
+    pub fn new(custom: &[VoiceCommand]) -> Self {
+        Self::from_config(&MillowConfig {
+            custom_commands: custom.to_vec(),
+            ..MillowConfig::default()
+        })
+    }
+
+    pub fn from_config(config: &MillowConfig) -> Self {
+        let mut registry = Self {
+            handlers: Vec::new(),
+            executor: Arc::new(SystemExecutor),
+            policy: CommandPolicy::from_config(config),
+            dry_run: config.command_dry_run,
+        };
+        for handler in builtins::handlers() {
+            let _ = registry.register(Box::new(handler));
+        }
+        for handler in assistant::handlers(config) {
+            let _ = registry.register(Box::new(handler));
+        }
+        for handler in timers::handlers() {
+            let _ = registry.register(Box::new(handler));
+        }
+        for command in &config.custom_commands {
+            let registered = command
+                .validate()
+                .and_then(|_| registry.register(Box::new(CustomHandler::new(command.clone()))));
+            if let Err(e) = registered {
+                println!("⚠️  Komut atlandı: {}", e);
+            }
+        }
+        registry
+    }
+
+    /// İşleyici ekle — aynı adda bir komut varsa hata
+    pub fn register(&mut self, handler: Box<dyn CommandHandler>) -> Result<(), String> {
+        let name = &handler.info().name;
+        if let Some(existing) = self.get(name) {
+            return Err(if existing.info().custom {
+                format!("'{}' adı birden fazla komutta kullanılmış", name)
+            } else {
+                format!("'{}' yerleşik bir komutun adı", name)
             });
-            Ok(format!("{} dakika zamanlayıcı kuruldu", minutes))
         }
+        self.handlers.push(handler);
+        Ok(())
+    }
 
-        // ── AI Destekli Panodan İşleme ──
-        "translate_clipboard" | "rewrite_clipboard" | "summarize_clipboard" | "generate_code" => {
-            // Bu komutlar frontend tarafından özel olarak işlenecek
-            // Panodan metin al → Gemini'ye gönder → sonucu panoya koy
-            Ok(format!("ai_action:{}", action))
+    pub fn get(&self, name: &str) -> Option<&dyn CommandHandler> {
+        self.handlers
+            .iter()
+            .map(|handler| handler.as_ref())
+            .find(|handler| handler.info().name == name)
+    }
+
+    /// Tüm komutların meta verisi (`available` doldurulmuş)
+    pub fn list(&self) -> Vec<CommandInfo> {
+        self.handlers
+            .iter()
+            .map(|handler| {
+                let mut info = handler.info().clone();
+                info.available = info.supported();
+                info
+            })
+            .collect()
+    }
+
+    /// Bu platformda çalışabilen işleyiciler; kullanıcı komutları önce
+    fn available(&self) -> impl Iterator<Item = &dyn CommandHandler> {
+        let custom = self.handlers.iter().filter(|h| h.info().custom);
+        let builtin = self.handlers.iter().filter(|h| !h.info().custom);
+        custom
+            .chain(builtin)
+            .map(|handler| handler.as_ref())
+            .filter(|handler| handler.info().supported())
+    }
+
+    /// Metni tetikleyicilerle eşle — birden çok eşleşmede en uzun sabit metinli tetikleyici kazanır
+    /// ("sesi aç", "{app} aç"tan önce gelir)
+    pub fn match_phrase(&self, text: &str) -> Option<CommandMatch<'_>> {
+        let text = clean(text);
+        if text.is_empty() {
+            return None;
         }
+        let mut best: Option<(usize, CommandMatch)> = None;
+        for handler in self.available() {
+            for trigger in &handler.info().triggers {
+                let Ok(parts) = parse_trigger(trigger) else {
+                    continue;
+                };
+                let score: usize = parts
+                    .iter()
+                    .map(|part| match part {
+                        Part::Literal(chars) => chars.len(),
+                        Part::Slot(_) => 0,
+                    })
+                    .sum();
+                if best
+                    .as_ref()
+                    .is_some_and(|(best_score, _)| *best_score >= score)
+                {
+                    continue;
+                }
+                let mut slots = Params::new();
+                if match_parts(&parts, &text, 0, &mut slots) {
+                    best = Some((score, CommandMatch { handler, slots }));
+                }
+            }
+        }
+        best.map(|(_, found)| found)
+    }
+
+    /// Modelin döndürdüğü komutu çalıştır
+    pub fn execute(&self, action: &str, params: Option<&str>) -> Result<Outcome, String> {
+        let handler = self
+            .get(action)
+            .ok_or_else(|| format!("Bilinmeyen komut: {}", action))?;
+        self.run(handler, handler.info().slots_from_params(params))
+    }
+
+    /// Tetikleyici eşleşmesini çalıştır
+    pub fn execute_match(&self, found: CommandMatch) -> Result<Outcome, String> {
+        self.run(found.handler, found.slots)
+    }
 
-        _ => Err(format!("Bilinmeyen komut: {}", action)),
+    /// Onaylanan komutu çalıştır — süresi geçmişse ya da artık yasaklıysa hata
+    pub fn execute_confirmed(&self, pending: PendingCommand) -> Result<String, String> {
+        if pending.expired() {
+            policy::audit(&pending.name, &pending.params, "expired", "");
+            return Err(format!("'{}' için onay süresi doldu", pending.name));
+        }
+        let handler = self
+            .get(&pending.name)
+            .ok_or_else(|| format!("Bilinmeyen komut: {}", pending.name))?;
+        if let Verdict::Deny(reason) = self.policy.check(handler.info(), &pending.params) {
+            policy::audit(&pending.name, &pending.params, "denied", &reason);
+            return Err(reason);
+        }
+        policy::audit(&pending.name, &pending.params, "confirmed", "");
+        self.dispatch(handler, &pending.params)
+    }
+
+    /// Komutu çalıştırmadan çöz: parametreleri, politika kararını ve yapılacak çağrıları döndür
+    pub fn dry_run(&self, action: &str, params: Option<&str>) -> Result<DryRun, String> {
+        let handler = self
+            .get(action)
+            .ok_or_else(|| format!("Bilinmeyen komut: {}", action))?;
+        self.simulate(handler, handler.info().slots_from_params(params))
+    }
+
+    /// Söylenen ifadeyi tetikleyicilerle eşle ve çalıştırmadan çöz
+    pub fn dry_run_phrase(&self, text: &str) -> Result<DryRun, String> {
+        let found = self
+            .match_phrase(text)
+            .ok_or_else(|| format!("Eşleşen komut yok: {}", text))?;
+        self.simulate(found.handler, found.slots)
+    }
+
+    fn simulate(&self, handler: &dyn CommandHandler, values: Params) -> Result<DryRun, String> {
+        let info = handler.info();
+        let params = Self::prepare(info, values)?;
+        let verdict = self.policy.check(info, &params);
+        if let Verdict::Deny(reason) = verdict {
+            return Err(reason);
+        }
+        let recorder = RecordingExecutor::dry_run();
+        let message = handler.execute(&params, &self.guarded(&recorder))?;
+        let invocations: Vec<Invocation> = recorder.take().into_iter().map(redact).collect();
+        Ok(DryRun {
+            command: info.name.clone(),
+            params: params.into_iter().collect(),
+            commands: invocations.iter().map(|i| i.to_string()).collect(),
+            invocations,
+            message,
+            needs_confirmation: verdict == Verdict::Confirm,
+        })
+    }
+
+    /// Platformu denetle ve parametreleri çöz
+    fn prepare(info: &CommandInfo, values: Params) -> Result<Params, String> {
+        if !info.supported() {
+            return Err(format!(
+                "'{}' bu platformda desteklenmiyor ({})",
+                info.name,
+                std::env::consts::OS
+            ));
+        }
+        info.resolve(values)
+    }
+
+    fn run(&self, handler: &dyn CommandHandler, values: Params) -> Result<Outcome, String> {
+        if self.dry_run {
+            let plan = self.simulate(handler, values)?;
+            println!("🧪 Deneme: {}", plan.summary());
+            return Ok(Outcome::Done(plan.summary()));
+        }
+        let info = handler.info();
+        let params = Self::prepare(info, values)?;
+        match self.policy.check(info, &params) {
+            Verdict::Allow => self.dispatch(handler, &params).map(Outcome::Done),
+            Verdict::Confirm => {
+                policy::audit(&info.name, &params, "pending", "");
+                Ok(Outcome::Confirm(PendingCommand::new(&info.name, params)))
+            }
+            Verdict::Deny(reason) => {
+                policy::audit(&info.name, &params, "denied", &reason);
+                Err(reason)
+            }
+        }
+    }
+
+    /// Yürütücüyü bu kaydın politikasıyla sar
+    fn guarded<'a>(&'a self, inner: &'a dyn Executor) -> Guarded<'a> {
+        Guarded {
+            inner,
+            policy: &self.policy,
+        }
+    }
+
+    /// İşleyiciyi çalıştır ve sonucu denetim günlüğüne yaz
+    fn dispatch(&self, handler: &dyn CommandHandler, params: &Params) -> Result<String, String> {
+        let name = &handler.info().name;
+        let result = handler.execute(params, &self.guarded(self.executor.as_ref()));
+        match &result {
+            Ok(msg) => policy::audit(name, params, "ok", msg),
+            Err(e) => policy::audit(name, params, "error", e),
+        }
+        result
+    }
+
+    /// Komut modu istemi için kısa liste: `set_timer(when, label): "{when} zamanlayıcı kur"`
+    pub fn describe(&self) -> String {
+        self.available()
+            .map(|handler| {
+                let info = handler.info();
+                let slots = info.slot_names();
+                let head = if slots.is_empty() {
+                    info.name.clone()
+                } else {
+                    format!("{}({})", info.name, slots.join(", "))
+                };
+                match info.triggers.first() {
+                    Some(trigger) => format!("{}: \"{}\"", head, trigger),
+                    None => head,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
     }
 }
 
-/// AppleScript çalıştır
-fn run_osascript(script: &str) -> Result<(), String> {
-    Command::new("osascript")
-        .args(["-e", script])
-        .output()
-        .map_err(|e| format!("AppleScript hatası: {}", e))?;
+/// Config'teki kullanıcı komutlarını doğrula (ad çakışmaları dahil)
+pub fn validate_commands(custom: &[VoiceCommand]) -> Result<(), String> {
+    let mut registry = CommandRegistry::new(&[]);
+    for command in custom {
+        command.validate()?;
+        registry.register(Box::new(CustomHandler::new(command.clone())))?;
+    }
     Ok(())
 }
+
+// ── Tetikleyici Eşleştirme ──
+
+/// Tetikleyici parçası
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    /// Küçük harfe çevrilmiş sabit metin
+    Literal(Vec<char>),
+    Slot(String),
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Eşleştirme için hazırla: baştaki/sondaki noktalamayı at, boşlukları tekle
+fn clean(text: &str) -> Vec<char> {
+    let trimmed = text.trim_matches(|c: char| c.is_whitespace() || TRIM_PUNCT.contains(&c));
+    let mut out = Vec::with_capacity(trimmed.len());
+    for word in trimmed.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.extend(word.chars());
+    }
+    out
+}
+
+fn is_slot_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn parse_trigger(trigger: &str) -> Result<Vec<Part>, String> {
+    let text: String = clean(trigger).into_iter().collect();
+    let mut parts = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            parts.push(Part::Literal(rest[..open].chars().map(fold).collect()));
+        }
+        let close = rest[open..]
+            .find('}')
+            .map(|i| open + i)
+            .ok_or_else(|| format!("kapanmamış yuva: \"{}\"", trigger))?;
+        let name = rest[open + 1..close].trim();
+        if !is_slot_name(name) {
+            return Err(format!("geçersiz yuva adı: {{{}}}", name));
+        }
+        if matches!(parts.last(), Some(Part::Slot(_))) {
+            return Err(format!("yan yana yuvalar ayırt edilemez: \"{}\"", trigger));
+        }
+        parts.push(Part::Slot(name.to_string()));
+        rest = &rest[close + 1..];
+    }
+    if rest.contains('}') {
+        return Err(format!("açılmamış yuva: \"{}\"", trigger));
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest.chars().map(fold).collect()));
+    }
+    if parts.is_empty() {
+        return Err("boş tetikleyici".into());
+    }
+    Ok(parts)
+}
+
+/// Parçaları `pos`tan itibaren metnin tamamıyla eşle; yuvalar en kısa değerden denenir
+fn match_parts(
+    parts: &[Part],
+    text: &[char],
+    pos: usize,
+    slots: &mut HashMap<String, String>,
+) -> bool {
+    let Some((part, rest)) = parts.split_first() else {
+        return pos == text.len();
+    };
+    match part {
+        Part::Literal(literal) => {
+            let end = pos + literal.len();
+            end <= text.len()
+                && text[pos..end]
+                    .iter()
+                    .zip(literal)
+                    .all(|(a, b)| fold(*a) == *b)
+                && match_parts(rest, text, end, slots)
+        }
+        Part::Slot(name) => {
+            for end in pos + 1..=text.len() {
+                let value: String = text[pos..end].iter().collect();
+                let value = value.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                if match_parts(rest, text, end, slots) {
+                    slots.insert(name.clone(), value.to_string());
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Şablondaki yuva adları
+fn placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        if let Some(close) = rest.find('}') {
+            let name = rest[..close].trim();
+            if is_slot_name(name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Şablondaki "{yuva}" yerlerine kaçışlanmış değerleri koy; bilinmeyen süslü parantezler olduğu gibi kalır
+fn fill(
+    template: &str,
+    slots: &HashMap<String, String>,
+    escape: impl Fn(&str) -> String,
+) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after
+            .find('}')
+            .and_then(|close| slots.get(after[..close].trim()).map(|value| (value, close)));
+        match value {
+            Some((value, close)) => {
+                out.push_str(&escape(value));
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Kabuk şablonunu konumsal argümanlı betiğe çevir — değerler betiğe girmez, `sh -c <betik> millow <değerler…>`
+/// ile geçer. Yuva tırnak dışında `"${1}"`, çift tırnak içinde `${1}`, tek tırnak içinde `'"${1}"'` olur
+fn shell_script(template: &str, slots: &Params) -> (String, Vec<String>) {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Quote {
+        None,
+        Single,
+        Double,
+    }
+
+    let mut script = String::with_capacity(template.len());
+    let mut names: Vec<&str> = Vec::new();
+    let mut values = Vec::new();
+    let mut quote = Quote::None;
+    let mut escaped = false;
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if c == '{' && !escaped {
+            let after = &rest[1..];
+            let slot = after.find('}').and_then(|close| {
+                let name = after[..close].trim();
+                slots.get(name).map(|value| (name, value, close))
+            });
+            if let Some((name, value, close)) = slot {
+                let index = match names.iter().position(|known| *known == name) {
+                    Some(i) => i + 1,
+                    None => {
+                        names.push(name);
+                        values.push(value.clone());
+                        values.len()
+                    }
+                };
+                let arg = format!("${{{}}}", index);
+                match quote {
+                    Quote::None => script.push_str(&format!("\"{}\"", arg)),
+                    Quote::Double => script.push_str(&arg),
+                    Quote::Single => script.push_str(&format!("'\"{}\"'", arg)),
+                }
+                rest = &after[close + 1..];
+                continue;
+            }
+        }
+        script.push(c);
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (Quote::Single, '\'') => quote = Quote::None,
+            (Quote::Single, _) => {}
+            (_, '\\') => escaped = true,
+            (Quote::None, '\'') => quote = Quote::Single,
+            (Quote::None, '"') => quote = Quote::Double,
+            (Quote::Double, '"') => quote = Quote::None,
+            _ => {}
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    (script, values)
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Programı ve argümanları kabukta yazılacağı gibi birleştir
+fn shell_line(program: &str, args: &[String]) -> String {
+    let mut line = program.to_string();
+    for arg in args {
+        line.push(' ');
+        let plain = !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+        if plain {
+            line.push_str(arg);
+        } else {
+            line.push_str(&shell_quote(arg));
+        }
+    }
+    line
+}
+
+fn applescript_escape(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', "\\\"")
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn encode_url_component(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+// ── Eylem Yardımcıları ──
+
+/// Tuş dizisi adımı
+enum KeyStep {
+    Text(String),
+    Chord(Vec<enigo::Key>, enigo::Key),
+}
+
+/// "cmd+shift+t", "enter", "f5" veya "text:merhaba"
+fn parse_key_step(spec: &str) -> Result<KeyStep, String> {
+    use enigo::Key;
+
+    if let Some(text) = spec.strip_prefix("text:") {
+        return Ok(KeyStep::Text(text.to_string()));
+    }
+    let mut names: Vec<String> = spec.split('+').map(|k| k.trim().to_lowercase()).collect();
+    let key_name = names
+        .pop()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| format!("geçersiz tuş: \"{}\"", spec))?;
+    let modifiers = names
+        .iter()
+        .map(|m| match m.as_str() {
+            "cmd" | "command" | "meta" | "super" | "win" => Ok(Key::Meta),
+            "ctrl" | "control" => Ok(Key::Control),
+            "alt" | "option" | "opt" => Ok(Key::Alt),
+            "shift" => Ok(Key::Shift),
+            other => Err(format!("bilinmeyen değiştirici tuş: {}", other)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = match key_name.as_str() {
+        "enter" | "return" => Key::Return,
+        "tab" => Key::Tab,
+        "esc" | "escape" => Key::Escape,
+        "space" => Key::Space,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "up" => Key::UpArrow,
+        "down" => Key::DownArrow,
+        "left" => Key::LeftArrow,
+        "right" => Key::RightArrow,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "f1" => Key::F1,
+        "f2" => Key::F2,
+        "f3" => Key::F3,
+        "f4" => Key::F4,
+        "f5" => Key::F5,
+        "f6" => Key::F6,
+        "f7" => Key::F7,
+        "f8" => Key::F8,
+        "f9" => Key::F9,
+        "f10" => Key::F10,
+        "f11" => Key::F11,
+        "f12" => Key::F12,
+        other => {
+            let mut chars = other.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Key::Unicode(c),
+                _ => return Err(format!("bilinmeyen tuş: {}", other)),
+            }
+        }
+    };
+    Ok(KeyStep::Chord(modifiers, key))
+}
+
+fn send_keys(keys: &[String]) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Keyboard, Settings};
+
+    let steps = keys
+        .iter()
+        .map(|key| parse_key_step(key))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Klavye simülasyonu başlatılamadı: {}", e))?;
+    for step in steps {
+        match step {
+            KeyStep::Text(text) => enigo
+                .text(&text)
+                .map_err(|e| format!("Tuş vuruşu gönderilemedi: {}", e))?,
+            KeyStep::Chord(modifiers, key) => {
+                for modifier in &modifiers {
+                    enigo
+                        .key(*modifier, Direction::Press)
+                        .map_err(|e| format!("Tuş gönderilemedi: {}", e))?;
+                }
+                let sent = enigo.key(key, Direction::Click);
+                for modifier in modifiers.iter().rev() {
+                    let _ = enigo.key(*modifier, Direction::Release);
+                }
+                sent.map_err(|e| format!("Tuş gönderilemedi: {}", e))?;
+            }
+        }
+        std::thread::sleep(KEY_STEP_DELAY);
+    }
+    Ok(())
+}
+
+fn http_request(
+    method: &str,
+    url: &str,
+    headers: &BTreeMap<String, String>,
+    body: Option<&str>,
+) -> Result<String, String> {
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|_| format!("Geçersiz HTTP yöntemi: {}", method))?;
+    let client = reqwest::blocking::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|e| format!("HTTP istemcisi oluşturulamadı: {}", e))?;
+
+    let mut request = client.request(method, url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.body(body.to_string());
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| format!("HTTP isteği başarısız: {}", e))?;
+    let status = response.status();
+    let text = response.text().unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!(
+            "HTTP hatası ({}): {}",
+            status,
+            text.chars().take(200).collect::<String>()
+        ));
+    }
+    Ok(text)
+}
+
+/// Uygulama öne gelene kadar bekle
+fn wait_for_app(app: &str, timeout: Duration) -> Result<(), String> {
+    let start = std::time::Instant::now();
+    while start.elapsed() < timeout {
+        if crate::focus::frontmost().is_some_and(|target| is_app(&target, app)) {
+            return Ok(());
+        }
+        std::thread::sleep(APP_POLL_INTERVAL);
+    }
+    Err(format!(
+        "{} {} ms içinde öne gelmedi",
+        app,
+        timeout.as_millis()
+    ))
+}
+
+/// Uygulamanın durumu: "frontmost", "running" ya da "stopped"
+fn app_state(app: &str) -> Result<String, String> {
+    if crate::focus::frontmost().is_some_and(|target| is_app(&target, app)) {
+        return Ok("frontmost".into());
+    }
+    let running = if cfg!(target_os = "macos") {
+        let script = format!("application \"{}\" is running", applescript_escape(app));
+        let output = Command::new("osascript")
+            .args(["-e", &script])
+            .output()
+            .map_err(|e| format!("osascript çalıştırılamadı: {}", e))?;
+        String::from_utf8_lossy(&output.stdout).trim() == "true"
+    } else {
+        Command::new("pgrep")
+            .args(["-x", "-i", app])
+            .output()
+            .map(|o| o.status.success())
+            .map_err(|e| format!("pgrep çalıştırılamadı: {}", e))?
+    };
+    Ok(if running { "running" } else { "stopped" }.into())
+}
+
+/// Ön plandaki uygulama adı ya da bundle ID'si eşleşiyor mu
+fn is_app(target: &crate::focus::FocusTarget, app: &str) -> bool {
+    target.name.eq_ignore_ascii_case(app)
+        || target
+            .bundle_id
+            .as_deref()
+            .is_some_and(|id| id.eq_ignore_ascii_case(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::desktop::{LinuxDesktop, MacDesktop};
+
+    fn literal(text: &str) -> Part {
+        Part::Literal(text.chars().collect())
+    }
+
+    fn slot(name: &str) -> Part {
+        Part::Slot(name.into())
+    }
+
+    /// Tetikleyiciyi metinle eşle, yuva değerlerini döndür
+    fn match_trigger(trigger: &str, text: &str) -> Option<Params> {
+        let parts = parse_trigger(trigger).unwrap();
+        let mut slots = Params::new();
+        match_parts(&parts, &clean(text), 0, &mut slots).then_some(slots)
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> Params {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn custom(json: &str) -> CustomHandler {
+        let command: VoiceCommand = serde_json::from_str(json).unwrap();
+        command.validate().unwrap();
+        CustomHandler::new(command)
+    }
+
+    #[test]
+    fn parses_triggers() {
+        assert_eq!(
+            parse_trigger("  {App}  Uygulamasını AÇ. ").unwrap(),
+            [slot("App"), literal(" uygulamasını aç")]
+        );
+        assert_eq!(
+            parse_trigger("{when} sonra {label} hatırlat").unwrap(),
+            [
+                slot("when"),
+                literal(" sonra "),
+                slot("label"),
+                literal(" hatırlat")
+            ]
+        );
+        assert_eq!(
+            parse_trigger("yeni sekme").unwrap(),
+            [literal("yeni sekme")]
+        );
+        for bad in ["{a", "a}", "{a}{b}", "{} aç", "{a b} aç", "..."] {
+            assert!(parse_trigger(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn matches_slots() {
+        assert_eq!(
+            match_trigger("{app} aç", "Visual Studio Code aç."),
+            Some(params(&[("app", "Visual Studio Code")]))
+        );
+        assert_eq!(
+            match_trigger("{a} ve {b} gönder", "elma ve armut ve kiraz gönder"),
+            Some(params(&[("a", "elma"), ("b", "armut ve kiraz")]))
+        );
+        assert_eq!(
+            match_trigger("SESİ  artır", "sesi artır!"),
+            Some(Params::new())
+        );
+        assert_eq!(match_trigger("{app} aç", "aç"), None);
+        assert_eq!(match_trigger("sesi artır", "sesi artır lütfen"), None);
+        assert_eq!(match_trigger("go to {url}", "go to"), None);
+    }
+
+    #[test]
+    fn longest_literal_trigger_wins() {
+        let registry = CommandRegistry::new(&[]);
+        let found = registry.match_phrase("sesi aç").unwrap();
+        assert_eq!(found.handler.info().name, "volume_up");
+        let found = registry.match_phrase("Firefox aç").unwrap();
+        assert_eq!(found.handler.info().name, "open_app");
+        assert_eq!(found.slots["app"], "Firefox");
+        assert!(registry.match_phrase("bugün hava güzel").is_none());
+    }
+
+    #[test]
+    fn fills_templates() {
+        let slots = params(&[("q", "it's \"x\""), ("n", "3")]);
+        assert_eq!(
+            fill("echo {q} {n}", &slots, shell_quote),
+            r#"echo 'it'\''s "x"' '3'"#
+        );
+        assert_eq!(
+            fill("https://x.com/?q={ q }", &slots, encode_url_component),
+            "https://x.com/?q=it%27s%20%22x%22"
+        );
+        // Bilinmeyen ve kapanmamış süslü parantezler olduğu gibi kalır
+        assert_eq!(
+            fill(r#"{"a":"{q}","b":{x}} {n"#, &slots, json_escape),
+            r#"{"a":"it's \"x\"","b":{x}} {n"#
+        );
+        assert_eq!(placeholders("{a} {b c} {_d} {"), ["a", "_d"]);
+    }
+
+    #[test]
+    fn escapes_values() {
+        assert_eq!(shell_quote("a'b"), r"'a'\''b'");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(
+            applescript_escape(r#"say "hi" \ bye"#),
+            r#"say \"hi\" \\ bye"#
+        );
+        assert_eq!(json_escape("a\"b\\c\nd"), r#"a\"b\\c\nd"#);
+        assert_eq!(encode_url_component("ğ ü/?"), "%C4%9F%20%C3%BC%2F%3F");
+        assert_eq!(
+            Invocation::run("osascript", &["-e", "set x to 1", "plain-arg"]).to_string(),
+            "osascript -e 'set x to 1' plain-arg"
+        );
+        assert_eq!(
+            Invocation::spawn("open", &["it's"]).to_string(),
+            r"open 'it'\''s' &"
+        );
+    }
+
+    #[test]
+    fn custom_actions_record_invocations() {
+        let exec = RecordingExecutor::default();
+        let shell = custom(
+            r#"{"name":"ara","triggers":["{q} ara"],"action":{"type":"shell","command":"grep -r {q} ."}}"#,
+        );
+        shell
+            .execute(&params(&[("q", "a'; rm -rf ~")]), &exec)
+            .unwrap();
+        assert_eq!(
+            exec.take(),
+            [Invocation::run(
+                "sh",
+                &["-c", r#"grep -r "${1}" ."#, "millow", "a'; rm -rf ~"]
+            )]
+        );
+
+        let hook = custom(
+            r#"{"name":"hook","triggers":["{m} gönder"],"action":{"type":"http","url":"https://h.example/{m}","body":"{\"text\":\"{m}\"}"}}"#,
+        );
+        hook.execute(&params(&[("m", "a \"b\"")]), &exec).unwrap();
+        assert_eq!(
+            exec.take(),
+            [Invocation::Http {
+                method: "POST".into(),
+                url: "https://h.example/a%20%22b%22".into(),
+                headers: BTreeMap::from([("Content-Type".into(), "application/json".into())]),
+                body: Some(r#"{"text":"a \"b\""}"#.into()),
+            }]
+        );
+
+        let script = custom(
+            r#"{"name":"say","triggers":["{m} söyle"],"action":{"type":"applescript","script":"say \"{m}\""}}"#,
+        );
+        script.execute(&params(&[("m", "\"hi\"")]), &exec).unwrap();
+        assert_eq!(
+            exec.take(),
+            [Invocation::run("osascript", &["-e", r#"say "\"hi\"""#])]
+        );
+    }
+
+    #[test]
+    fn shell_slots_become_positional_args() {
+        let slots = params(&[("msg", "$(rm -rf ~)"), ("n", "2")]);
+        let script = |template: &str| shell_script(template, &slots);
+        assert_eq!(
+            script(r#"say "{msg}" {n} {msg}"#),
+            (
+                r#"say "${1}" "${2}" "${1}""#.into(),
+                vec!["$(rm -rf ~)".into(), "2".into()]
+            )
+        );
+        assert_eq!(
+            script("echo '{msg}' {x}"),
+            (r#"echo ''"${1}"'' {x}"#.into(), vec!["$(rm -rf ~)".into()])
+        );
+        assert_eq!(
+            script(r#"echo "it's \" {n}" '"' {n}"#),
+            (r#"echo "it's \" ${1}" '"' "${1}""#.into(), vec!["2".into()])
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn quoted_shell_template_does_not_run_slot_values() {
+        let command = custom(
+            r#"{"name":"say","triggers":["{msg} söyle"],"action":{"type":"shell","command":"printf '%s|%s|%s' \"{msg}\" '{msg}' {msg}"}}"#,
+        );
+        validate_commands(std::slice::from_ref(&command.command)).unwrap();
+        let value = "$(echo pwned) `id` 'x' \"y\"";
+        let output = command
+            .execute(&params(&[("msg", value)]), &SystemExecutor)
+            .unwrap();
+        assert_eq!(output, format!("{0}|{0}|{0}", value));
+    }
+
+    #[test]
+    fn recorder_uses_given_desktop() {
+        let open = custom(
+            r#"{"name":"docs","triggers":["belgeler"],"action":{"type":"open_url","url":"https://docs.example/{p}"},"slots":{"p":{"default":"a b"}}}"#,
+        );
+        let p = params(&[("p", "a b")]);
+
+        let exec = RecordingExecutor::default().with_desktop(MacDesktop);
+        open.execute(&p, &exec).unwrap();
+        assert_eq!(
+            exec.take(),
+            [Invocation::spawn("open", &["https://docs.example/a%20b"])]
+        );
+
+        let exec = RecordingExecutor::default().with_desktop(LinuxDesktop { wayland: false });
+        open.execute(&p, &exec).unwrap();
+        assert_eq!(
+            exec.take(),
+            [Invocation::spawn(
+                "xdg-open",
+                &["https://docs.example/a%20b"]
+            )]
+        );
+    }
+
+    /// Yalnızca example.com'a izin veren, lock_screen'i yasaklayan kayıt
+    fn guarded_registry(commands: &[&str]) -> CommandRegistry {
+        CommandRegistry::from_config(&MillowConfig {
+            custom_commands: commands
+                .iter()
+                .map(|json| serde_json::from_str(json).unwrap())
+                .collect(),
+            command_denylist: vec!["lock_screen".into()],
+            url_allowed_domains: vec!["example.com".into()],
+            ..MillowConfig::default()
+        })
+    }
+
+    #[test]
+    fn policy_covers_templated_urls() {
+        let registry = guarded_registry(&[
+            r#"{"name":"site","triggers":["{site} sitesine git"],"action":{"type":"open_url","url":"https://{site}/"}}"#,
+            r#"{"name":"ping","triggers":["{host} yokla"],"action":{"type":"http","method":"GET","url":"https://{host}/ping"}}"#,
+            r#"{"name":"git","triggers":["{site} adresine git"],"action":{"type":"command","name":"open_url","params":{"url":"https://{site}"}}}"#,
+        ]);
+        for phrase in [
+            "evil.com sitesine git",
+            "evil.com yokla",
+            "evil.com adresine git",
+        ] {
+            let err = registry.dry_run_phrase(phrase).unwrap_err();
+            assert!(
+                err.contains("'evil.com' alan adına izin verilmiyor"),
+                "{}: {}",
+                phrase,
+                err
+            );
+        }
+        for phrase in [
+            "docs.example.com sitesine git",
+            "example.com yokla",
+            "example.com adresine git",
+        ] {
+            let plan = registry.dry_run_phrase(phrase).unwrap();
+            assert_eq!(plan.invocations.len(), 1, "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn policy_covers_nested_builtins() {
+        let registry = guarded_registry(&[
+            r#"{"name":"kilitle","triggers":["ekranı kapat"],"action":{"type":"command","name":"lock_screen"}}"#,
+            r#"{"name":"mola","triggers":["mola ver"],"action":{"type":"macro","steps":[{"type":"wait","ms":10},{"type":"command","name":"lock_screen"}]}}"#,
+        ]);
+        assert!(registry.dry_run("lock_screen", None).is_err());
+        for phrase in ["ekranı kapat", "mola ver"] {
+            let err = registry.dry_run_phrase(phrase).unwrap_err();
+            assert!(err.contains("'lock_screen' yasaklı"), "{}: {}", phrase, err);
+        }
+    }
+
+    #[test]
+    fn recorder_answers_matching_calls() {
+        let exec = RecordingExecutor::default().answer("--output", "x");
+        assert_eq!(
+            exec.execute(&Invocation::run("xsel", &["--clipboard", "--output"])),
+            Ok("x".into())
+        );
+        assert_eq!(
+            exec.execute(&Invocation::run("xclip", &["-o"])),
+            Ok(String::new())
+        );
+        assert_eq!(exec.take().len(), 2);
+    }
+
+    #[test]
+    fn dry_run_feeds_clipboard_and_llm() {
+        let registry = CommandRegistry::new(&[]);
+        let plan = registry.dry_run_phrase("panoyu çevir").unwrap();
+        assert_eq!(plan.message, "Çeviri panoya kopyalandı");
+        assert!(plan
+            .commands
+            .iter()
+            .any(|line| line.contains("/chat/completions") && line.contains(DRY_RUN_CLIPBOARD)));
+        assert!(plan
+            .commands
+            .last()
+            .unwrap()
+            .contains("(deneme: model cevabı)"));
+    }
+
+    #[test]
+    fn dry_run_finds_a_media_player() {
+        let registry = CommandRegistry::new(&[]);
+        let plan = registry.dry_run("next_track", None).unwrap();
+        assert!(
+            plan.message.starts_with("Sonraki parça"),
+            "{}",
+            plan.message
+        );
+        if cfg!(target_os = "linux") {
+            assert_eq!(plan.message, "Sonraki parça (deneme)");
+            assert!(plan.commands.last().unwrap().ends_with("Player.Next"));
+        }
+    }
+
+    #[test]
+    fn macro_runs_steps_in_order() {
+        let exec = RecordingExecutor::default();
+        let handler = custom(
+            r#"{"name":"sekme","triggers":["sekmeye {m} yaz"],"action":{"type":"macro","steps":[
+                {"type":"wait","ms":10},
+                {"type":"keystrokes","keys":["cmd+t"],"delay_ms":5},
+                {"type":"type_text","text":"{m}"}
+            ]}}"#,
+        );
+        let message = handler
+            .execute(&params(&[("m", "merhaba")]), &exec)
+            .unwrap();
+        assert_eq!(message, "sekme: 3 adım çalıştı");
+        assert_eq!(
+            exec.take(),
+            [
+                Invocation::Sleep { ms: 10 },
+                Invocation::Sleep { ms: 5 },
+                Invocation::Keys {
+                    keys: vec!["cmd+t".into()]
+                },
+                Invocation::Keys {
+                    keys: vec!["text:merhaba".into()]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn macro_skips_unmet_conditions() {
+        let exec = RecordingExecutor::default()
+            .answer("'Slack' durumu", "stopped")
+            .answer("'Zoom' durumu", "frontmost");
+        let handler = custom(
+            r#"{"name":"toplantı","triggers":["toplantıya hazırlan"],"action":{"type":"macro","steps":[
+                {"type":"type_text","text":"a","when":{"running":"Slack"}},
+                {"type":"type_text","text":"b","when":{"not_running":"Slack"}},
+                {"type":"wait","ms":1,"when":{"frontmost":"Zoom"}},
+                {"type":"wait","ms":2,"when":{"not_frontmost":"Zoom"}}
+            ]}}"#,
+        );
+        let message = handler.execute(&Params::new(), &exec).unwrap();
+        assert_eq!(message, "toplantı: 2 adım çalıştı, 2 atlandı");
+        let state = |app: &str| Invocation::AppState { app: app.into() };
+        assert_eq!(
+            exec.take(),
+            [
+                state("Slack"),
+                state("Slack"),
+                Invocation::Keys {
+                    keys: vec!["text:b".into()]
+                },
+                state("Zoom"),
+                Invocation::Sleep { ms: 1 },
+                state("Zoom"),
+            ]
+        );
+    }
+
+    #[test]
+    fn macro_stops_on_failure_unless_told_otherwise() {
+        let exec = RecordingExecutor::default();
+        let steps = |continue_on_error: bool| {
+            format!(
+                r#"{{"name":"deneme","triggers":["dene"],"action":{{"type":"macro","steps":[
+                    {{"type":"open_url","url":"https://evil.org","continue_on_error":{}}},
+                    {{"type":"wait","ms":3}}
+                ]}}}}"#,
+                continue_on_error
+            )
+        };
+        let policy = CommandPolicy {
+            url_domains: vec!["example.com".into()],
+            ..Default::default()
+        };
+        let guarded = Guarded {
+            inner: &exec,
+            policy: &policy,
+        };
+
+        let err = custom(&steps(false))
+            .execute(&Params::new(), &guarded)
+            .unwrap_err();
+        assert!(err.starts_with("deneme: 1. adım başarısız"), "{}", err);
+        assert!(exec.take().is_empty());
+
+        let message = custom(&steps(true))
+            .execute(&Params::new(), &guarded)
+            .unwrap();
+        assert_eq!(message, "deneme: 1 adım çalıştı, 1 başarısız");
+        assert_eq!(exec.take(), [Invocation::Sleep { ms: 3 }]);
+    }
+
+    #[test]
+    fn macro_platforms_intersect() {
+        let command = |steps: &str| -> VoiceCommand {
+            serde_json::from_str(&format!(
+                r#"{{"name":"m","triggers":["m"],"action":{{"type":"macro","steps":{}}}}}"#,
+                steps
+            ))
+            .unwrap()
+        };
+        let mac_only = command(
+            r#"[{"type":"open_app","app":"Notes"},{"type":"wait","ms":1},{"type":"applescript","script":"beep"}]"#,
+        );
+        assert!(mac_only.validate().is_ok());
+        assert_eq!(mac_only.info().platforms, ["macos"]);
+
+        let anywhere =
+            command(r#"[{"type":"wait","ms":1},{"type":"keystrokes","keys":["enter"]}]"#);
+        assert!(anywhere.validate().is_ok());
+        assert!(anywhere.info().platforms.is_empty());
+
+        let lists = |lists: &[&[&str]]| -> Vec<Vec<String>> {
+            lists
+                .iter()
+                .map(|list| list.iter().map(|p| p.to_string()).collect())
+                .collect()
+        };
+        assert_eq!(common_platforms(lists(&[])), None);
+        assert_eq!(common_platforms(lists(&[&[], &[]])), None);
+        assert_eq!(
+            common_platforms(lists(&[&["macos", "linux"], &[], &["linux", "windows"]])),
+            Some(vec!["linux".to_string()])
+        );
+        assert_eq!(
+            common_platforms(lists(&[&["macos"], &["linux"]])),
+            Some(Vec::new())
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // Kalıcı ayarları ~/.millow/config.json'dan okur/yazar
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -63,11 +64,36 @@ pub struct MillowConfig {
     #[serde(default)]
     pub whisper_mode: bool,
 
+    // ── Ses Ön İşleme (DSP) ──
+    /// Kodlamadan önce DSP zincirini uygula
+    #[serde(default = "default_true")]
+    pub dsp_enabled: bool,
+
+    /// Yüksek geçiren filtre kesim frekansı (Hz, 0 = kapalı, varsayılan 80)
+    #[serde(default = "default_highpass_cutoff")]
+    pub highpass_cutoff: f32,
+
+    /// Spektral gürültü bastırma gücü (0 = kapalı, varsayılan 1.0)
+    #[serde(default = "default_noise_suppression")]
+    pub noise_suppression: f32,
+
+    /// Otomatik kazanç kontrolü / normalizasyon
+    #[serde(default = "default_true")]
+    pub agc_enabled: bool,
+
+    /// AGC hedef konuşma seviyesi (RMS, 0-1, varsayılan 0.1)
+    #[serde(default = "default_agc_target_level")]
+    pub agc_target_level: f32,
+
     // ── Groq Whisper (hızlı transcription) ──
     /// Groq API anahtarı (ücretsiz — console.groq.com)
     #[serde(default)]
     pub groq_api_key: Option<String>,
 
+    /// Groq (OpenAI uyumlu) API adresi — yerel test sunucusu için değiştirilebilir
+    #[serde(default = "default_groq_endpoint")]
+    pub groq_endpoint: String,
+
     // ── Başlangıçta Çalış ──
     /// Mac açılınca otomatik başlat
     #[serde(default)]
@@ -90,6 +116,119 @@ pub struct MillowConfig {
     #[serde(default)]
     pub newline_after_segment: bool,
 
+    // ── Çıktı Yöntemi ──
+    /// Varsayılan çıktı yöntemi: "paste" (pano + Cmd+V) veya "type" (tuş vuruşu)
+    #[serde(default = "default_output_method")]
+    pub output_method: String,
+
+    /// Tuş vuruşu modunda saniyedeki karakter sayısı (0 = sınırsız, varsayılan 120)
+    #[serde(default = "default_typing_rate")]
+    pub typing_rate: f32,
+
+    /// Uygulamaya özel çıktı yöntemi — uygulama adı veya bundle ID → "paste" / "type"
+    #[serde(default)]
+    pub app_output_methods: HashMap<String, String>,
+
+    /// Metin çıkış arka ucu: "auto", "macos", "x11" veya "wayland"
+    #[serde(default = "default_output_backend")]
+    pub output_backend: String,
+
+    /// İmlecin çevresine göre baştaki boşluğu, ilk harfi ve sondaki noktayı düzelt
+    #[serde(default = "default_true")]
+    pub smart_spacing: bool,
+
+    // ── Çıktı Hedefleri ──
+    /// Mod → hedef listesi ("app", "preview", "clipboard", "file", "stdout", "webhook";
+    /// "file:/yol", "webhook:http://…" ile özel hedef). Tanımsız mod yalnızca "app" kullanır.
+    #[serde(default)]
+    pub output_routes: HashMap<String, Vec<String>>,
+
+    /// Metni yazmadan önce önizleme penceresinde onay istenen uygulamalar (ad veya bundle ID)
+    #[serde(default)]
+    pub preview_apps: Vec<String>,
+
+    /// "file" hedefinin varsayılan dosyası (.md ise Markdown listesi)
+    #[serde(default = "default_output_file")]
+    pub output_file: String,
+
+    /// "webhook" hedefinin varsayılan adresi (JSON POST)
+    #[serde(default)]
+    pub output_webhook: String,
+
+    // ── Son Dikteyi Geri Al ──
+    /// Geri alma kısayolu (bırakınca tetiklenir)
+    #[serde(default = "default_undo_hotkey")]
+    pub undo_hotkey: String,
+
+    /// Yapıştırılan metni geri alma yöntemi: "erase" (Backspace) veya "undo" (Cmd+Z)
+    #[serde(default = "default_undo_method")]
+    pub undo_method: String,
+
+    /// "bunu sil", "son cümleyi sil", "X yerine Y yaz" gibi sesli düzeltmeler
+    #[serde(default = "default_true")]
+    pub voice_corrections: bool,
+
+    // ── Kayıt Limitleri ──
+    /// Maksimum kayıt süresi (saniye, varsayılan 600) — basılı tutma dahil
+    #[serde(default = "default_max_recording_duration")]
+    pub max_recording_duration: f32,
+
+    /// Kayıt buffer'ı için bellek sınırı (MB, varsayılan 64)
+    #[serde(default = "default_max_recording_memory_mb")]
+    pub max_recording_memory_mb: f32,
+
+    /// Uzun kayıtlar sessizlik noktalarından bu süreyi aşmayan parçalara bölünür (saniye, varsayılan 120)
+    #[serde(default = "default_chunk_duration")]
+    pub chunk_duration: f32,
+
+    // ── Sesli Komutlar ──
+    /// Kullanıcı tanımlı komutlar — tetikleyici ifadeler, yuvalar ve eylem (shell, open_url,
+    /// keystrokes, applescript, http, open_app, wait_for_app, type_text, wait, command ya da
+    /// gecikme ve koşul içeren adımlardan oluşan macro). Yerleşik komutlarla aynı kayıtta çalışır.
+    #[serde(default)]
+    pub custom_commands: Vec<crate::commander::VoiceCommand>,
+
+    /// Deneme modu — komutlar çalıştırılmaz, yapacakları bildirilir
+    #[serde(default)]
+    pub command_dry_run: bool,
+
+    // ── Komut Güvenliği ──
+    /// Doluysa yalnızca bu komutlar çalışır
+    #[serde(default)]
+    pub command_allowlist: Vec<String>,
+
+    /// Hiçbir zaman çalışmayacak komutlar (izin listesinden önce gelir)
+    #[serde(default)]
+    pub command_denylist: Vec<String>,
+
+    /// Geri alınması zor komutlar (ekran kilidi, Wi-Fi…) sesli "evet" onayı bekler
+    #[serde(default = "default_true")]
+    pub confirm_destructive_commands: bool,
+
+    /// Komutların açabileceği URL şemaları
+    #[serde(default = "default_url_schemes")]
+    pub url_allowed_schemes: Vec<String>,
+
+    /// Komutların açabileceği alan adları (alt alan adları dahil); boşsa hepsi
+    #[serde(default)]
+    pub url_allowed_domains: Vec<String>,
+
+    // ── AI Pano İşlemleri ──
+    /// OpenAI uyumlu chat completions API kökü
+    #[serde(default = "default_groq_endpoint")]
+    pub ai_endpoint: String,
+
+    /// LLM API anahtarı — yoksa ve uç nokta Groq ise Groq anahtarı kullanılır
+    #[serde(default)]
+    pub ai_api_key: Option<String>,
+
+    #[serde(default = "default_ai_model")]
+    pub ai_model: String,
+
+    /// Sonuç: "clipboard" (panoya kopyala) veya "paste" (kopyala ve yapıştır)
+    #[serde(default = "default_ai_output")]
+    pub ai_output: String,
+
     // ── Hallucination Filtresi ──
     /// Filtrelenen kelimeler/cümleler listesi
     #[serde(default = "default_hallucinations")]
@@ -112,6 +251,54 @@ fn default_auto_stop_duration() -> f32 {
     30.0
 }
 
+fn default_highpass_cutoff() -> f32 {
+    80.0
+}
+
+fn default_noise_suppression() -> f32 {
+    1.0
+}
+
+fn default_agc_target_level() -> f32 {
+    0.1
+}
+
+fn default_output_method() -> String {
+    "paste".into()
+}
+
+fn default_typing_rate() -> f32 {
+    120.0
+}
+
+fn default_output_backend() -> String {
+    "auto".into()
+}
+
+fn default_output_file() -> String {
+    "~/.millow/notes.md".into()
+}
+
+fn default_undo_hotkey() -> String {
+    "Alt+Shift+Z".into()
+}
+
+fn default_undo_method() -> String {
+    "erase".into()
+}
+
+fn default_max_recording_duration() -> f32 {
+    600.0
+}
+
+fn default_max_recording_memory_mb() -> f32 {
+    64.0
+}
+
+fn default_chunk_duration() -> f32 {
+    120.0
+}
+
 fn default_hallucinations() -> Vec<String> {
     vec![
         "Altyazı M.K.".into(), "altyazı m.k.".into(), "Altyazı M.K".into(),
@@ -133,6 +320,23 @@ fn default_hallucinations() -> Vec<String> {
     ]
 }
 
+fn default_url_schemes() -> Vec<String> {
+    vec!["https".into(), "http".into()]
+}
+
+fn default_ai_model() -> String {
+    "llama-3.3-70b-versatile".into()
+}
+
+fn default_ai_output() -> String {
+    "clipboard".into()
+}
+
+/// Groq'un OpenAI uyumlu API kökü
+pub fn default_groq_endpoint() -> String {
+    "https://api.groq.com/openai/v1".into()
+}
+
 fn default_style() -> String {
     "auto".into()
 }
@@ -158,12 +362,44 @@ impl Default for MillowConfig {
             hold_to_talk: true,
             writing_style: "auto".into(),
             whisper_mode: false,
+            dsp_enabled: true,
+            highpass_cutoff: 80.0,
+            noise_suppression: 1.0,
+            agc_enabled: true,
+            agc_target_level: 0.1,
             groq_api_key: None,
+            groq_endpoint: default_groq_endpoint(),
             auto_launch: false,
             noise_tolerance: 0.15,
             silence_duration: 1.5,
             auto_stop_duration: 30.0,
             newline_after_segment: false,
+            output_method: "paste".into(),
+            typing_rate: 120.0,
+            app_output_methods: HashMap::new(),
+            output_backend: "auto".into(),
+            smart_spacing: true,
+            output_routes: HashMap::new(),
+            preview_apps: Vec::new(),
+            output_file: default_output_file(),
+            output_webhook: String::new(),
+            undo_hotkey: "Alt+Shift+Z".into(),
+            undo_method: "erase".into(),
+            voice_corrections: true,
+            max_recording_duration: 600.0,
+            max_recording_memory_mb: 64.0,
+            chunk_duration: 120.0,
+            custom_commands: Vec::new(),
+            command_dry_run: false,
+            command_allowlist: Vec::new(),
+            command_denylist: Vec::new(),
+            confirm_destructive_commands: true,
+            url_allowed_schemes: default_url_schemes(),
+            url_allowed_domains: Vec::new(),
+            ai_endpoint: default_groq_endpoint(),
+            ai_api_key: None,
+            ai_model: "llama-3.3-70b-versatile".into(),
+            ai_output: "clipboard".into(),
             hallucination_filters: default_hallucinations(),
         }
     }
@@ -189,6 +425,15 @@ impl MillowConfig {
         }
     }
 
+    /// VAD eşik çarpanı — fısıltı modunda eşikler düşürülür
+    pub fn vad_scale(&self) -> f32 {
+        if self.whisper_mode {
+            crate::dsp::WHISPER_VAD_SCALE
+        } else {
+            1.0
+        }
+    }
+
     /// Ayarları dosyaya kaydet
     pub fn save(&self) {
         let path = Self::config_path();
@@ -0,0 +1,361 @@
+// Millow — Sesli Düzeltme
+// "bunu sil", "son cümleyi sil", "X yerine Y yaz" gibi komutları en az tuş vuruşlu düzenlemeye çevirir
+
+use crate::history::{self, keystrokes};
+
+/// Sesli düzeltme komutu
+#[derive(Debug, Clone, PartialEq)]
+pub enum Correction {
+    /// Son eklemeyi tamamen sil ("scratch that", "bunu sil")
+    ScratchThat,
+    /// Son kelimeyi sil
+    DeleteLastWord,
+    /// Son cümleyi sil
+    DeleteLastSentence,
+    /// Son geçen `from`'u `to` ile değiştir
+    Replace { from: String, to: String },
+}
+
+impl Correction {
+    /// Bildirim için kısa açıklama
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ScratchThat => "Son dikte silindi".into(),
+            Self::DeleteLastWord => "Son kelime silindi".into(),
+            Self::DeleteLastSentence => "Son cümle silindi".into(),
+            Self::Replace { from, to } => format!("\"{}\" → \"{}\"", from, to),
+        }
+    }
+}
+
+/// İmlecin bulunduğu yerden (metnin sonu) uygulanacak düzenleme:
+/// `back` kadar sola git, `erase` kadar sil, `insert` yaz, `forward` kadar sağa dön
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Edit {
+    pub back: usize,
+    pub erase: usize,
+    pub insert: String,
+    pub forward: usize,
+}
+
+const DELETE_WORD_PHRASES: &[&str] = &[
+    "son kelimeyi sil",
+    "delete last word",
+    "delete the last word",
+];
+
+const DELETE_SENTENCE_PHRASES: &[&str] = &[
+    "son cümleyi sil",
+    "delete last sentence",
+    "delete the last sentence",
+];
+
+/// Söylenen metin tamamen bir düzeltme komutuysa çözümle
+pub fn parse(text: &str) -> Option<Correction> {
+    if history::is_undo_phrase(text) {
+        return Some(Correction::ScratchThat);
+    }
+
+    let normalized = history::normalize(text);
+    let matches = |phrases: &[&str]| phrases.iter().any(|p| history::normalize(p) == normalized);
+    if matches(DELETE_WORD_PHRASES) {
+        return Some(Correction::DeleteLastWord);
+    }
+    if matches(DELETE_SENTENCE_PHRASES) {
+        return Some(Correction::DeleteLastSentence);
+    }
+
+    parse_replace(text)
+}
+
+/// "replace X with Y", "change X to Y", "X yerine Y yaz/koy/olsun", "X'i Y ile değiştir".
+/// Fiilsiz "X yerine Y …" düz cümledir ("Ahmet yerine Mehmet gelecek"), komut sayılmaz.
+fn parse_replace(text: &str) -> Option<Correction> {
+    let text = text.trim().trim_end_matches(['.', '!', '?', '…']).trim();
+
+    let (from, to) = if let Some(rest) = strip_prefix_ci(text, "replace ") {
+        split_last_ci(rest, " with ")?
+    } else if let Some(rest) = strip_prefix_ci(text, "change ") {
+        split_last_ci(rest, " to ")?
+    } else if let Some(rest) = strip_suffix_ci(text, " ile değiştir") {
+        if let Some((from, to)) = split_last_ci(rest, " kelimesini ") {
+            (from, to)
+        } else if let Some(pos) = rest.find(['\'', '’']) {
+            // X'i Y — kesme işaretinden sonraki hâl ekini at
+            let after = &rest[pos..];
+            let to = after.split_once(' ')?.1;
+            (&rest[..pos], to)
+        } else {
+            rest.split_once(' ')?
+        }
+    } else if let Some((from, to)) = split_last_ci(text, " yerine ") {
+        let to = ["yaz", "koy", "olsun"]
+            .iter()
+            .find_map(|verb| strip_suffix_ci(to, &format!(" {}", verb)))?;
+        (from, to)
+    } else {
+        return None;
+    };
+
+    let clean = |s: &str| {
+        s.trim()
+            .trim_matches(|c: char| c == '"' || c == '\'' || c == '“' || c == '”' || c == ',')
+            .trim()
+            .to_string()
+    };
+    let (from, to) = (clean(from), clean(to));
+    if from.is_empty() || to.is_empty() {
+        return None;
+    }
+    Some(Correction::Replace { from, to })
+}
+
+/// Düzeltmeyi uygulamaya yazılmış metne göre planla — (düzenleme, yeni metin).
+/// `ScratchThat` son eklemeye göre çalışır, burada planlanmaz.
+pub fn plan(buffer: &str, correction: &Correction) -> Result<(Edit, String), String> {
+    let chars: Vec<char> = buffer.chars().collect();
+    let keep = match correction {
+        Correction::ScratchThat => return Err("Son ekleme geri alma ile silinir".into()),
+        Correction::DeleteLastWord => last_word_start(&chars),
+        Correction::DeleteLastSentence => last_sentence_start(&chars),
+        Correction::Replace { from, to } => return plan_replace(&chars, from, to),
+    };
+    let Some(keep) = keep else {
+        return Err("Silinecek metin yok".into());
+    };
+
+    let removed: String = chars[keep..].iter().collect();
+    let edit = Edit {
+        erase: keystrokes(&removed),
+        ..Edit::default()
+    };
+    Ok((edit, chars[..keep].iter().collect()))
+}
+
+/// Son kelimenin (önündeki boşlukla birlikte) başladığı yer
+fn last_word_start(chars: &[char]) -> Option<usize> {
+    let mut end = chars.len();
+    while end > 0 && chars[end - 1].is_whitespace() {
+        end -= 1;
+    }
+    if end == 0 {
+        return None;
+    }
+    let mut start = end;
+    while start > 0 && !chars[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    // Satır sonlarını koru, yalnızca aynı satırdaki boşluğu sil
+    while start > 0 && chars[start - 1].is_whitespace() && chars[start - 1] != '\n' {
+        start -= 1;
+    }
+    Some(start)
+}
+
+/// Son cümlenin (önündeki boşlukla birlikte) başladığı yer
+fn last_sentence_start(chars: &[char]) -> Option<usize> {
+    let mut end = chars.len();
+    while end > 0 && chars[end - 1].is_whitespace() {
+        end -= 1;
+    }
+    // Cümlenin kendi bitiş noktalamasını atla
+    while end > 0 && is_sentence_end(chars[end - 1]) {
+        end -= 1;
+    }
+    if end == 0 {
+        return None;
+    }
+    let mut start = end;
+    while start > 0 && !is_sentence_end(chars[start - 1]) && chars[start - 1] != '\n' {
+        start -= 1;
+    }
+    while start > 0 && chars[start - 1].is_whitespace() && chars[start - 1] != '\n' {
+        start -= 1;
+    }
+    Some(start)
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…')
+}
+
+/// Son geçen `from`'u bul, ortak ön/son ekleri koruyarak yalnızca farklı kısmı yeniden yaz
+fn plan_replace(chars: &[char], from: &str, to: &str) -> Result<(Edit, String), String> {
+    let needle: Vec<char> = from.chars().collect();
+    if to.is_empty() {
+        return Err("Yeni metin boş".into());
+    }
+    let start = find_last(chars, &needle)
+        .ok_or_else(|| format!("\"{}\" son yazılanlarda bulunamadı", from))?;
+    let end = start + needle.len();
+
+    // Büyük harfle başlıyorsa (cümle başı) yeni kelime de öyle başlasın
+    let mut replacement: Vec<char> = to.chars().collect();
+    if chars[start].is_uppercase() && replacement[0].is_lowercase() {
+        let upper: Vec<char> = replacement[0].to_uppercase().collect();
+        replacement.splice(0..1, upper);
+    }
+
+    let old = &chars[start..end];
+    let prefix = old
+        .iter()
+        .zip(&replacement)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(replacement[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let tail: String = chars[end - suffix..].iter().collect();
+    let erased: String = old[prefix..old.len() - suffix].iter().collect();
+    let inserted: String = replacement[prefix..replacement.len() - suffix].iter().collect();
+    let edit = Edit {
+        back: keystrokes(&tail),
+        erase: keystrokes(&erased),
+        insert: inserted,
+        forward: keystrokes(&tail),
+    };
+
+    let mut updated: String = chars[..start].iter().collect();
+    updated.extend(&replacement);
+    updated.extend(&chars[end..]);
+    Ok((edit, updated))
+}
+
+/// Büyük/küçük harf duyarsız son eşleşme — tam kelime eşleşmesi öncelikli
+fn find_last(haystack: &[char], needle: &[char]) -> Option<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+    let is_boundary = |i: usize| i == 0 || !haystack[i - 1].is_alphanumeric();
+    let candidates: Vec<usize> = (0..=haystack.len() - needle.len())
+        .rev()
+        .filter(|&i| {
+            haystack[i..i + needle.len()]
+                .iter()
+                .zip(needle)
+                .all(|(&a, &b)| fold(a) == fold(b))
+        })
+        .collect();
+    candidates
+        .iter()
+        .copied()
+        .find(|&i| {
+            is_boundary(i) && !matches!(haystack.get(i + needle.len()), Some(c) if c.is_alphanumeric())
+        })
+        .or_else(|| candidates.first().copied())
+}
+
+/// Karşılaştırma için harf katlama (Türkçe i/ı farkı yok sayılır)
+fn fold(c: char) -> char {
+    match c.to_lowercase().next().unwrap_or(c) {
+        'ı' => 'i',
+        other => other,
+    }
+}
+
+fn strip_prefix_ci<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let len = prefix.len();
+    (text.len() >= len && text.is_char_boundary(len) && fold_eq(&text[..len], prefix))
+        .then(|| &text[len..])
+}
+
+fn strip_suffix_ci<'a>(text: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = text.len().checked_sub(suffix.len())?;
+    (text.is_char_boundary(start) && fold_eq(&text[start..], suffix)).then(|| &text[..start])
+}
+
+/// `separator`'ın son geçtiği yerden ikiye böl
+fn split_last_ci<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let len = separator.len();
+    (0..text.len().saturating_sub(len - 1))
+        .rev()
+        .filter(|&i| text.is_char_boundary(i) && text.is_char_boundary(i + len))
+        .find(|&i| fold_eq(&text[i..i + len], separator))
+        .map(|i| (&text[..i], &text[i + len..]))
+}
+
+fn fold_eq(a: &str, b: &str) -> bool {
+    a.chars().map(fold).eq(b.chars().map(fold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Düzenlemeyi imleç metnin sonundayken tuş tuş uygula
+    fn apply(buffer: &str, edit: &Edit) -> String {
+        let mut chars: Vec<char> = buffer.chars().collect();
+        let mut cursor = chars.len() - edit.back;
+        for _ in 0..edit.erase {
+            cursor -= 1;
+            chars.remove(cursor);
+        }
+        for c in edit.insert.chars() {
+            chars.insert(cursor, c);
+            cursor += 1;
+        }
+        assert_eq!(cursor + edit.forward, chars.len());
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn plans_minimal_edits() {
+        let cases = [
+            (
+                "Kedi bahçede oynuyor. Hava güzel.",
+                "kedi yerine köpek yaz",
+                "Köpek bahçede oynuyor. Hava güzel.",
+            ),
+            ("I like the cat a lot.", "Replace cat with dog.", "I like the dog a lot."),
+            ("I like the cat a lot.", "change cat to cats", "I like the cats a lot."),
+            (
+                "Toplantı yarın saat üçte.",
+                "yarın kelimesini bugün ile değiştir",
+                "Toplantı bugün saat üçte.",
+            ),
+            (
+                "Toplantı yarın saat üçte.",
+                "yarın'ı bugün ile değiştir",
+                "Toplantı bugün saat üçte.",
+            ),
+            ("Merhaba dünya. Nasılsın bugün?", "son cümleyi sil", "Merhaba dünya."),
+            (
+                "Merhaba dünya. Nasılsın bugün?",
+                "Delete last word.",
+                "Merhaba dünya. Nasılsın",
+            ),
+            ("Tek cümle.", "son cümleyi sil", ""),
+        ];
+        for (buffer, command, expected) in cases {
+            let correction = parse(command).unwrap_or_else(|| panic!("çözülemedi: {}", command));
+            let (edit, updated) = plan(buffer, &correction).unwrap();
+            assert_eq!(updated, expected);
+            assert_eq!(apply(buffer, &edit), expected);
+        }
+    }
+
+    #[test]
+    fn ordinary_sentences_are_not_corrections() {
+        for text in [
+            "bugün hava çok güzel",
+            "Ahmet yerine Mehmet gelecek",
+            "Toplantı yerine kısa bir görüşme yapalım.",
+        ] {
+            assert_eq!(parse(text), None, "{}", text);
+        }
+        assert_eq!(parse("Bunu sil."), Some(Correction::ScratchThat));
+        assert_eq!(parse("scratch that"), Some(Correction::ScratchThat));
+    }
+
+    #[test]
+    fn replace_needs_target_in_buffer() {
+        // Komut biçimindeki cümle, hedefi yazılmamışsa planlanamaz → dikte olarak yazılır
+        let correction = parse("Change the title to Budget").unwrap();
+        assert!(plan("Merhaba dünya.", &correction).is_err());
+        assert!(plan("The title is draft.", &correction).is_ok());
+    }
+}
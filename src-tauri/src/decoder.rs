@@ -0,0 +1,111 @@
+// Millow — Ses Dosyası Çözücü
+// WAV dosyalarını doğrudan okur; MP3/M4A/OGG/FLAC için ffmpeg (yoksa afconvert) ile WAV'a çevirir
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Desteklenen dosya uzantıları
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "ogg", "flac"];
+
+/// Ses dosyasını mono PCM örneklerine çöz — (örnekler, örnekleme hızı)
+pub fn decode_file(path: &Path) -> Result<(Vec<i16>, u32), String> {
+    if !path.is_file() {
+        return Err(format!("Dosya bulunamadı: {}", path.display()));
+    }
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !SUPPORTED_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(format!(
+            "Desteklenmeyen dosya türü: .{} (desteklenenler: {})",
+            ext,
+            SUPPORTED_EXTENSIONS.join(", ")
+        ));
+    }
+
+    // Düz PCM WAV ise dönüştürmeye gerek yok
+    if ext == "wav" {
+        if let Ok(decoded) = read_wav(path) {
+            return Ok(decoded);
+        }
+    }
+
+    let tmp = temp_wav_path();
+    let result = convert_to_wav(path, &tmp).and_then(|_| read_wav(&tmp));
+    let _ = std::fs::remove_file(&tmp);
+    result
+}
+
+/// WAV dosyasını oku, kanalları ortalayarak mono'ya indir
+pub fn read_wav(path: &Path) -> Result<(Vec<i16>, u32), String> {
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| format!("WAV okunamadı: {}", e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("WAV örneği okunamadı: {}", e))?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("WAV örneği okunamadı: {}", e))?
+        }
+    };
+
+    let mono = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .map(|s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
+        .collect();
+
+    Ok((mono, spec.sample_rate))
+}
+
+/// Sıkıştırılmış dosyayı 16-bit mono WAV'a çevir
+fn convert_to_wav(input: &Path, output: &Path) -> Result<(), String> {
+    // ffmpeg tüm formatları destekler
+    let ffmpeg_err = match Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(input)
+        .args(["-ac", "1", "-c:a", "pcm_s16le"])
+        .arg(output)
+        .output()
+    {
+        Ok(out) if out.status.success() => return Ok(()),
+        Ok(out) => String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        Err(_) => "ffmpeg bulunamadı".to_string(),
+    };
+
+    // macOS'ta yerleşik afconvert (OGG desteklemez)
+    #[cfg(target_os = "macos")]
+    {
+        let out = Command::new("afconvert")
+            .args(["-f", "WAVE", "-d", "LEI16", "-c", "1"])
+            .arg(input)
+            .arg(output)
+            .output()
+            .map_err(|e| format!("afconvert hatası: {}", e))?;
+        if out.status.success() {
+            return Ok(());
+        }
+    }
+
+    Err(format!("Ses dosyası çözülemedi ({}): {}", input.display(), ffmpeg_err))
+}
+
+fn temp_wav_path() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("millow_decode_{}_{}.wav", std::process::id(), nanos))
+}
@@ -0,0 +1,554 @@
+// Millow — Masaüstü Eylemleri
+// Yerleşik komutların sistem çağrıları: macOS (open, osascript, networksetup…) ve Linux (xdg-open, wpctl/pactl, nmcli, D-Bus…)
+
+use crate::commander::{Executor, Invocation};
+use crate::media::{self, MediaKey};
+use std::path::{Path, PathBuf};
+
+/// Desteklenen işletim sistemleri
+pub const PLATFORMS: &[&str] = &["macos", "linux"];
+
+/// Komutların platforma özgü arka ucu — desteklenmeyen eylem açık bir hata döndürür
+pub trait Desktop: Send + Sync {
+    fn open_app(&self, _exec: &dyn Executor, _app: &str) -> Result<(), String> {
+        unsupported("Uygulama açma")
+    }
+
+    fn open_url(&self, _exec: &dyn Executor, _url: &str) -> Result<(), String> {
+        unsupported("Adres açma")
+    }
+
+    /// Ekran görüntüsünü `path`'e kaydet
+    fn screenshot(&self, _exec: &dyn Executor, _path: &str) -> Result<(), String> {
+        unsupported("Ekran görüntüsü")
+    }
+
+    /// Ses seviyesini yüzde olarak değiştir
+    fn change_volume(&self, _exec: &dyn Executor, _delta: i32) -> Result<(), String> {
+        unsupported("Ses kontrolü")
+    }
+
+    fn toggle_mute(&self, _exec: &dyn Executor) -> Result<(), String> {
+        unsupported("Sessize alma")
+    }
+
+    /// Parlaklığı yüzde olarak değiştir
+    fn change_brightness(&self, _exec: &dyn Executor, _delta: i32) -> Result<(), String> {
+        unsupported("Parlaklık kontrolü")
+    }
+
+    /// Sonucu anlatan mesaj döner
+    fn toggle_dark_mode(&self, _exec: &dyn Executor) -> Result<String, String> {
+        unsupported("Karanlık mod")
+    }
+
+    fn lock_screen(&self, _exec: &dyn Executor) -> Result<(), String> {
+        unsupported("Ekran kilitleme")
+    }
+
+    /// Sonucu anlatan mesaj döner
+    fn toggle_wifi(&self, _exec: &dyn Executor) -> Result<String, String> {
+        unsupported("Wi-Fi kontrolü")
+    }
+
+    fn bluetooth_settings(&self, _exec: &dyn Executor) -> Result<(), String> {
+        unsupported("Bluetooth ayarları")
+    }
+
+    /// Cmd/Ctrl + `key` kısayolunu odaktaki uygulamaya gönder
+    fn shortcut(&self, _exec: &dyn Executor, _key: char) -> Result<(), String> {
+        unsupported("Klavye kısayolu")
+    }
+
+    fn read_clipboard(&self, _exec: &dyn Executor) -> Result<String, String> {
+        unsupported("Pano okuma")
+    }
+
+    fn write_clipboard(&self, _exec: &dyn Executor, _text: &str) -> Result<(), String> {
+        unsupported("Panoya yazma")
+    }
+
+    /// Medya tuşunu çalan oynatıcıya gönder, sonucu anlatan mesaj döner
+    fn media_key(&self, _exec: &dyn Executor, _key: MediaKey) -> Result<String, String> {
+        unsupported("Medya kontrolü")
+    }
+}
+
+/// Bu platformun arka ucu
+pub fn current() -> Box<dyn Desktop> {
+    if cfg!(target_os = "macos") {
+        Box::new(MacDesktop)
+    } else if cfg!(target_os = "linux") {
+        Box::new(LinuxDesktop::detect())
+    } else {
+        Box::new(UnsupportedDesktop)
+    }
+}
+
+fn unsupported<T>(action: &str) -> Result<T, String> {
+    Err(format!(
+        "{} bu platformda desteklenmiyor ({})",
+        action,
+        std::env::consts::OS
+    ))
+}
+
+/// Sırayla dene, ilk başarılının çıktısını döndür ("wpctl yoksa pactl")
+fn first_ok(exec: &dyn Executor, candidates: &[Invocation]) -> Result<String, String> {
+    let mut errors = Vec::new();
+    for invocation in candidates {
+        match exec.execute(invocation) {
+            Ok(output) => return Ok(output),
+            Err(e) => match invocation {
+                Invocation::Run { program, .. } | Invocation::Spawn { program, .. } => {
+                    errors.push(format!("{}: {}", program, e))
+                }
+                _ => errors.push(e),
+            },
+        }
+    }
+    Err(errors.join("; "))
+}
+
+/// .desktop kayıtlarının arandığı dizinler (XDG_DATA_HOME ve XDG_DATA_DIRS altındaki applications)
+fn application_dirs() -> Vec<PathBuf> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| format!("{}/.local/share", home));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    std::iter::once(data_home.as_str())
+        .chain(data_dirs.split(':'))
+        .map(|dir| Path::new(dir).join("applications"))
+        .collect()
+}
+
+/// Uygulamanın .desktop kaydı — önce dosya adı ("firefox", "org.gnome.Nautilus", "nautilus"),
+/// sonra görünen ad ("Visual Studio Code")
+fn find_desktop_entry(app: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    let wanted = app.trim().to_lowercase();
+    if wanted.is_empty() {
+        return None;
+    }
+    let entries: Vec<PathBuf> = dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.filter_map(|entry| entry.ok().map(|e| e.path())))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+        .collect();
+
+    let id_matches = |path: &PathBuf| {
+        let id = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        id == wanted || id.rsplit('.').next() == Some(wanted.as_str())
+    };
+    let name_matches = |path: &PathBuf| {
+        std::fs::read_to_string(path).ok().is_some_and(|content| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix("Name="))
+                .is_some_and(|name| name.trim().to_lowercase() == wanted)
+        })
+    };
+    entries
+        .iter()
+        .find(|path| id_matches(path))
+        .or_else(|| entries.iter().find(|path| name_matches(path)))
+        .cloned()
+}
+
+/// `printf '%s' "$1" | <komut>` — metin kabuğa yorumlanmadan argüman olarak geçer
+fn pipe_to(command: &str, text: &str) -> Invocation {
+    let script = format!("printf '%s' \"$1\" | {}", command);
+    Invocation::run("sh", &["-c", &script, "millow", text])
+}
+
+// ── macOS ──
+
+pub struct MacDesktop;
+
+impl MacDesktop {
+    fn osascript(exec: &dyn Executor, script: &str) -> Result<(), String> {
+        exec.execute(&Invocation::run("osascript", &["-e", script]))
+            .map_err(|e| format!("AppleScript hatası: {}", e))?;
+        Ok(())
+    }
+}
+
+impl Desktop for MacDesktop {
+    fn open_app(&self, exec: &dyn Executor, app: &str) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("open", &["-a", app]))?;
+        Ok(())
+    }
+
+    fn open_url(&self, exec: &dyn Executor, url: &str) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("open", &[url]))?;
+        Ok(())
+    }
+
+    fn screenshot(&self, exec: &dyn Executor, path: &str) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("screencapture", &["-x", path]))?;
+        Ok(())
+    }
+
+    fn change_volume(&self, exec: &dyn Executor, delta: i32) -> Result<(), String> {
+        Self::osascript(
+            exec,
+            &format!(
+                "set volume output volume ((output volume of (get volume settings)) + {})",
+                delta
+            ),
+        )
+    }
+
+    fn toggle_mute(&self, exec: &dyn Executor) -> Result<(), String> {
+        Self::osascript(
+            exec,
+            "set volume output muted not (output muted of (get volume settings))",
+        )
+    }
+
+    fn change_brightness(&self, exec: &dyn Executor, delta: i32) -> Result<(), String> {
+        // Parlaklık tuşları: 144 artır, 145 azalt
+        let key_code = if delta > 0 { 144 } else { 145 };
+        Self::osascript(
+            exec,
+            &format!(
+                "tell application \"System Events\" to key code {}",
+                key_code
+            ),
+        )
+    }
+
+    fn toggle_dark_mode(&self, exec: &dyn Executor) -> Result<String, String> {
+        Self::osascript(
+            exec,
+            "tell application \"System Events\" to tell appearance preferences to set dark mode to not dark mode",
+        )?;
+        Ok("Karanlık mod değiştirildi".into())
+    }
+
+    fn lock_screen(&self, exec: &dyn Executor) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("pmset", &["displaysleepnow"]))?;
+        Ok(())
+    }
+
+    fn toggle_wifi(&self, exec: &dyn Executor) -> Result<String, String> {
+        let status = exec
+            .execute(&Invocation::run(
+                "networksetup",
+                &["-getairportpower", "en0"],
+            ))
+            .map_err(|e| format!("Wi-Fi kontrolü başarısız: {}", e))?;
+        let turn_on = !status.contains("On");
+        exec.execute(&Invocation::spawn(
+            "networksetup",
+            &[
+                "-setairportpower",
+                "en0",
+                if turn_on { "on" } else { "off" },
+            ],
+        ))?;
+        Ok(wifi_message(turn_on))
+    }
+
+    fn bluetooth_settings(&self, exec: &dyn Executor) -> Result<(), String> {
+        Self::osascript(
+            exec,
+            r#"tell application "System Preferences" to reveal pane id "com.apple.preferences.Bluetooth""#,
+        )
+    }
+
+    fn shortcut(&self, exec: &dyn Executor, key: char) -> Result<(), String> {
+        Self::osascript(
+            exec,
+            &format!(
+                "tell application \"System Events\" to keystroke \"{}\" using command down",
+                key
+            ),
+        )
+    }
+
+    fn read_clipboard(&self, exec: &dyn Executor) -> Result<String, String> {
+        exec.execute(&Invocation::run("pbpaste", &[]))
+    }
+
+    fn write_clipboard(&self, exec: &dyn Executor, text: &str) -> Result<(), String> {
+        exec.execute(&pipe_to("pbcopy", text))?;
+        Ok(())
+    }
+
+    fn media_key(&self, exec: &dyn Executor, key: MediaKey) -> Result<String, String> {
+        // Sistem medya tuşu — Music, Spotify, tarayıcı; "Şimdi Oynatılıyor"un sahibi hangisiyse
+        exec.execute(&Invocation::MediaKey { key })?;
+        Ok(key.label().into())
+    }
+}
+
+// ── Linux ──
+
+pub struct LinuxDesktop {
+    /// Wayland oturumu mu (X11 değil)
+    pub wayland: bool,
+}
+
+impl LinuxDesktop {
+    /// Oturum türünü ortam değişkenlerinden belirle
+    pub fn detect() -> Self {
+        let session = std::env::var("XDG_SESSION_TYPE").unwrap_or_default();
+        Self {
+            wayland: std::env::var_os("WAYLAND_DISPLAY").is_some() || session == "wayland",
+        }
+    }
+}
+
+impl Desktop for LinuxDesktop {
+    fn open_app(&self, exec: &dyn Executor, app: &str) -> Result<(), String> {
+        // Yalnızca .desktop kayıtları açılır — söylenen ad program olarak çalıştırılmaz
+        let candidates = match find_desktop_entry(app, &application_dirs()) {
+            Some(path) => {
+                let id = path.file_stem().unwrap_or_default().to_string_lossy();
+                vec![
+                    Invocation::run("gtk-launch", &[&id]),
+                    Invocation::run("gio", &["launch", &path.to_string_lossy()]),
+                ]
+            }
+            // Dizinlerde bulunamasa da gtk-launch kendi aramasını yapar
+            None => vec![Invocation::run("gtk-launch", &[app])],
+        };
+        first_ok(exec, &candidates)
+            .map_err(|e| format!("'{}' uygulaması bulunamadı ({})", app, e))?;
+        Ok(())
+    }
+
+    fn open_url(&self, exec: &dyn Executor, url: &str) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("xdg-open", &[url]))?;
+        Ok(())
+    }
+
+    fn screenshot(&self, exec: &dyn Executor, path: &str) -> Result<(), String> {
+        let grim = Invocation::run("grim", &[path]);
+        let gnome = Invocation::run("gnome-screenshot", &["-f", path]);
+        let candidates = if self.wayland {
+            [grim, gnome]
+        } else {
+            [gnome, grim]
+        };
+        first_ok(exec, &candidates)?;
+        Ok(())
+    }
+
+    fn change_volume(&self, exec: &dyn Executor, delta: i32) -> Result<(), String> {
+        let sign = if delta < 0 { '-' } else { '+' };
+        let step = delta.unsigned_abs();
+        // PipeWire (wpctl), yoksa PulseAudio (pactl); en fazla %100
+        first_ok(
+            exec,
+            &[
+                Invocation::run(
+                    "wpctl",
+                    &[
+                        "set-volume",
+                        "-l",
+                        "1.0",
+                        "@DEFAULT_AUDIO_SINK@",
+                        &format!("{}%{}", step, sign),
+                    ],
+                ),
+                Invocation::run(
+                    "pactl",
+                    &[
+                        "set-sink-volume",
+                        "@DEFAULT_SINK@",
+                        &format!("{}{}%", sign, step),
+                    ],
+                ),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn toggle_mute(&self, exec: &dyn Executor) -> Result<(), String> {
+        first_ok(
+            exec,
+            &[
+                Invocation::run("wpctl", &["set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"]),
+                Invocation::run("pactl", &["set-sink-mute", "@DEFAULT_SINK@", "toggle"]),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn change_brightness(&self, exec: &dyn Executor, delta: i32) -> Result<(), String> {
+        let sign = if delta < 0 { '-' } else { '+' };
+        let value = format!("{}%{}", delta.unsigned_abs(), sign);
+        exec.execute(&Invocation::run("brightnessctl", &["set", &value]))?;
+        Ok(())
+    }
+
+    fn toggle_dark_mode(&self, exec: &dyn Executor) -> Result<String, String> {
+        // Geçerli tercih XDG masaüstü portalından (D-Bus) okunur: 1 koyu, 0/2 varsayılan/açık
+        let current = first_ok(
+            exec,
+            &[
+                Invocation::run(
+                    "dbus-send",
+                    &[
+                        "--session",
+                        "--print-reply",
+                        "--dest=org.freedesktop.portal.Desktop",
+                        "/org/freedesktop/portal/desktop",
+                        "org.freedesktop.portal.Settings.Read",
+                        "string:org.freedesktop.appearance",
+                        "string:color-scheme",
+                    ],
+                ),
+                Invocation::run(
+                    "gsettings",
+                    &["get", "org.gnome.desktop.interface", "color-scheme"],
+                ),
+            ],
+        )
+        .unwrap_or_default();
+        let dark = current.contains("uint32 1") || current.contains("prefer-dark");
+        // Portal salt okunur; tercih GNOME ayarına (dconf) yazılır, portal değişikliği uygulamalara yayar
+        exec.execute(&Invocation::run(
+            "gsettings",
+            &[
+                "set",
+                "org.gnome.desktop.interface",
+                "color-scheme",
+                if dark { "default" } else { "prefer-dark" },
+            ],
+        ))?;
+        Ok(if dark {
+            "Karanlık mod kapatıldı".into()
+        } else {
+            "Karanlık mod açıldı".into()
+        })
+    }
+
+    fn lock_screen(&self, exec: &dyn Executor) -> Result<(), String> {
+        exec.execute(&Invocation::run("loginctl", &["lock-session"]))?;
+        Ok(())
+    }
+
+    fn toggle_wifi(&self, exec: &dyn Executor) -> Result<String, String> {
+        let status = exec
+            .execute(&Invocation::run("nmcli", &["radio", "wifi"]))
+            .map_err(|e| format!("Wi-Fi kontrolü başarısız: {}", e))?;
+        let turn_on = status.trim() != "enabled";
+        exec.execute(&Invocation::run(
+            "nmcli",
+            &["radio", "wifi", if turn_on { "on" } else { "off" }],
+        ))?;
+        Ok(wifi_message(turn_on))
+    }
+
+    fn bluetooth_settings(&self, exec: &dyn Executor) -> Result<(), String> {
+        exec.execute(&Invocation::spawn("gnome-control-center", &["bluetooth"]))?;
+        Ok(())
+    }
+
+    fn shortcut(&self, exec: &dyn Executor, key: char) -> Result<(), String> {
+        exec.execute(&Invocation::Keys {
+            keys: vec![format!("ctrl+{}", key)],
+        })?;
+        Ok(())
+    }
+
+    fn read_clipboard(&self, exec: &dyn Executor) -> Result<String, String> {
+        if self.wayland {
+            exec.execute(&Invocation::run("wl-paste", &["--no-newline"]))
+        } else {
+            first_ok(
+                exec,
+                &[
+                    Invocation::run("xclip", &["-selection", "clipboard", "-o"]),
+                    Invocation::run("xsel", &["--clipboard", "--output"]),
+                ],
+            )
+        }
+    }
+
+    fn write_clipboard(&self, exec: &dyn Executor, text: &str) -> Result<(), String> {
+        if self.wayland {
+            exec.execute(&pipe_to("wl-copy", text))?;
+        } else {
+            first_ok(
+                exec,
+                &[
+                    pipe_to("xclip -selection clipboard", text),
+                    pipe_to("xsel --clipboard --input", text),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn media_key(&self, exec: &dyn Executor, key: MediaKey) -> Result<String, String> {
+        media::mpris(exec, key)
+    }
+}
+
+// ── Diğer ──
+
+/// Arka ucu olmayan platform — tüm eylemler hata döndürür
+pub struct UnsupportedDesktop;
+
+impl Desktop for UnsupportedDesktop {}
+
+fn wifi_message(on: bool) -> String {
+    format!("Wi-Fi {}", if on { "açıldı" } else { "kapatıldı" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_desktop_entries_by_id_and_name() {
+        let root = std::env::temp_dir().join(format!("millow-apps-{}", std::process::id()));
+        let (user, system) = (root.join("user"), root.join("system"));
+        for dir in [&user, &system] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let write = |dir: &PathBuf, file: &str, name: &str| {
+            let content = format!("[Desktop Entry]\nType=Application\nName={}\n", name);
+            std::fs::write(dir.join(file), content).unwrap();
+        };
+        write(&user, "code.desktop", "Visual Studio Code");
+        write(&system, "org.gnome.Nautilus.desktop", "Files");
+        write(&system, "firefox.desktop", "Firefox Web Browser");
+        std::fs::write(system.join("notes.txt"), "Name=Notes").unwrap();
+
+        let dirs = [user.clone(), system.clone(), root.join("yok")];
+        let find = |app: &str| {
+            find_desktop_entry(app, &dirs)
+                .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        };
+        assert_eq!(find("Firefox").as_deref(), Some("firefox.desktop"));
+        assert_eq!(
+            find("nautilus").as_deref(),
+            Some("org.gnome.Nautilus.desktop")
+        );
+        assert_eq!(
+            find("org.gnome.Nautilus").as_deref(),
+            Some("org.gnome.Nautilus.desktop")
+        );
+        assert_eq!(find("visual studio code").as_deref(), Some("code.desktop"));
+        assert_eq!(find("files").as_deref(), Some("org.gnome.Nautilus.desktop"));
+        assert_eq!(find("notes"), None);
+        assert_eq!(find("sh"), None);
+        assert_eq!(find(" "), None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
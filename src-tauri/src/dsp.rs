@@ -0,0 +1,412 @@
+// Millow — Ses Ön İşleme Zinciri
+// Kodlamadan önce: DC temizleme → yüksek geçiren filtre → spektral gürültü bastırma → otomatik kazanç
+
+use crate::config::MillowConfig;
+use std::f32::consts::PI;
+
+/// Fısıltı modunda VAD eşikleri bu oranla düşürülür
+pub const WHISPER_VAD_SCALE: f32 = 0.4;
+
+/// DSP zinciri ayarları
+#[derive(Debug, Clone, Default)]
+pub struct DspSettings {
+    /// Zincir tamamen açık/kapalı
+    pub enabled: bool,
+    /// Yüksek geçiren filtre kesim frekansı (Hz, 0 = kapalı)
+    pub highpass_hz: f32,
+    /// Spektral çıkarma gücü (0 = kapalı, 1 = normal, 2 = agresif)
+    pub noise_suppression: f32,
+    /// Otomatik kazanç/normalizasyon
+    pub agc: bool,
+    /// Hedef konuşma seviyesi (RMS, tam ölçeğe oranla)
+    pub target_level: f32,
+    /// En fazla uygulanacak kazanç
+    pub max_gain: f32,
+}
+
+impl DspSettings {
+    /// Config'den ayarları oluştur — fısıltı modu kazancı artıran bir ön ayardır
+    pub fn from_config(config: &MillowConfig) -> Self {
+        let mut settings = Self {
+            enabled: config.dsp_enabled,
+            highpass_hz: config.highpass_cutoff,
+            noise_suppression: config.noise_suppression,
+            agc: config.agc_enabled,
+            target_level: config.agc_target_level,
+            max_gain: 8.0,
+        };
+        if config.whisper_mode {
+            settings.agc = true;
+            settings.max_gain = 30.0;
+            settings.target_level = settings.target_level.max(0.15);
+        }
+        settings
+    }
+}
+
+/// Zinciri uygula, aynı uzunlukta işlenmiş örnekleri döndür
+pub fn process(samples: &[i16], rate: u32, settings: &DspSettings) -> Vec<i16> {
+    if !settings.enabled || samples.is_empty() || rate == 0 {
+        return samples.to_vec();
+    }
+
+    let mut x: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
+
+    remove_dc(&mut x);
+    if settings.highpass_hz > 0.0 {
+        highpass(&mut x, rate, settings.highpass_hz);
+    }
+    if settings.noise_suppression > 0.0 {
+        x = suppress_noise(&x, rate, settings.noise_suppression);
+    }
+    if settings.agc {
+        normalize(&mut x, rate, settings.target_level, settings.max_gain);
+    }
+
+    x.iter()
+        .map(|&s| (s * 32768.0).clamp(-32768.0, 32767.0) as i16)
+        .collect()
+}
+
+/// Tek kutuplu DC engelleyici: y[n] = x[n] - x[n-1] + R·y[n-1]
+fn remove_dc(x: &mut [f32]) {
+    const R: f32 = 0.995;
+    let mut prev_x = 0.0;
+    let mut prev_y = 0.0;
+    for s in x.iter_mut() {
+        let y = *s - prev_x + R * prev_y;
+        prev_x = *s;
+        prev_y = y;
+        *s = y;
+    }
+}
+
+/// 2. derece Butterworth yüksek geçiren biquad (RBJ)
+fn highpass(x: &mut [f32], rate: u32, cutoff: f32) {
+    let cutoff = cutoff.min(rate as f32 * 0.45);
+    let w0 = 2.0 * PI * cutoff / rate as f32;
+    let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+    let cos_w0 = w0.cos();
+
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 + cos_w0) / 2.0 / a0;
+    let b1 = -(1.0 + cos_w0) / a0;
+    let b2 = b0;
+    let a1 = -2.0 * cos_w0 / a0;
+    let a2 = (1.0 - alpha) / a0;
+
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+    for s in x.iter_mut() {
+        let y = b0 * *s + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+        x2 = x1;
+        x1 = *s;
+        y2 = y1;
+        y1 = y;
+        *s = y;
+    }
+}
+
+/// Spektral çıkarma ile durağan gürültüyü bastır.
+/// Gürültü spektrumu kaydın en sessiz %10'luk çerçevelerinden tahmin edilir.
+fn suppress_noise(x: &[f32], rate: u32, strength: f32) -> Vec<f32> {
+    let n = ((rate as f32 * 0.032) as usize).next_power_of_two().max(256);
+    let hop = n / 2;
+    if x.len() < n * 4 {
+        return x.to_vec();
+    }
+
+    // Kenarlarda da pencere toplamı 1 olsun diye başa/sona sıfır ekle
+    let mut padded = vec![0.0f32; hop];
+    padded.extend_from_slice(x);
+    padded.resize(padded.len() + n, 0.0);
+
+    let window: Vec<f32> = (0..n)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / n as f32).cos())
+        .collect();
+    let frame_count = (padded.len() - n) / hop + 1;
+
+    // 1. geçiş: en sessiz çerçevelerden gürültü büyüklük spektrumu
+    let mut energies: Vec<(usize, f32)> = (0..frame_count)
+        .map(|f| {
+            let start = f * hop;
+            (f, padded[start..start + n].iter().map(|s| s * s).sum::<f32>())
+        })
+        .filter(|&(_, e)| e > 0.0)
+        .collect();
+    if energies.is_empty() {
+        return x.to_vec();
+    }
+    energies.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let quiet = &energies[..(energies.len() / 10).max(1)];
+
+    let mut noise = vec![0.0f32; n];
+    let mut re = vec![0.0f32; n];
+    let mut im = vec![0.0f32; n];
+    for &(f, _) in quiet {
+        let start = f * hop;
+        for i in 0..n {
+            re[i] = padded[start + i] * window[i];
+            im[i] = 0.0;
+        }
+        fft(&mut re, &mut im, false);
+        for k in 0..n {
+            noise[k] += (re[k] * re[k] + im[k] * im[k]).sqrt();
+        }
+    }
+    for v in noise.iter_mut() {
+        *v /= quiet.len() as f32;
+    }
+
+    // 2. geçiş: her çerçeveye kazanç uygula, üst üste ekle (Hann %50 örtüşme → toplam 1)
+    const FLOOR: f32 = 0.1;
+    let mut out = vec![0.0f32; padded.len()];
+    for f in 0..frame_count {
+        let start = f * hop;
+        for i in 0..n {
+            re[i] = padded[start + i] * window[i];
+            im[i] = 0.0;
+        }
+        fft(&mut re, &mut im, false);
+        for k in 0..n {
+            let mag = (re[k] * re[k] + im[k] * im[k]).sqrt();
+            let gain = (1.0 - strength * noise[k] / (mag + 1e-9)).max(FLOOR);
+            re[k] *= gain;
+            im[k] *= gain;
+        }
+        fft(&mut re, &mut im, true);
+        for i in 0..n {
+            out[start + i] += re[i];
+        }
+    }
+
+    out[hop..hop + x.len()].to_vec()
+}
+
+/// Konuşma seviyesini hedefe çek; tepe değeri kırpılmayacak şekilde sınırla
+fn normalize(x: &mut [f32], rate: u32, target_level: f32, max_gain: f32) {
+    let window = (rate as usize / 50).max(1);
+    let mut levels: Vec<f32> = x
+        .chunks(window)
+        .map(|w| (w.iter().map(|s| s * s).sum::<f32>() / w.len() as f32).sqrt())
+        .collect();
+    levels.sort_by(|a, b| b.total_cmp(a));
+
+    // Konuşma seviyesi: en yüksek yarının ortalaması (sessizlikler hesaba katılmaz)
+    let loud = &levels[..(levels.len() / 2).max(1)];
+    let level = loud.iter().sum::<f32>() / loud.len() as f32;
+    let peak = x.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    if level <= 1e-6 || peak <= 1e-6 {
+        return;
+    }
+
+    let gain = (target_level / level).clamp(0.5, max_gain).min(0.98 / peak);
+    for s in x.iter_mut() {
+        *s *= gain;
+    }
+}
+
+/// Yerinde radix-2 FFT (n ikinin kuvveti olmalı)
+fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let n = re.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f32;
+        let (w_re, w_im) = (angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let (mut c_re, mut c_im) = (1.0f32, 0.0f32);
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * c_re - im[b] * c_im;
+                let t_im = re[b] * c_im + im[b] * c_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                let next = c_re * w_re - c_im * w_im;
+                c_im = c_re * w_im + c_im * w_re;
+                c_re = next;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        for i in 0..n {
+            re[i] /= n as f32;
+            im[i] /= n as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioEngine;
+
+    /// tests/fixtures altındaki 16 kHz mono kayıtlar:
+    /// - noisy_speech.wav: 1–2 sn arası sesli konuşma; baştan sona beyaz gürültü, DC kayması ve 50 Hz uğultu
+    /// - whisper.wav: 1–2 sn arası çok kısık konuşma, hafif gürültü
+    fn fixture(name: &str) -> (Vec<i16>, u32) {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let mut reader = hound::WavReader::open(&path).expect(&path);
+        let rate = reader.spec().sample_rate;
+        let samples = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        (samples, rate)
+    }
+
+    fn settings() -> DspSettings {
+        DspSettings::from_config(&MillowConfig::default())
+    }
+
+    /// `secs` aralığının RMS'i
+    fn rms(x: &[i16], rate: u32, secs: std::ops::Range<f32>) -> f64 {
+        let (a, b) = (
+            (secs.start * rate as f32) as usize,
+            (secs.end * rate as f32) as usize,
+        );
+        AudioEngine::rms(&x[a..b])
+    }
+
+    fn mean(x: &[i16]) -> f64 {
+        x.iter().map(|&s| s as f64).sum::<f64>() / x.len() as f64
+    }
+
+    /// Tek frekanstaki genlik (Goertzel)
+    fn tone(x: &[i16], rate: u32, hz: f32) -> f64 {
+        let w = 2.0 * std::f64::consts::PI * hz as f64 / rate as f64;
+        let (mut s1, mut s2) = (0.0f64, 0.0f64);
+        for &s in x {
+            let s0 = s as f64 + 2.0 * w.cos() * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        (s1 * s1 + s2 * s2 - 2.0 * w.cos() * s1 * s2).sqrt() * 2.0 / x.len() as f64
+    }
+
+    #[test]
+    fn disabled_chain_passes_through() {
+        let (x, rate) = fixture("noisy_speech.wav");
+        let off = DspSettings {
+            enabled: false,
+            ..settings()
+        };
+        assert_eq!(process(&x, rate, &off), x);
+    }
+
+    #[test]
+    fn keeps_length() {
+        let (x, rate) = fixture("noisy_speech.wav");
+        assert_eq!(process(&x, rate, &settings()).len(), x.len());
+        assert_eq!(process(&x[..100], rate, &settings()).len(), 100);
+    }
+
+    #[test]
+    fn removes_dc_and_hum() {
+        let (x, rate) = fixture("noisy_speech.wav");
+        let filters_only = DspSettings {
+            noise_suppression: 0.0,
+            agc: false,
+            ..settings()
+        };
+        let y = process(&x, rate, &filters_only);
+        // Girişte ~%5 tam ölçek DC var
+        assert!(mean(&x) > 1000.0);
+        // İlk çeyrek saniye filtrenin oturma süresi
+        let settled = &y[rate as usize / 4..];
+        assert!(mean(settled).abs() < 50.0, "DC kaldı: {}", mean(settled));
+        // 80 Hz 2. derece Butterworth, 50 Hz'i ~%36'ya indirir
+        let (hum_in, hum_out) = (tone(&x, rate, 50.0), tone(settled, rate, 50.0));
+        assert!(hum_out < hum_in * 0.5, "uğultu {} → {}", hum_in, hum_out);
+    }
+
+    #[test]
+    fn suppresses_noise_between_words() {
+        let (x, rate) = fixture("noisy_speech.wav");
+        let no_agc = DspSettings {
+            agc: false,
+            ..settings()
+        };
+        let y = process(&x, rate, &no_agc);
+        let snr = |v: &[i16]| rms(v, rate, 1.0..2.0) / rms(v, rate, 0.3..0.9);
+        // Gürültü en az yarıya insin, konuşma korunsun
+        assert!(
+            rms(&y, rate, 0.3..0.9) < rms(&x, rate, 0.3..0.9) * 0.5,
+            "gürültü {:.0} → {:.0}",
+            rms(&x, rate, 0.3..0.9),
+            rms(&y, rate, 0.3..0.9)
+        );
+        assert!(rms(&y, rate, 1.0..2.0) > rms(&x, rate, 1.0..2.0) * 0.5);
+        assert!(
+            snr(&y) > snr(&x) * 2.0,
+            "SNR {:.1} → {:.1}",
+            snr(&x),
+            snr(&y)
+        );
+    }
+
+    #[test]
+    fn agc_reaches_target_without_clipping() {
+        let (x, rate) = fixture("noisy_speech.wav");
+        let s = settings();
+        let y = process(&x, rate, &s);
+        let level = rms(&y, rate, 1.0..2.0) / 32768.0;
+        assert!(
+            (s.target_level as f64 * 0.5..s.target_level as f64 * 2.0).contains(&level),
+            "seviye {:.3}",
+            level
+        );
+        assert!(y.iter().all(|&v| v > i16::MIN && v < i16::MAX));
+    }
+
+    #[test]
+    fn whisper_preset_boosts_quiet_speech() {
+        let (x, rate) = fixture("whisper.wav");
+        let normal = process(&x, rate, &settings());
+        let whisper = process(
+            &x,
+            rate,
+            &DspSettings::from_config(&MillowConfig {
+                whisper_mode: true,
+                ..MillowConfig::default()
+            }),
+        );
+        let speech = |v: &[i16]| rms(v, rate, 1.0..2.0);
+        // Normal ayarda kazanç 8x ile sınırlı, fısıltı ön ayarı hedefe kadar yükseltir
+        assert!(
+            speech(&whisper) > speech(&normal) * 1.5,
+            "{:.0} / {:.0}",
+            speech(&normal),
+            speech(&whisper)
+        );
+        assert!(speech(&whisper) / 32768.0 > 0.1);
+        assert!(whisper.iter().all(|&v| v > i16::MIN && v < i16::MAX));
+    }
+
+    #[test]
+    fn fft_round_trip() {
+        let input: Vec<f32> = (0..256).map(|i| (i as f32 * 0.37).sin()).collect();
+        let (mut re, mut im) = (input.clone(), vec![0.0; 256]);
+        fft(&mut re, &mut im, false);
+        fft(&mut re, &mut im, true);
+        for (a, b) in input.iter().zip(&re) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// Millow — Transkript Dışa Aktarma
+// Zaman damgalı transkripti SRT, WebVTT, düz metin, Markdown veya JSON'a çevirir
+
+use crate::transcriber::Transcript;
+
+/// Dışa aktarma biçimi
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Text,
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    /// "srt", "vtt", "txt", "md" veya "json"
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "srt" => Ok(Self::Srt),
+            "vtt" | "webvtt" => Ok(Self::Vtt),
+            "txt" | "text" => Ok(Self::Text),
+            "md" | "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Bilinmeyen dışa aktarma biçimi: {}", other)),
+        }
+    }
+
+    /// Önerilen dosya uzantısı
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Transkripti istenen biçime çevir
+pub fn export(transcript: &Transcript, format: ExportFormat) -> Result<String, String> {
+    let out = match format {
+        ExportFormat::Srt => to_srt(transcript),
+        ExportFormat::Vtt => to_vtt(transcript),
+        ExportFormat::Text => format!("{}\n", transcript.text.trim()),
+        ExportFormat::Markdown => to_markdown(transcript),
+        ExportFormat::Json => serde_json::to_string_pretty(transcript)
+            .map_err(|e| format!("JSON oluşturulamadı: {}", e))?,
+    };
+    Ok(out)
+}
+
+fn to_srt(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for (i, seg) in transcript.segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            timestamp(seg.start, ','),
+            timestamp(seg.end, ','),
+            seg.text.trim()
+        ));
+    }
+    out
+}
+
+fn to_vtt(transcript: &Transcript) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for seg in &transcript.segments {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(seg.start, '.'),
+            timestamp(seg.end, '.'),
+            seg.text.trim()
+        ));
+    }
+    out
+}
+
+fn to_markdown(transcript: &Transcript) -> String {
+    let mut out = format!("# Transkript\n\n_Süre: {}_\n\n", clock(transcript.duration));
+    for seg in &transcript.segments {
+        out.push_str(&format!("**[{}]** {}\n\n", clock(seg.start), seg.text.trim()));
+    }
+    out
+}
+
+/// SRT/VTT zaman damgası: 00:01:02,345 (ayraç SRT için ',', VTT için '.')
+fn timestamp(secs: f64, separator: char) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+/// Okunabilir saat: 01:02 veya 1:01:02
+fn clock(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total / 60) % 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::TranscriptSegment;
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    fn sample() -> Transcript {
+        Transcript {
+            text: " Merhaba dünya. İkinci cümle. ".into(),
+            duration: 3725.5,
+            segments: vec![
+                segment(0.0, 1.5, " Merhaba dünya."),
+                segment(59.9995, 62.25, "İkinci cümle. "),
+                segment(3661.001, 3725.5, "Son"),
+            ],
+        }
+    }
+
+    #[test]
+    fn writes_srt() {
+        let srt = export(&sample(), ExportFormat::Srt).unwrap();
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,500\nMerhaba dünya.\n\n\
+             2\n00:01:00,000 --> 00:01:02,250\nİkinci cümle.\n\n\
+             3\n01:01:01,001 --> 01:02:05,500\nSon\n\n"
+        );
+    }
+
+    #[test]
+    fn writes_vtt() {
+        let vtt = export(&sample(), ExportFormat::Vtt).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nMerhaba dünya.\n\n"));
+        assert!(vtt.contains("\n00:01:00.000 --> 00:01:02.250\n"));
+        assert!(!vtt.contains(','));
+        assert!(!vtt.contains("\n2\n"));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(-1.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(59.9995, ','), "00:01:00,000");
+        assert_eq!(timestamp(59.9994, '.'), "00:00:59.999");
+        assert_eq!(timestamp(3599.9999, ','), "01:00:00,000");
+        assert_eq!(timestamp(36_000.0, '.'), "10:00:00.000");
+    }
+
+    #[test]
+    fn formats_clock() {
+        assert_eq!(clock(0.0), "00:00");
+        assert_eq!(clock(59.9), "00:59");
+        assert_eq!(clock(62.0), "01:02");
+        assert_eq!(clock(3600.0), "1:00:00");
+        assert_eq!(clock(3725.5), "1:02:05");
+        assert_eq!(clock(-5.0), "00:00");
+    }
+
+    #[test]
+    fn writes_text_markdown_and_json() {
+        let transcript = sample();
+        assert_eq!(
+            export(&transcript, ExportFormat::Text).unwrap(),
+            "Merhaba dünya. İkinci cümle.\n"
+        );
+
+        let md = export(&transcript, ExportFormat::Markdown).unwrap();
+        assert!(md.starts_with("# Transkript\n\n_Süre: 1:02:05_\n\n"));
+        assert!(md.contains("**[00:59]** İkinci cümle.\n\n"));
+        assert!(md.contains("**[1:01:01]** Son\n\n"));
+
+        let json = export(&transcript, ExportFormat::Json).unwrap();
+        let parsed: Transcript = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.text, transcript.text);
+        assert_eq!(parsed.duration, 3725.5);
+        assert_eq!(parsed.segments.len(), 3);
+        assert_eq!(parsed.segments[1].start, 59.9995);
+        assert_eq!(parsed.segments[2].text, "Son");
+    }
+
+    #[test]
+    fn empty_transcript() {
+        let empty = Transcript {
+            text: String::new(),
+            duration: 0.0,
+            segments: Vec::new(),
+        };
+        assert_eq!(export(&empty, ExportFormat::Srt).unwrap(), "");
+        assert_eq!(export(&empty, ExportFormat::Vtt).unwrap(), "WEBVTT\n\n");
+        assert_eq!(export(&empty, ExportFormat::Text).unwrap(), "\n");
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::parse(" SRT "), Ok(ExportFormat::Srt));
+        assert_eq!(ExportFormat::parse("webvtt"), Ok(ExportFormat::Vtt));
+        assert_eq!(ExportFormat::parse("text"), Ok(ExportFormat::Text));
+        assert_eq!(ExportFormat::parse("Markdown"), Ok(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::parse("json").unwrap().extension(), "json");
+        assert_eq!(ExportFormat::parse("md").unwrap().extension(), "md");
+        assert_eq!(
+            ExportFormat::parse("DOCX"),
+            Err("Bilinmeyen dışa aktarma biçimi: docx".into())
+        );
+    }
+}
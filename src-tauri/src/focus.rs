@@ -0,0 +1,301 @@
+// Millow — Odak Yönetimi
+// Kayıt başındaki ön plan uygulamasını PID / bundle ID / pencereyle yakalar, yazmadan önce geri getirir
+
+use std::time::{Duration, Instant};
+
+/// Hedef öne gelene kadar en fazla bu kadar beklenir
+const FOCUS_TIMEOUT: Duration = Duration::from_millis(1500);
+/// Ön plan kontrolleri arası bekleme
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Kayıt başladığında odakta olan uygulama ve pencere
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusTarget {
+    /// Görünen ad (macOS: localizedName, X11: pencere sınıfı)
+    pub name: String,
+    /// Süreç kimliği
+    pub pid: Option<i32>,
+    /// macOS bundle identifier ("com.apple.Notes")
+    pub bundle_id: Option<String>,
+    /// macOS: ön pencerenin başlığı, X11: pencere kimliği
+    pub window: Option<String>,
+}
+
+impl FocusTarget {
+    /// Aynı uygulama mı — PID, bundle ID (yeniden başlatılmış uygulama) veya ad eşleşmesi
+    pub fn same_app(&self, other: &FocusTarget) -> bool {
+        if self.pid.is_some() && self.pid == other.pid {
+            return true;
+        }
+        if let (Some(a), Some(b)) = (&self.bundle_id, &other.bundle_id) {
+            return a.eq_ignore_ascii_case(b);
+        }
+        self.name.eq_ignore_ascii_case(&other.name)
+    }
+
+    /// Uygulama başına kayıtlar için anahtar (bundle ID yoksa ad)
+    pub fn key(&self) -> String {
+        self.bundle_id
+            .as_deref()
+            .unwrap_or(&self.name)
+            .trim()
+            .to_lowercase()
+    }
+}
+
+/// Şu an ön planda olan uygulama ve penceresi
+pub fn frontmost() -> Option<FocusTarget> {
+    let mut target = platform::frontmost_app()?;
+    if target.window.is_none() {
+        target.window = platform::current_window(&target);
+    }
+    Some(target)
+}
+
+/// Hedef uygulama şu an ön planda mı — ön plan bilinemiyorsa false
+pub fn is_frontmost(target: &FocusTarget) -> bool {
+    platform::frontmost_app().is_some_and(|current| current.same_app(target))
+}
+
+/// Hedef uygulama ve (biliniyorsa) hedef pencere şu an odakta mı — aynı uygulamanın başka penceresi sayılmaz
+pub fn is_focused(target: &FocusTarget) -> bool {
+    is_frontmost(target)
+        && target
+            .window
+            .as_ref()
+            .is_none_or(|window| platform::current_window(target).as_ref() == Some(window))
+}
+
+/// Hedefi öne getir ve gerçekten öne gelene kadar bekle
+pub fn restore(target: &FocusTarget) -> Result<(), String> {
+    if is_focused(target) {
+        return Ok(());
+    }
+
+    println!(
+        "🔄 Focus veriliyor: {} (pid {:?}, {:?})",
+        target.name, target.pid, target.bundle_id
+    );
+    let started = Instant::now();
+    platform::activate(target)?;
+
+    while started.elapsed() < FOCUS_TIMEOUT {
+        if is_frontmost(target) {
+            platform::raise_window(target);
+            println!("✅ Focus alındı ({} ms)", started.elapsed().as_millis());
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    Err(format!("{} öne getirilemedi", target.name))
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::FocusTarget;
+    use cocoa::base::{id, nil, BOOL, NO};
+    use cocoa::foundation::{NSAutoreleasePool, NSString, NSUInteger};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CStr;
+    use std::process::Command;
+
+    /// NSApplicationActivateIgnoringOtherApps
+    const ACTIVATE_IGNORING_OTHER_APPS: NSUInteger = 1 << 1;
+
+    /// Ön plandaki uygulama (pencere başlığı olmadan — hızlı, yoklamada kullanılır)
+    pub fn frontmost_app() -> Option<FocusTarget> {
+        unsafe {
+            let pool = NSAutoreleasePool::new(nil);
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let app: id = msg_send![workspace, frontmostApplication];
+            let target = (app != nil).then(|| app_info(app));
+            pool.drain();
+            target
+        }
+    }
+
+    /// Sürecin ön penceresinin başlığı (Erişilebilirlik izni gerekir)
+    pub fn current_window(target: &FocusTarget) -> Option<String> {
+        let script = format!(
+            "tell application \"System Events\" to get name of front window of (first process whose unix id is {})",
+            target.pid?
+        );
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(&script)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!title.is_empty() && title != "missing value").then_some(title)
+    }
+
+    /// Önce PID, uygulama yeniden başlatıldıysa bundle ID, en son adla etkinleştir
+    pub fn activate(target: &FocusTarget) -> Result<(), String> {
+        let activated = unsafe {
+            let pool = NSAutoreleasePool::new(nil);
+            let mut app: id = nil;
+            if let Some(pid) = target.pid {
+                app = msg_send![class!(NSRunningApplication), runningApplicationWithProcessIdentifier: pid];
+            }
+            if app == nil {
+                if let Some(bundle_id) = &target.bundle_id {
+                    let value = NSString::alloc(nil).init_str(bundle_id);
+                    let value: id = msg_send![value, autorelease];
+                    let apps: id = msg_send![class!(NSRunningApplication), runningApplicationsWithBundleIdentifier: value];
+                    app = msg_send![apps, firstObject];
+                }
+            }
+            let ok = if app == nil {
+                false
+            } else {
+                let ok: BOOL = msg_send![app, activateWithOptions: ACTIVATE_IGNORING_OTHER_APPS];
+                ok != NO
+            };
+            pool.drain();
+            ok
+        };
+        if activated {
+            return Ok(());
+        }
+
+        // Süreç bulunamadı — eski yöntem: adla etkinleştir
+        let script = format!("tell application \"{}\" to activate", escape(&target.name));
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(&script)
+            .output()
+            .map_err(|e| format!("osascript çalıştırılamadı: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!("{} etkinleştirilemedi", target.name))
+        }
+    }
+
+    /// Kayıttaki pencere artık önde değilse öne getir (çok pencereli uygulamalar)
+    pub fn raise_window(target: &FocusTarget) {
+        let (Some(window), Some(pid)) = (&target.window, target.pid) else {
+            return;
+        };
+        let script = format!(
+            "tell application \"System Events\" to tell (first process whose unix id is {})\n\
+             if name of front window is not \"{}\" then perform action \"AXRaise\" of (first window whose name is \"{}\")\n\
+             end tell",
+            pid,
+            escape(window),
+            escape(window)
+        );
+        let _ = Command::new("osascript").arg("-e").arg(&script).output();
+    }
+
+    unsafe fn app_info(app: id) -> FocusTarget {
+        let pid: i32 = msg_send![app, processIdentifier];
+        let name: id = msg_send![app, localizedName];
+        let bundle_id: id = msg_send![app, bundleIdentifier];
+        FocusTarget {
+            name: ns_to_string(name).unwrap_or_default(),
+            pid: (pid > 0).then_some(pid),
+            bundle_id: ns_to_string(bundle_id),
+            window: None,
+        }
+    }
+
+    unsafe fn ns_to_string(value: id) -> Option<String> {
+        if value == nil {
+            return None;
+        }
+        let ptr = NSString::UTF8String(value);
+        if ptr.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+
+    fn escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::FocusTarget;
+    use crate::sink::{command_output, which};
+
+    /// X11'de odaktaki pencere — Wayland istemcilere bunu söylemez
+    pub fn frontmost_app() -> Option<FocusTarget> {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() || !which("xdotool") {
+            return None;
+        }
+        let window = command_output("xdotool", &["getactivewindow"])?
+            .trim()
+            .to_string();
+        let name = command_output("xdotool", &["getwindowclassname", &window])
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())?;
+        let pid = command_output("xdotool", &["getwindowpid", &window])
+            .and_then(|pid| pid.trim().parse().ok());
+        Some(FocusTarget {
+            name,
+            pid,
+            bundle_id: None,
+            window: Some(window),
+        })
+    }
+
+    /// Kayıttaki pencereyi, kapandıysa aynı sınıftan bir pencereyi etkinleştir
+    pub fn activate(target: &FocusTarget) -> Result<(), String> {
+        if !which("xdotool") {
+            return Err("xdotool bulunamadı".into());
+        }
+        if let Some(window) = &target.window {
+            if command_output("xdotool", &["windowactivate", window]).is_some() {
+                return Ok(());
+            }
+        }
+        command_output(
+            "xdotool",
+            &[
+                "search",
+                "--limit",
+                "1",
+                "--class",
+                &target.name,
+                "windowactivate",
+            ],
+        )
+        .map(|_| ())
+        .ok_or_else(|| format!("{} penceresi bulunamadı", target.name))
+    }
+
+    pub fn current_window(_target: &FocusTarget) -> Option<String> {
+        command_output("xdotool", &["getactivewindow"]).map(|window| window.trim().to_string())
+    }
+
+    /// windowactivate pencereyi zaten öne getirir
+    pub fn raise_window(_target: &FocusTarget) {}
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod platform {
+    use super::FocusTarget;
+
+    pub fn frontmost_app() -> Option<FocusTarget> {
+        None
+    }
+
+    pub fn current_window(_target: &FocusTarget) -> Option<String> {
+        None
+    }
+
+    pub fn activate(target: &FocusTarget) -> Result<(), String> {
+        Err(format!(
+            "{} öne getirilemiyor: platform desteklenmiyor",
+            target.name
+        ))
+    }
+
+    pub fn raise_window(_target: &FocusTarget) {}
+}
@@ -0,0 +1,265 @@
+// Millow — Ekleme Geçmişi
+// Pencere başına son yazılan metni hatırlar, "son dikteyi geri al" ifadelerini tanır
+
+use crate::focus::FocusTarget;
+use crate::typer::OutputMethod;
+use std::collections::HashMap;
+
+/// "Son dikteyi geri al" sesli komutları (TR/EN)
+const UNDO_PHRASES: &[&str] = &[
+    "son dikteyi geri al",
+    "dikteyi geri al",
+    "son yazdığını geri al",
+    "bunu sil",
+    "sil bunu",
+    "undo last dictation",
+    "undo dictation",
+    "undo that dictation",
+    "scratch that",
+    "delete that",
+];
+
+/// Uygulama başına hatırlanan en fazla karakter
+const LOG_LIMIT: usize = 4000;
+
+/// Millow'un odaktaki uygulamaya yazdığı bir metin
+#[derive(Debug, Clone)]
+pub struct Insertion {
+    /// Yazılan metin (satır sonu dahil)
+    pub text: String,
+    /// Yazıldığı uygulama (bilinmiyorsa None)
+    pub app: Option<FocusTarget>,
+    /// Yapıştırma mı, tuş vuruşu mu
+    pub method: OutputMethod,
+}
+
+impl Insertion {
+    pub fn new(text: &str, app: Option<FocusTarget>, method: OutputMethod) -> Self {
+        Self {
+            text: text.to_string(),
+            app,
+            method,
+        }
+    }
+
+    /// Silmek için gereken Backspace sayısı
+    pub fn char_count(&self) -> usize {
+        keystrokes(&self.text)
+    }
+}
+
+/// Uygulama başına yazılanların kaydı — imlecin son eklemenin sonunda olduğu varsayılır
+#[derive(Debug, Default)]
+pub struct InsertionLog {
+    last: Option<Insertion>,
+    per_app: HashMap<String, String>,
+    /// En son yazılan/düzeltilen uygulama
+    last_app: Option<String>,
+}
+
+impl InsertionLog {
+    /// Yeni eklemeyi kaydet
+    pub fn record(&mut self, insertion: Insertion) {
+        let key = app_key(insertion.app.as_ref());
+        self.last_app = Some(key.clone());
+        let buffer = self.per_app.entry(key).or_default();
+        buffer.push_str(&insertion.text.replace('\r', ""));
+        let excess = buffer.chars().count().saturating_sub(LOG_LIMIT);
+        if excess > 0 {
+            let cut = buffer.char_indices().nth(excess).map(|(i, _)| i).unwrap_or(0);
+            buffer.drain(..cut);
+        }
+        self.last = Some(insertion);
+    }
+
+    /// Son ekleme (geri alınmadıysa)
+    pub fn last(&self) -> Option<&Insertion> {
+        self.last.as_ref()
+    }
+
+    /// Son ekleme geri alındı — uygulamanın metninden de çıkar
+    pub fn forget_last(&mut self) {
+        let Some(insertion) = self.last.take() else {
+            return;
+        };
+        let key = app_key(insertion.app.as_ref());
+        if let Some(buffer) = self.per_app.get_mut(&key) {
+            let text = insertion.text.replace('\r', "");
+            match buffer.strip_suffix(text.as_str()) {
+                Some(rest) => *buffer = rest.to_string(),
+                None => buffer.clear(),
+            }
+        }
+    }
+
+    /// Uygulamaya yazılmış son metin
+    pub fn text_for(&self, app: Option<&FocusTarget>) -> &str {
+        self.per_app.get(&app_key(app)).map(|s| s.as_str()).unwrap_or("")
+    }
+
+    /// Son yazılan uygulama buysa ona yazılmış metin — imlecin hâlâ sonunda olduğu varsayılır
+    pub fn recent_text(&self, app: Option<&FocusTarget>) -> Option<&str> {
+        let key = app_key(app);
+        if self.last_app.as_ref() != Some(&key) {
+            return None;
+        }
+        self.per_app.get(&key).map(|s| s.as_str()).filter(|s| !s.is_empty())
+    }
+
+    /// Bir düzeltme sonrası uygulamanın metnini güncelle — son ekleme artık güvenilir değil
+    pub fn replace_text(&mut self, app: Option<&FocusTarget>, text: String) {
+        let key = app_key(app);
+        self.last_app = Some(key.clone());
+        self.per_app.insert(key, text);
+        self.last = None;
+    }
+}
+
+/// Uygulama ve pencere başına anahtar — aynı uygulamanın başka penceresine yazılanlar karışmasın
+fn app_key(app: Option<&FocusTarget>) -> String {
+    match app {
+        Some(app) => match &app.window {
+            Some(window) => format!("{}\n{}", app.key(), window),
+            None => app.key(),
+        },
+        None => String::new(),
+    }
+}
+
+/// Metni silmek/üzerinden geçmek için gereken tuş sayısı — '\r' yazılmaz,
+/// birleşik işaretler taban harfiyle tek karakter sayılır
+pub fn keystrokes(text: &str) -> usize {
+    text.chars()
+        .filter(|&c| c != '\r' && !is_combining_mark(c))
+        .count()
+}
+
+/// Metin tamamen bir geri alma komutu mu
+pub fn is_undo_phrase(text: &str) -> bool {
+    let normalized = normalize(text);
+    !normalized.is_empty() && UNDO_PHRASES.iter().any(|p| normalize(p) == normalized)
+}
+
+/// Karşılaştırma için sadeleştir: küçük harf, noktasız/noktalı i farkı yok sayılır
+/// (Whisper "İ"/"I" karışık döndürebilir), noktalama atılır, boşluklar teklenir
+pub fn normalize(text: &str) -> String {
+    let lowered: String = text
+        .chars()
+        .flat_map(|c| c.to_lowercase())
+        .filter(|&c| !is_combining_mark(c))
+        .map(|c| if c == 'ı' { 'i' } else { c })
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    lowered.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Önceki karaktere eklenen birleşik işaret mi (aksan, nokta vb.)
+pub fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str, window: Option<&str>) -> Option<FocusTarget> {
+        Some(FocusTarget {
+            name: name.into(),
+            pid: Some(42),
+            bundle_id: None,
+            window: window.map(str::to_string),
+        })
+    }
+
+    fn insert(log: &mut InsertionLog, text: &str, target: &Option<FocusTarget>) {
+        log.record(Insertion::new(text, target.clone(), OutputMethod::Paste));
+    }
+
+    #[test]
+    fn records_and_forgets_per_window() {
+        let notes = app("Notes", Some("0x1"));
+        let other = app("Notes", Some("0x2"));
+        let mut log = InsertionLog::default();
+        insert(&mut log, "Merhaba ", &notes);
+        insert(&mut log, "dünya.\r\n", &notes);
+        insert(&mut log, "Başka pencere", &other);
+
+        assert_eq!(log.text_for(notes.as_ref()), "Merhaba dünya.\n");
+        assert_eq!(log.text_for(other.as_ref()), "Başka pencere");
+        assert_eq!(log.last().unwrap().text, "Başka pencere");
+
+        log.forget_last();
+        assert!(log.last().is_none());
+        assert_eq!(log.text_for(other.as_ref()), "");
+        // İkinci kez unutmak hiçbir şeyi değiştirmez
+        log.forget_last();
+        assert_eq!(log.text_for(notes.as_ref()), "Merhaba dünya.\n");
+    }
+
+    #[test]
+    fn forgetting_unknown_suffix_clears_buffer() {
+        let notes = app("Notes", None);
+        let mut log = InsertionLog::default();
+        insert(&mut log, "bir iki", &notes);
+        log.replace_text(notes.as_ref(), "bir".into());
+        assert!(log.last().is_none());
+        insert(&mut log, " üç", &notes);
+        log.per_app.insert(app_key(notes.as_ref()), "değişti".into());
+        log.forget_last();
+        assert_eq!(log.text_for(notes.as_ref()), "");
+    }
+
+    #[test]
+    fn trims_to_log_limit() {
+        let notes = app("Notes", None);
+        let mut log = InsertionLog::default();
+        insert(&mut log, &"ğ".repeat(LOG_LIMIT - 2), &notes);
+        insert(&mut log, "abcde", &notes);
+        let text = log.text_for(notes.as_ref());
+        assert_eq!(text.chars().count(), LOG_LIMIT);
+        assert!(text.ends_with("ğğabcde"));
+        assert!(text.starts_with('ğ'));
+    }
+
+    #[test]
+    fn recent_text_only_for_last_window() {
+        let notes = app("Notes", Some("0x1"));
+        let mail = app("Mail", None);
+        let mut log = InsertionLog::default();
+        assert_eq!(log.recent_text(notes.as_ref()), None);
+        insert(&mut log, "not", &notes);
+        assert_eq!(log.recent_text(notes.as_ref()), Some("not"));
+        insert(&mut log, "posta", &mail);
+        assert_eq!(log.recent_text(notes.as_ref()), None);
+        assert_eq!(log.recent_text(mail.as_ref()), Some("posta"));
+        assert_eq!(log.recent_text(app("Notes", Some("0x9")).as_ref()), None);
+        log.forget_last();
+        assert_eq!(log.recent_text(mail.as_ref()), None);
+    }
+
+    #[test]
+    fn counts_keystrokes() {
+        assert_eq!(keystrokes("abc"), 3);
+        assert_eq!(keystrokes("satır\r\n"), 6);
+        // "İ" ayrışık yazımı: I + U+0307 tek tuş
+        assert_eq!(keystrokes("I\u{0307}stanbul"), 8);
+        assert_eq!(keystrokes("e\u{0301}\u{0323}"), 1);
+        assert_eq!(
+            Insertion::new("cafe\u{0301} ", None, OutputMethod::Type).char_count(),
+            5
+        );
+    }
+
+    #[test]
+    fn recognises_undo_phrases() {
+        assert!(is_undo_phrase("Son dikteyi geri al."));
+        assert!(is_undo_phrase("SİL BUNU!"));
+        assert!(is_undo_phrase("scratch that"));
+        assert!(!is_undo_phrase("bunu sil ve yeniden yaz"));
+        assert!(!is_undo_phrase(""));
+        assert_eq!(normalize("  İyi,  akşamlar! "), "iyi akşamlar");
+    }
+}
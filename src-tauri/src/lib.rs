@@ -1,9 +1,30 @@
 // Millow — Ana Uygulama Modülü
 // Tüm modülleri birleştirir, tray menü ve global kısayolu Rust tarafında yönetir
 
+mod assistant;
 mod audio;
+mod builtins;
+#[cfg(target_os = "macos")]
+mod clipboard;
 mod commander;
 mod config;
+mod correction;
+mod decoder;
+mod desktop;
+mod dsp;
+mod export;
+mod focus;
+mod history;
+mod media;
+mod output;
+mod policy;
+mod preview;
+mod queue;
+mod sink;
+mod spacing;
+#[cfg(test)]
+mod stub;
+mod timers;
 mod transcriber;
 mod typer;
 
@@ -17,7 +38,7 @@ use std::sync::Arc;
 use tauri::{
     menu::{MenuBuilder, MenuEvent, MenuItemBuilder},
     tray::TrayIconBuilder,
-    AppHandle, Manager, WebviewWindow,
+    AppHandle, Emitter, Manager, WebviewWindow,
 };
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
@@ -43,7 +64,7 @@ fn hide_dock() {
     }
 }
 
-use transcriber::{GeminiTranscriber, TranscribeContext, TranscribeMode};
+use transcriber::{GeminiTranscriber, TranscribeContext, TranscribeMode, Transcript};
 
 /// Uygulama durumu
 pub struct AppState {
@@ -51,38 +72,26 @@ pub struct AppState {
     config: Mutex<MillowConfig>,
     /// Uygulama aktif mi (uyandırma kelimesiyle kontrol)
     is_active: Mutex<bool>,
-    /// Mevcut mod: "dictation", "translate", "command"
+    /// Mevcut mod: "dictation", "translate", "command", "edit"
     current_mode: Mutex<String>,
-    /// Kayıt başladığında aktif olan uygulama
-    source_app: Mutex<Option<String>>,
+    /// Kayıt başladığında aktif olan uygulama (PID, bundle ID, pencere)
+    source_app: Mutex<Option<focus::FocusTarget>>,
+    /// Uygulama başına yazılanlar (geri alma ve sesli düzeltme için)
+    insertions: Mutex<history::InsertionLog>,
+    /// Önizleme penceresinde onay bekleyen metin
+    preview: Mutex<Option<preview::PendingPreview>>,
+    /// Sesli onay bekleyen komut
+    pending_command: Mutex<Option<policy::PendingCommand>>,
     /// Kayıt durumu
     is_recording: Mutex<bool>,
-    is_processing: std::sync::atomic::AtomicBool,
+    /// Segmentleri yakalanma sırasıyla teslim eden kuyruk
+    segments: Arc<queue::DeliveryQueue>,
     /// Pencere görünür mü (rdev crash fix)
     window_visible: std::sync::atomic::AtomicBool,
     /// Debounce: son kayıt başlama zamanı
     last_record_start: Mutex<std::time::Instant>,
 }
 
-/// P6: macOS'ta aktif uygulamanın adını al
-fn get_active_app() -> Option<String> {
-    let output = std::process::Command::new("osascript")
-        .args([
-            "-e",
-            r#"tell application "System Events" to get name of first application process whose frontmost is true"#,
-        ])
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !name.is_empty() {
-            return Some(name);
-        }
-    }
-    None
-}
-
 /// Config'den TranscribeContext oluştur
 fn build_context(config: &MillowConfig) -> TranscribeContext {
     TranscribeContext {
@@ -90,19 +99,264 @@ fn build_context(config: &MillowConfig) -> TranscribeContext {
         format_commands: config.format_commands,
         dictionary: config.custom_dictionary.clone(),
         writing_style: config.writing_style.clone(),
-        active_app: get_active_app(),
+        active_app: focus::frontmost().map(|app| app.name),
         whisper_mode: config.whisper_mode,
+        dsp: dsp::DspSettings::from_config(config),
+        commands: commander::CommandRegistry::from_config(config).describe(),
+    }
+}
+
+/// Metni hedef uygulamaya yaz ve geri alma için hatırla
+fn insert_text(state: &Arc<AppState>, text: &str, src_app: Option<focus::FocusTarget>) -> Result<(), String> {
+    let typer = typer::AutoTyper::new()?;
+    let previous = state
+        .insertions
+        .lock()
+        .recent_text(src_app.as_ref())
+        .map(str::to_string);
+    let written = typer.type_text_to_app(text, src_app.as_ref(), previous.as_deref())?;
+    let method = typer.method_for(src_app.as_ref());
+    state.insertions.lock().record(history::Insertion::new(&written, src_app, method));
+    Ok(())
+}
+
+/// Sonucu modun çıktı hedeflerine gönder — bir hedef başarısız olsa da diğerleri denenir.
+/// `src_app`: segmentin kaydedildiği sırada odakta olan uygulama
+fn deliver(
+    state: &Arc<AppState>,
+    text: &str,
+    mode: &str,
+    src_app: Option<&focus::FocusTarget>,
+) -> Result<(), String> {
+    let config = state.config.lock().clone();
+    let src_app = src_app.cloned();
+    let app_name = src_app.as_ref().map(|app| app.name.clone());
+    let event = output::OutputEvent::new(text, mode, app_name);
+
+    let mut errors = Vec::new();
+    for target in output::routes_for(&config, mode) {
+        let target = match target {
+            output::OutputTarget::App if output::wants_preview(&config, src_app.as_ref()) => {
+                output::OutputTarget::Preview
+            }
+            other => other,
+        };
+        let sent = match &target {
+            output::OutputTarget::App => insert_text(state, text, src_app.clone()),
+            output::OutputTarget::Preview => show_preview(state, text, src_app.clone()),
+            other => output::send(other, &event, &config),
+        };
+        if let Err(e) = sent {
+            println!("❌ Çıktı hatası ({:?}): {}", target, e);
+            errors.push(e);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Metni önizleme penceresinde göster — aynı uygulama için bekleyen metin varsa sonuna ekle
+fn show_preview(state: &Arc<AppState>, text: &str, src_app: Option<focus::FocusTarget>) -> Result<(), String> {
+    let handle = APP_HANDLE.get().ok_or("Uygulama henüz hazır değil")?;
+    let text = {
+        let mut pending = state.preview.lock();
+        match pending.as_mut() {
+            Some(p) if p.app == src_app => p.append(text),
+            Some(_) => {
+                println!("⚠️ Önceki önizleme başka uygulamaya aitti, atılıyor");
+                *pending = Some(preview::PendingPreview::new(text, src_app));
+            }
+            None => *pending = Some(preview::PendingPreview::new(text, src_app)),
+        }
+        pending.as_ref().map(|p| p.text.clone()).unwrap_or_default()
+    };
+    println!("👁️ Önizleme gösteriliyor ({} karakter)", text.chars().count());
+    preview::show(handle, &text)
+}
+
+/// Dikte sonucu bir düzeltme komutuysa çözümle (ayar kapalıysa yalnızca geri alma)
+fn parse_correction(
+    state: &Arc<AppState>,
+    text: &str,
+    src_app: Option<&focus::FocusTarget>,
+) -> Option<correction::Correction> {
+    if !state.config.lock().voice_corrections {
+        return history::is_undo_phrase(text).then_some(correction::Correction::ScratchThat);
+    }
+    let correction = correction::parse(text)?;
+    // Değiştirilecek metin son yazılanlarda yoksa bu bir komut değil, dikte edilen cümledir
+    if let correction::Correction::Replace { ref from, .. } = correction {
+        let log = state.insertions.lock();
+        if correction::plan(log.text_for(src_app), &correction).is_err() {
+            println!("📝 \"{}\" son yazılanlarda yok — düzeltme değil, dikte olarak yazılıyor", from);
+            return None;
+        }
+    }
+    Some(correction)
+}
+
+/// Sesli düzeltmeyi odaktaki uygulamaya yazılmış son metne uygula
+fn apply_correction(
+    state: &Arc<AppState>,
+    correction: correction::Correction,
+    src_app: Option<&focus::FocusTarget>,
+) {
+    if correction == correction::Correction::ScratchThat {
+        undo_last_dictation(state);
+        return;
+    }
+
+    if let Err(e) = check_focus(src_app) {
+        println!("⚠️ {} ({:?}), düzeltme atlandı", e, src_app);
+        notify("Düzeltilemedi", &e);
+        return;
+    }
+
+    let planned = {
+        let log = state.insertions.lock();
+        correction::plan(log.text_for(src_app), &correction)
+    };
+    let result = planned.and_then(|(edit, updated)| {
+        typer::AutoTyper::new()?.apply_edit(&edit)?;
+        state.insertions.lock().replace_text(src_app, updated);
+        Ok(())
+    });
+    match result {
+        Ok(()) => {
+            println!("✏️ Düzeltme uygulandı: {:?}", correction);
+            notify("✏️ Düzeltildi", &correction.describe());
+        }
+        Err(e) => {
+            println!("❌ Düzeltme hatası: {}", e);
+            notify("Düzeltilemedi", &e);
+        }
+    }
+}
+
+/// Seçili metni sesli talimata göre LLM ile düzenle ve seçimin yerine yaz
+fn edit_selection(state: &Arc<AppState>, instruction: &str, src_app: Option<&focus::FocusTarget>) {
+    let settings = assistant::AiSettings::from_config(&state.config.lock());
+    let result = typer::AutoTyper::new().and_then(|typer| {
+        let selection = typer.selected_text(src_app)?;
+        if selection.trim().is_empty() {
+            return Err("Önce düzenlenecek metni seçin".to_string());
+        }
+        println!("✏️ Seçim düzenleniyor ({} karakter): {}", selection.chars().count(), instruction);
+        notify("✏️ Düzenleniyor…", instruction);
+        let edited = assistant::edit_text(&settings, &commander::SystemExecutor, instruction, &selection)?;
+        typer.replace_selection(&edited, src_app)?;
+        Ok(edited)
+    });
+    match result {
+        Ok(edited) => {
+            println!("✅ Seçim düzenlendi: {}", edited);
+            notify("✅ Düzenlendi", &edited);
+        }
+        Err(e) => {
+            println!("❌ Düzenleme hatası: {}", e);
+            notify("Düzenlenemedi", &e);
+        }
     }
 }
 
-/// Segment flush: mevcut buffer'ı transkript edip yapıştır, kayda devam et
+/// Komut sonucunu günlüğe yaz ve bildir — onay gerekiyorsa komutu beklet
+fn report_command(state: &Arc<AppState>, action: &str, outcome: Result<commander::Outcome, String>) {
+    match outcome {
+        Ok(commander::Outcome::Done(msg)) => {
+            println!("✅ Komut: {} → {}", action, msg);
+            notify("Komut çalıştırıldı", &msg);
+        }
+        Ok(commander::Outcome::Confirm(pending)) => {
+            println!("⚠️ Onay bekleniyor: {}", action);
+            notify(
+                "⚠️ Onay gerekiyor",
+                &format!("'{}' çalıştırılsın mı? Onaylamak için \"evet\" deyin", action),
+            );
+            *state.pending_command.lock() = Some(pending);
+        }
+        Err(e) => {
+            println!("❌ Komut hatası: {}", e);
+            notify("Komut hatası", &e);
+        }
+    }
+}
+
+/// Bekleyen komutu onayla ya da iptal et
+fn resolve_pending_command(state: &Arc<AppState>, accept: bool) -> Result<String, String> {
+    let pending = state.pending_command.lock().take().ok_or("Onay bekleyen komut yok")?;
+    if !accept {
+        policy::audit(&pending.name, &pending.params, "cancelled", "");
+        return Ok(format!("'{}' iptal edildi", pending.name));
+    }
+    let config = state.config.lock().clone();
+    commander::CommandRegistry::from_config(&config).execute_confirmed(pending)
+}
+
+/// Komut onay beklerken gelen söz: "evet" çalıştırır, diğer her şey iptal eder.
+/// Söz onay ya da ret cevabıysa true — başka bir şeyse normal işlenmeye devam eder
+fn answer_pending_command(state: &Arc<AppState>, text: &str) -> bool {
+    let Some(name) = state.pending_command.lock().as_ref().map(|p| p.name.clone()) else {
+        return false;
+    };
+    let answer = policy::is_confirmation(text);
+    if answer == Some(true) {
+        let outcome = resolve_pending_command(state, true).map(commander::Outcome::Done);
+        report_command(state, &name, outcome);
+    } else if let Ok(msg) = resolve_pending_command(state, false) {
+        println!("🚫 {}", msg);
+        notify("Komut iptal edildi", &msg);
+    }
+    answer.is_some()
+}
+
+/// Metnin yazıldığı uygulama ve pencere hâlâ odakta mı — hedef bilinmiyorsa (Wayland) silmek güvenli değil
+fn check_focus(target: Option<&focus::FocusTarget>) -> Result<(), String> {
+    match target {
+        None => Err("Odaktaki pencere bilinmiyor".into()),
+        Some(app) if !focus::is_focused(app) => {
+            Err(format!("Odak değişti — {} penceresine dönün", app.name))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Son dikteyi geri al — odak o zamandan beri değiştiyse hiçbir şey yapma
+fn undo_last_dictation(state: &Arc<AppState>) {
+    let Some(insertion) = state.insertions.lock().last().cloned() else {
+        notify("Geri alınamadı", "Geri alınacak dikte yok");
+        return;
+    };
+
+    if let Err(e) = check_focus(insertion.app.as_ref()) {
+        println!("⚠️ {} ({:?}), geri alma atlandı", e, insertion.app);
+        notify("Geri alınamadı", &e);
+        return;
+    }
+
+    let result = typer::AutoTyper::new().and_then(|t| t.remove(&insertion));
+    match result {
+        Ok(()) => {
+            state.insertions.lock().forget_last();
+            println!("↩️ Son dikte geri alındı ({} karakter)", insertion.char_count());
+            notify("↩️ Geri alındı", &insertion.text);
+        }
+        Err(e) => {
+            println!("❌ Geri alma hatası: {}", e);
+            notify("Geri alma hatası", &e);
+        }
+    }
+}
+
+/// Segment flush: mevcut buffer'ı transkript et, sırası gelince yapıştır, kayda devam et
 pub fn flush_segment(state: Arc<AppState>) {
-    use std::sync::atomic::Ordering;
-    if state.is_processing.load(Ordering::SeqCst) {
-        println!("⚠️  Zaten işleniyor, segment flush atlanıyor");
+    // Düzenleme modunda talimat kayıt bitince tek parça işlenir
+    if state.current_mode.lock().as_str() == "edit" {
         return;
     }
-    
     let samples = state.audio_engine.lock().drain_samples();
     if samples.is_empty() {
         println!("⏭️  Segment boş, atlanıyor");
@@ -110,28 +364,28 @@ pub fn flush_segment(state: Arc<AppState>) {
     }
     
     // Ses seviyesi kontrolü — sessiz segmentleri atla (API hallucination önleme)
-    let rms: f64 = (samples.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>() / samples.len() as f64).sqrt();
-    let peak = samples.iter().map(|s| s.abs() as u16).max().unwrap_or(0);
-    if rms < 200.0 && peak < 400 {
+    // Fısıltı modunda eşikler düşük tutulur
+    let vad_scale = state.config.lock().vad_scale() as f64;
+    let rms = AudioEngine::rms(&samples);
+    let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    if rms < 200.0 * vad_scale && (peak as f64) < 400.0 * vad_scale {
         println!("⏭️  Segment çok sessiz (rms={:.0}, peak={}), atlanıyor", rms, peak);
         return;
     }
     
-    state.is_processing.store(true, Ordering::SeqCst);
+    // Sırayı yakalama anında al — transkripsiyon daha geç bitse de yazma sırası korunur
+    let ticket = state.segments.ticket();
+    let src_app = state.source_app.lock().clone();
     
     let config = state.config.lock().clone();
     let actual_rate = state.audio_engine.lock().get_actual_sample_rate();
-    let wav_bytes = match AudioEngine::samples_to_wav(&samples, actual_rate) {
-        Ok(b) => b,
-        Err(e) => {
-            println!("❌ Segment WAV hatası: {}", e);
-            state.is_processing.store(false, Ordering::SeqCst);
-            return;
-        }
-    };
     
     let duration = samples.len() as f32 / config.sample_rate as f32;
-    println!("📝 Segment flush: {:.1}s ses transkript ediliyor…", duration);
+    println!(
+        "📝 Segment flush: {:.1}s ses transkript ediliyor… (kuyrukta {})",
+        duration,
+        state.segments.pending()
+    );
     
     let mode = {
         let current = state.current_mode.lock().clone();
@@ -154,79 +408,68 @@ pub fn flush_segment(state: Arc<AppState>) {
     let state_proc = Arc::clone(&state);
     std::thread::spawn(move || {
         let t_start = std::time::Instant::now();
-        match transcriber.transcribe(&wav_bytes, &mode, &ctx) {
+        match transcriber.transcribe_samples(&samples, actual_rate, config.chunk_duration, &mode, &ctx) {
             Ok(result) => {
                 println!("📝 Segment sonuç ({:.1}s): {:?}", t_start.elapsed().as_secs_f64(), result);
-                if !result.text.is_empty() {
-                    let cfg = state_proc.config.lock().clone();
-                    let final_text = if cfg.newline_after_segment {
-                        format!("{}
-", result.text)
-                    } else {
-                        result.text.clone()
-                    };
-                    match typer::AutoTyper::new() {
-                        Ok(t) => {
-                            let src_app = state_proc.source_app.lock().clone();
-                            if let Err(e) = t.type_text_to_app(&final_text, src_app.as_deref()) {
-                                println!("❌ Segment yazma hatası: {}", e);
-                            } else {
-                                println!("✅ Segment yazıldı: {}", result.text);
-                            }
+                ticket.deliver(move || {
+                    if answer_pending_command(&state_proc, &result.text) {
+                        return;
+                    }
+                    if let Some(correction) = parse_correction(&state_proc, &result.text, src_app.as_ref()) {
+                        apply_correction(&state_proc, correction, src_app.as_ref());
+                    } else if !result.text.is_empty() {
+                        let cfg = state_proc.config.lock().clone();
+                        let final_text = if cfg.newline_after_segment {
+                            format!("{}\n", result.text)
+                        } else {
+                            result.text.clone()
+                        };
+                        if let Err(e) = deliver(&state_proc, &final_text, mode.name(), src_app.as_ref()) {
+                            println!("❌ Segment yazma hatası: {}", e);
+                        } else {
+                            println!("✅ Segment yazıldı: {}", result.text);
                         }
-                        Err(e) => println!("❌ AutoTyper hatası: {}", e),
                     }
-                }
+                });
             }
             Err(e) => {
+                // Bilet düşer, sıradaki segmentler beklemeden teslim edilir
                 println!("❌ Segment transkript hatası: {}", e);
             }
         }
-        state_proc.is_processing.store(false, Ordering::SeqCst);
     });
 }
 
 /// Kaydı başlat/durdur ve transkript et (Rust tarafında tam döngü)
 pub fn toggle_recording(state: Arc<AppState>) {
-    use std::sync::atomic::Ordering;
-    if state.is_processing.load(Ordering::SeqCst) {
-        println!("⚠️  Zaten isleniyor, atlaniyor");
-        return;
-    }
     let is_rec = *state.is_recording.lock();
     println!("⏺️  toggle_recording çağrıldı (is_recording: {})", is_rec);
 
     if is_rec {
         // ── Kaydı durdur & transkript et ──
         println!("⏹️  Kayıt durduruluyor…");
-        state.is_processing.store(true, Ordering::SeqCst);
         *state.is_recording.lock() = false;
 
         let samples = state.audio_engine.lock().stop_recording();
         if samples.is_empty() {
             println!("❌ Ses kaydı boş");
             notify("Ses kaydı boş", "Mikrofona konuştuğunuzdan emin olun");
-            state.is_processing.store(false, Ordering::SeqCst);
             return;
         }
 
+        // Son parça, önceki segmentler yazıldıktan sonra yazılır
+        let ticket = state.segments.ticket();
+        let src_app = state.source_app.lock().clone();
+
         let config = state.config.lock().clone();
         let actual_rate = state.audio_engine.lock().get_actual_sample_rate();
-        let wav_bytes = match AudioEngine::samples_to_wav(&samples, actual_rate) {
-            Ok(b) => b,
-            Err(e) => {
-                println!("❌ WAV dönüşüm hatası: {}", e);
-                state.is_processing.store(false, Ordering::SeqCst);
-                notify("Hata", &e);
-                return;
-            }
-        };
 
         let duration = samples.len() as f32 / config.sample_rate as f32;
         println!("✅ {} saniye ses kaydedildi, transkript ediliyor…", duration);
         notify("İşleniyor…", &format!("{:.1}s ses transkript ediliyor", duration));
 
-        // Mod belirle
+        // Mod belirle — düzenleme modunda konuşma, seçili metin için talimattır
+        let editing = state.current_mode.lock().as_str() == "edit";
         let mode = {
             let current = state.current_mode.lock().clone();
             match current.as_str() {
@@ -246,6 +489,7 @@ pub fn toggle_recording(state: Arc<AppState>) {
 
         // P1-P7: Bağlam oluştur
         let ctx = build_context(&config);
+        let commands = commander::CommandRegistry::from_config(&config);
 
         let transcriber = Arc::new(GeminiTranscriber::new(
             &config.api_key,
@@ -254,75 +498,76 @@ pub fn toggle_recording(state: Arc<AppState>) {
         ));
 
         let state_internal = Arc::clone(&state);
-        let state_proc = Arc::clone(&state);
         std::thread::spawn(move || {
             let t_start = std::time::Instant::now();
-            match transcriber.transcribe(&wav_bytes, &mode, &ctx) {
+            match transcriber.transcribe_samples(&samples, actual_rate, config.chunk_duration, &mode, &ctx) {
                 Ok(result) => {
                     println!("📝 Sonuç ({:.1}s): {:?}", t_start.elapsed().as_secs_f64(), result);
-                    match result.result_type.as_str() {
-                        "dictation" => {
-                            if !result.text.is_empty() {
-                                match typer::AutoTyper::new() {
-                                    Ok(t) => {
-                                        let src_app = state_internal.source_app.lock().clone();
-                                        if let Err(e) =
-                                            t.type_text_to_app(&result.text, src_app.as_deref())
-                                        {
-                                            println!("❌ Yazma hatası: {}", e);
-                                            notify("Yazma hatası", &e);
-                                        } else {
-                                            println!("✅ Yazıldı: {}", result.text);
-                                            notify("✅ Yazıldı", &result.text);
-                                        }
-                                    }
-                                    Err(e) => {
-                                        println!("❌ Typer hatası: {}", e);
-                                        notify("Typer hatası", &e);
+                    ticket.deliver(move || {
+                        if answer_pending_command(&state_internal, &result.text) {
+                            return;
+                        }
+                        match result.result_type.as_str() {
+                            "dictation" if editing => {
+                                edit_selection(&state_internal, &result.text, src_app.as_ref());
+                            }
+                            "dictation" => {
+                                // Groq yalnızca metin döndürür — komut modunda tetikleyici ifadeleri yerelde eşle
+                                let matched = match mode {
+                                    TranscribeMode::Command => commands.match_phrase(&result.text),
+                                    _ => None,
+                                };
+                                if let Some(found) = matched {
+                                    let action = found.handler.info().name.clone();
+                                    report_command(&state_internal, &action, commands.execute_match(found));
+                                } else if let Some(correction) = parse_correction(&state_internal, &result.text, src_app.as_ref()) {
+                                    apply_correction(&state_internal, correction, src_app.as_ref());
+                                } else if !result.text.is_empty() {
+                                    if let Err(e) = deliver(&state_internal, &result.text, mode.name(), src_app.as_ref()) {
+                                        println!("❌ Yazma hatası: {}", e);
+                                        notify("Yazma hatası", &e);
+                                    } else {
+                                        println!("✅ Yazıldı: {}", result.text);
+                                        notify("✅ Yazıldı", &result.text);
                                     }
                                 }
                             }
-                        }
-                        "command" => {
-                            if let Some(ref action) = result.action {
-                                match commander::execute_command(action, result.params.as_deref()) {
-                                    Ok(msg) => {
-                                        println!("✅ Komut: {} → {}", action, msg);
-                                        notify("Komut çalıştırıldı", &msg);
-                                    }
-                                    Err(e) => {
-                                        println!("❌ Komut hatası: {}", e);
-                                        notify("Komut hatası", &e);
-                                    }
+                            "command" => {
+                                if result.action.as_deref() == Some("undo_dictation") {
+                                    undo_last_dictation(&state_internal);
+                                } else if result.action.as_deref() == Some("edit_selection") {
+                                    let instruction = result.params.as_deref().unwrap_or_default();
+                                    edit_selection(&state_internal, instruction, src_app.as_ref());
+                                } else if let Some(ref action) = result.action {
+                                    report_command(&state_internal, action, commands.execute(action, result.params.as_deref()));
                                 }
                             }
+                            "wakeword" => {
+                                *state_internal.is_active.lock() = true;
+                                println!("🌿 Millow aktif!");
+                                notify("🌿 Millow", "Aktif — dinliyorum!");
+                            }
+                            "sleep" => {
+                                *state_internal.is_active.lock() = false;
+                                println!("😴 Millow uyuyor");
+                                notify("😴 Millow", "Uyku moduna geçildi");
+                            }
+                            _ => {}
                         }
-                        "wakeword" => {
-                            *state_internal.is_active.lock() = true;
-                            println!("🌿 Millow aktif!");
-                            notify("🌿 Millow", "Aktif — dinliyorum!");
-                        }
-                        "sleep" => {
-                            *state_internal.is_active.lock() = false;
-                            println!("😴 Millow uyuyor");
-                            notify("😴 Millow", "Uyku moduna geçildi");
-                        }
-                        _ => {}
-                    }
+                    });
                 }
                 Err(e) => {
                     println!("❌ Transkripsiyon hatası: {}", e);
                     notify("Transkripsiyon hatası", &e);
                 }
             }
-            state_proc.is_processing.store(false, std::sync::atomic::Ordering::SeqCst);
         });
     } else {
         // ── Kaydı başlat ──
         match state.audio_engine.lock().start_recording() {
             Ok(_) => {
                 // Kayıt başlamadan önceki aktif uygulamayı kaydet
-                *state.source_app.lock() = get_active_app();
+                *state.source_app.lock() = focus::frontmost();
                 *state.is_recording.lock() = true;
                 println!("🎙️  Kayıt başladı!");
                 std::thread::spawn(|| { notify("🎙️ Kayıt", "3s susunca yazar, 30s susunca kapanır"); });
@@ -340,6 +585,7 @@ pub fn toggle_recording(state: Arc<AppState>) {
                         std::thread::sleep(std::time::Duration::from_millis(500));
                         let is_rec = *state_wd.is_recording.lock();
                         if !is_rec { break; }
+                        if stop_if_limit_reached(&state_wd) { break; }
                         
                         let silence_secs = state_wd.audio_engine.lock().seconds_since_voice();
                         
@@ -376,6 +622,95 @@ pub fn toggle_recording(state: Arc<AppState>) {
     }
 }
 
+/// Tray ikonunun kimliği
+const TRAY_ID: &str = "main";
+
+/// Giriş seviyesine göre tray durumu
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TrayLevelState {
+    Idle,
+    Hearing,
+    Silent,
+    Clipping,
+}
+
+/// Tray başlığını ve ipucunu seviye durumuna göre güncelle
+fn set_tray_level_state(app: &AppHandle, tray_state: TrayLevelState) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let (title, tooltip): (Option<&str>, &str) = match tray_state {
+        TrayLevelState::Idle => (None, "Millow"),
+        TrayLevelState::Hearing => (Some("●"), "Millow — ses alınıyor"),
+        TrayLevelState::Silent => (Some("○"), "Millow — mikrofondan ses gelmiyor"),
+        TrayLevelState::Clipping => (Some("◉"), "Millow — ses çok yüksek (kırpılma)"),
+    };
+    let _ = tray.set_title(title);
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+/// Ses motorunun seviye ölçümlerini UI'a (input-level olayı) ve tray'e bağla
+fn attach_level_meter(app: &AppHandle, state: &Arc<AppState>) {
+    let handle = app.clone();
+    let last_emit = Mutex::new(std::time::Instant::now());
+    let last_signal = Mutex::new(std::time::Instant::now());
+    let tray_state = Mutex::new(TrayLevelState::Idle);
+
+    state.audio_engine.lock().set_level_listener(move |level| {
+        let mut current = tray_state.lock();
+        if *current == TrayLevelState::Idle || level.rms > 0.005 {
+            *last_signal.lock() = std::time::Instant::now();
+        }
+        let next = if !level.recording {
+            TrayLevelState::Idle
+        } else if level.clipping {
+            TrayLevelState::Clipping
+        } else if last_signal.lock().elapsed().as_secs_f32() > 2.0 {
+            TrayLevelState::Silent
+        } else {
+            TrayLevelState::Hearing
+        };
+        if next != *current {
+            *current = next;
+            set_tray_level_state(&handle, next);
+        }
+        drop(current);
+
+        // UI olaylarını saniyede ~10 ile sınırla; kayıt bitişi ve kırpılma her zaman gider
+        let mut last = last_emit.lock();
+        if level.recording && !level.clipping && last.elapsed().as_millis() < 100 {
+            return;
+        }
+        *last = std::time::Instant::now();
+        let _ = handle.emit("input-level", level);
+    });
+}
+
+/// Kayıt limiti dolduysa kaydı durdur ve transkript et
+fn stop_if_limit_reached(state: &Arc<AppState>) -> bool {
+    if !state.audio_engine.lock().limit_reached() {
+        return false;
+    }
+    let max_secs = state.config.lock().max_recording_duration;
+    println!("⛔ Kayıt limiti doldu — otomatik durdurma");
+    notify("⛔ Kayıt limiti", &format!("Kayıt sınırına ulaşıldı (en fazla {:.0}s), durduruldu", max_secs));
+    toggle_recording(Arc::clone(state));
+    true
+}
+
+/// Basılı tutma modunda watchdog yok — sadece kayıt limitini izle
+fn watch_recording_limit(state: Arc<AppState>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        if !*state.is_recording.lock() {
+            break;
+        }
+        if stop_if_limit_reached(&state) {
+            break;
+        }
+    });
+}
+
 /// macOS bildirimi göster
 fn notify(title: &str, message: &str) {
     if let Some(handle) = APP_HANDLE.get() {
@@ -398,7 +733,7 @@ fn notify(title: &str, message: &str) {
 #[tauri::command]
 fn start_recording(state: tauri::State<'_, Arc<AppState>>) -> Result<String, String> {
     state.audio_engine.lock().start_recording()?;
-    *state.source_app.lock() = get_active_app();
+    *state.source_app.lock() = focus::frontmost();
     *state.is_recording.lock() = true;
     Ok("Kayıt başladı".into())
 }
@@ -409,14 +744,13 @@ async fn stop_and_transcribe(
 ) -> Result<serde_json::Value, String> {
     *state.is_recording.lock() = false;
 
-    let wav_bytes = {
-        let mut audio = state.audio_engine.lock();
+    let (samples, actual_rate) = {
+        let audio = state.audio_engine.lock();
         let samples = audio.stop_recording();
         if samples.is_empty() {
             return Err("Ses kaydı boş".into());
         }
-        let actual_rate = audio.get_actual_sample_rate();
-        AudioEngine::samples_to_wav(&samples, actual_rate)?
+        (samples, audio.get_actual_sample_rate())
     }; // audio kilidi burada (await öncesinde) serbest bırakılır
 
     let config = state.config.lock().clone();
@@ -427,10 +761,127 @@ async fn stop_and_transcribe(
         TranscribeMode::Dictation
     };
     let ctx = build_context(&config);
-    let result = transcriber.transcribe(&wav_bytes, &mode, &ctx)?;
+    let result = transcriber.transcribe_samples(&samples, actual_rate, config.chunk_duration, &mode, &ctx)?;
     Ok(serde_json::to_value(&result).unwrap_or_default())
 }
 
+/// Mikrofon testi: 3 saniye kaydet, seviye ve kırpılma raporu döndür
+#[tauri::command]
+async fn mic_test(state: tauri::State<'_, Arc<AppState>>) -> Result<audio::MicTestReport, String> {
+    if *state.is_recording.lock() {
+        return Err("Kayıt sürerken mikrofon testi yapılamaz".into());
+    }
+    let state = (*state).clone();
+    tauri::async_runtime::spawn_blocking(move || -> Result<audio::MicTestReport, String> {
+        // Test boyunca motor kayıtta kalır; kısayolla başlatma denemeleri akışa dokunmadan reddedilir
+        state.audio_engine.lock().start_recording()?;
+        println!("🎤 Mikrofon testi: 3s kayıt");
+        std::thread::sleep(std::time::Duration::from_secs(3));
+
+        let audio = state.audio_engine.lock();
+        let samples = audio.stop_recording();
+        let report = AudioEngine::analyze_levels(&samples, audio.get_actual_sample_rate(), &audio.get_device_name());
+        println!(
+            "🎤 Mikrofon testi: {} — rms {:.1} dB, tepe {:.1} dB ({})",
+            report.device, report.rms_db, report.peak_db, report.verdict
+        );
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Mikrofon testi hatası: {}", e))?
+}
+
+/// Diskteki ses dosyasını transkript et (WAV/MP3/M4A/OGG/FLAC), zaman damgalı sonuç döndür
+#[tauri::command]
+async fn transcribe_file(
+    state: tauri::State<'_, Arc<AppState>>,
+    path: String,
+    mode: String,
+) -> Result<Transcript, String> {
+    let config = state.config.lock().clone();
+    tauri::async_runtime::spawn_blocking(move || -> Result<Transcript, String> {
+        let t_start = std::time::Instant::now();
+        let (samples, rate) = decoder::decode_file(std::path::Path::new(&path))?;
+        println!(
+            "📂 Dosya çözüldü: {} ({:.1}s, {}Hz)",
+            path,
+            samples.len() as f64 / rate.max(1) as f64,
+            rate
+        );
+
+        // Dosyalar için komut modu anlamsız — dikte olarak işlenir
+        let mode = match mode.as_str() {
+            "translate" => TranscribeMode::Translate {
+                target_lang: config.translation_target.clone(),
+            },
+            _ => TranscribeMode::Dictation,
+        };
+        let mut ctx = build_context(&config);
+        ctx.active_app = None;
+
+        let transcriber = GeminiTranscriber::new(&config.api_key, &config.proxy_endpoint, &config.model);
+        let transcript = transcriber.transcribe_timed(&samples, rate, config.chunk_duration, &mode, &ctx)?;
+        println!(
+            "📂 Dosya transkript edildi ({:.1}s): {} parça",
+            t_start.elapsed().as_secs_f64(),
+            transcript.segments.len()
+        );
+        Ok(transcript)
+    })
+    .await
+    .map_err(|e| format!("Dosya transkripsiyon hatası: {}", e))?
+}
+
+/// Transkripti SRT/VTT/TXT/MD/JSON'a çevir; yol verilirse dosyaya da yaz
+#[tauri::command]
+fn export_transcript(transcript: Transcript, format: String, path: Option<String>) -> Result<String, String> {
+    let format = export::ExportFormat::parse(&format)?;
+    let content = export::export(&transcript, format)?;
+    if let Some(path) = path {
+        let mut path = std::path::PathBuf::from(path);
+        if path.extension().is_none() {
+            path.set_extension(format.extension());
+        }
+        std::fs::write(&path, &content)
+            .map_err(|e| format!("Dosya yazılamadı ({}): {}", path.display(), e))?;
+        println!("💾 Transkript kaydedildi: {}", path.display());
+    }
+    Ok(content)
+}
+
+/// Önizleme penceresinde onay bekleyen metin
+#[tauri::command]
+fn get_preview(state: tauri::State<'_, Arc<AppState>>) -> Option<String> {
+    state.preview.lock().as_ref().map(|p| p.text.clone())
+}
+
+/// Önizlemeyi onayla (Enter) — düzenlenmiş metni kaydın başladığı uygulamaya yaz
+#[tauri::command]
+fn confirm_preview(app: AppHandle, state: tauri::State<'_, Arc<AppState>>, text: String) -> Result<(), String> {
+    let pending = state.preview.lock().take().ok_or("Onay bekleyen metin yok")?;
+    preview::hide(&app);
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    let state = Arc::clone(&state);
+    std::thread::spawn(move || match insert_text(&state, &text, pending.app) {
+        Ok(()) => println!("✅ Önizleme yazıldı: {}", text),
+        Err(e) => {
+            println!("❌ Önizleme yazma hatası: {}", e);
+            notify("Yazma hatası", &e);
+        }
+    });
+    Ok(())
+}
+
+/// Önizlemeyi at (Esc)
+#[tauri::command]
+fn cancel_preview(app: AppHandle, state: tauri::State<'_, Arc<AppState>>) {
+    state.preview.lock().take();
+    preview::hide(&app);
+    println!("🗑️ Önizleme atıldı");
+}
+
 #[tauri::command]
 fn is_recording_cmd(state: tauri::State<'_, Arc<AppState>>) -> bool {
     *state.is_recording.lock()
@@ -442,10 +893,40 @@ fn get_config(state: tauri::State<'_, Arc<AppState>>) -> MillowConfig {
 }
 
 #[tauri::command]
-fn save_config(state: tauri::State<'_, Arc<AppState>>, new_config: MillowConfig) {
+fn save_config(state: tauri::State<'_, Arc<AppState>>, new_config: MillowConfig) -> Result<(), String> {
+    commander::validate_commands(&new_config.custom_commands)?;
     let mut config = state.config.lock();
     *config = new_config.clone();
     new_config.save();
+    Ok(())
+}
+
+/// Kayıtlı tüm komutlar (yerleşik + kullanıcı) ve meta verileri
+#[tauri::command]
+fn list_commands(state: tauri::State<'_, Arc<AppState>>) -> Vec<commander::CommandInfo> {
+    commander::CommandRegistry::from_config(&state.config.lock()).list()
+}
+
+/// Komutu çalıştırmadan çöz ve yapacaklarını döndür — `input` bir komut adıysa `params` ile,
+/// değilse söylenmiş bir ifade olarak tetikleyicilerle eşlenir
+#[tauri::command]
+fn dry_run_command(
+    state: tauri::State<'_, Arc<AppState>>,
+    input: String,
+    params: Option<String>,
+) -> Result<commander::DryRun, String> {
+    let commands = commander::CommandRegistry::from_config(&state.config.lock());
+    if commands.get(input.trim()).is_some() {
+        commands.dry_run(input.trim(), params.as_deref())
+    } else {
+        commands.dry_run_phrase(&input)
+    }
+}
+
+/// Onay bekleyen komutu onayla ya da iptal et
+#[tauri::command]
+fn confirm_command(state: tauri::State<'_, Arc<AppState>>, accept: bool) -> Result<String, String> {
+    resolve_pending_command(&state, accept)
 }
 
 #[tauri::command]
@@ -491,9 +972,10 @@ fn change_hotkey(app: AppHandle, state: tauri::State<'_, Arc<AppState>>, new_hot
                         std::thread::spawn(move || {
                             match state.audio_engine.lock().start_recording() {
                                 Ok(_) => {
-                                    *state.source_app.lock() = get_active_app();
+                                    *state.source_app.lock() = focus::frontmost();
                                     *state.is_recording.lock() = true;
                                     println!("🎙️  Kayıt başladı (basılı tutma)");
+                                    watch_recording_limit(Arc::clone(&state));
                                 }
                                 Err(e) => println!("❌ Kayıt hatası: {}", e),
                             }
@@ -604,8 +1086,11 @@ pub fn run() {
         is_active: Mutex::new(false),
         current_mode: Mutex::new("dictation".into()),
         source_app: Mutex::new(None),
+        insertions: Mutex::new(history::InsertionLog::default()),
+        preview: Mutex::new(None),
+        pending_command: Mutex::new(None),
         is_recording: Mutex::new(false),
-        is_processing: std::sync::atomic::AtomicBool::new(false),
+        segments: Arc::new(queue::DeliveryQueue::default()),
         window_visible: std::sync::atomic::AtomicBool::new(false),
         last_record_start: Mutex::new(std::time::Instant::now()),
     });
@@ -623,11 +1108,20 @@ pub fn run() {
             is_recording_cmd,
             get_config,
             save_config,
+            list_commands,
+            confirm_command,
+            dry_run_command,
             set_mode,
             health_check,
             change_hotkey,
             get_auto_launch,
             set_auto_launch,
+            mic_test,
+            transcribe_file,
+            export_transcript,
+            get_preview,
+            confirm_preview,
+            cancel_preview,
         ])
         .setup(move |app| {
             let _ = APP_HANDLE.set(app.handle().clone());
@@ -640,12 +1134,16 @@ pub fn run() {
             // ── Menü Oluştur ──
             let toggle = MenuItemBuilder::with_id("toggle", "Kayıt Başlat/Durdur")
                 .build(app)?;
+            let undo_dictation =
+                MenuItemBuilder::with_id("undo_dictation", "Son Dikteyi Geri Al").build(app)?;
             let mode_dictation =
                 MenuItemBuilder::with_id("mode_dictation", "Yazdırma Modu").build(app)?;
             let mode_translate =
                 MenuItemBuilder::with_id("mode_translate", "Çeviri Modu").build(app)?;
             let mode_command =
                 MenuItemBuilder::with_id("mode_command", "Komut Modu").build(app)?;
+            let mode_edit =
+                MenuItemBuilder::with_id("mode_edit", "Seçimi Düzenleme Modu").build(app)?;
             let sep1 = MenuItemBuilder::with_id("sep1", "───────────")
                 .enabled(false)
                 .build(app)?;
@@ -658,10 +1156,12 @@ pub fn run() {
             let menu = MenuBuilder::new(app)
                 .items(&[
                     &toggle,
+                    &undo_dictation,
                     &sep1,
                     &mode_dictation,
                     &mode_translate,
                     &mode_command,
+                    &mode_edit,
                     &sep2,
                     &settings,
                     &quit,
@@ -670,7 +1170,7 @@ pub fn run() {
 
             // ── Tray İkonu ──
             let state_for_tray = state_for_manager.clone();
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .icon(
                     tauri::image::Image::from_bytes(include_bytes!("../icons/tray-icon.png"))
                         .expect("tray ikon yüklenemedi"),
@@ -687,6 +1187,12 @@ pub fn run() {
                                 toggle_recording(state);
                             });
                         }
+                        "undo_dictation" => {
+                            let state = state_for_tray.clone();
+                            std::thread::spawn(move || {
+                                undo_last_dictation(&state);
+                            });
+                        }
                         "mode_dictation" => {
                             *state_for_tray.current_mode.lock() = "dictation".into();
                             notify("Mod", "📝 Yazdırma modu aktif");
@@ -699,6 +1205,10 @@ pub fn run() {
                             *state_for_tray.current_mode.lock() = "command".into();
                             notify("Mod", "🤖 Komut modu aktif");
                         }
+                        "mode_edit" => {
+                            *state_for_tray.current_mode.lock() = "edit".into();
+                            notify("Mod", "✏️ Metni seçin, kısayola basıp talimatı söyleyin");
+                        }
                         "settings" => {
                             state_for_tray.window_visible.store(true, std::sync::atomic::Ordering::Relaxed);
                             #[cfg(target_os = "macos")]
@@ -716,6 +1226,9 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // ── Giriş Seviyesi Ölçümü ──
+            attach_level_meter(app.handle(), &state_for_manager);
+
             // ── P4: Global Kısayol — hold_to_talk destekli ──
             let state_for_shortcut = state_for_manager.clone();
             let hotkey_str = state_for_manager.config.lock().hotkey.clone();
@@ -738,9 +1251,10 @@ pub fn run() {
                                     match state.audio_engine.lock().start_recording() {
                                         Ok(_) => {
                                             // Kayıt başlamadan önceki aktif uygulamayı kaydet
-                *state.source_app.lock() = get_active_app();
+                *state.source_app.lock() = focus::frontmost();
                 *state.is_recording.lock() = true;
                                             println!("🎙️  Kayıt başladı (basılı tutma)");
+                                            watch_recording_limit(Arc::clone(&state));
                                         }
                                         Err(e) => println!("❌ Kayıt hatası: {}", e),
                                     }
@@ -770,9 +1284,32 @@ pub fn run() {
                 }
             })?;
 
+            // ── Son Dikteyi Geri Al Kısayolu ──
+            let state_for_undo = state_for_manager.clone();
+            let undo_hotkey = state_for_manager.config.lock().undo_hotkey.clone();
+            if !undo_hotkey.is_empty() {
+                println!("🎹 Geri alma kısayolu: {}", undo_hotkey);
+                let registered = app.global_shortcut().on_shortcut(undo_hotkey.as_str(), move |_app, _shortcut, event| {
+                    if event.state == tauri_plugin_global_shortcut::ShortcutState::Released {
+                        let state = state_for_undo.clone();
+                        std::thread::spawn(move || {
+                            // Basılı değiştirici tuşlar Backspace'i Alt+Backspace'e çevirmesin
+                            std::thread::sleep(std::time::Duration::from_millis(300));
+                            undo_last_dictation(&state);
+                        });
+                    }
+                });
+                if let Err(e) = registered {
+                    println!("❌ Geri alma kısayolu kaydedilemedi: {}", e);
+                }
+            }
+
+            // ── Zamanlayıcılar ──
+            timers::start();
+
             // ── Double-Tap Fn Tuşu Dinleyicisi (NSEvent global monitor) ──
-            let state_for_fn = state_for_manager.clone();
             // NSEvent.addGlobalMonitorForEvents — main-thread-safe, WKWebView ile çakışmaz
+            #[cfg(target_os = "macos")]
             {
                 use cocoa::base::{id, nil};
                 use cocoa::foundation::NSAutoreleasePool;
@@ -782,6 +1319,7 @@ pub fn run() {
                 use objc::sel_impl;
                 use std::sync::Arc;
                 
+                let state_for_fn = state_for_manager.clone();
                 let state = state_for_fn.clone();
                 let last_fn_press = Arc::new(parking_lot::Mutex::new(std::time::Instant::now() - std::time::Duration::from_secs(10)));
                 
@@ -816,7 +1354,7 @@ pub fn run() {
                                     std::thread::spawn(move || {
                                         match state_start.audio_engine.lock().start_recording() {
                                             Ok(_) => {
-                                                *state_start.source_app.lock() = get_active_app();
+                                                *state_start.source_app.lock() = focus::frontmost();
                                                 *state_start.is_recording.lock() = true;
                                                 println!("🎙️  Fn kayıt başladı (hedef: {:?})", state_start.source_app.lock());
                                                 std::thread::spawn(|| { notify("🎙️ Kayıt", "3s susunca yazar, 30s susunca kapanır"); });
@@ -833,6 +1371,7 @@ pub fn run() {
                                                         std::thread::sleep(std::time::Duration::from_millis(500));
                                                         let is_rec = *state_wd.is_recording.lock();
                                                         if !is_rec { break; }
+                                                        if stop_if_limit_reached(&state_wd) { break; }
                                                         
                                                         let silence_secs = state_wd.audio_engine.lock().seconds_since_voice();
                                                         
@@ -0,0 +1,346 @@
+// Millow — Medya Kontrolü
+// Oynat/duraklat ve parça geçişini hangi oynatıcı çalıyorsa ona gönderir: macOS'ta sistem medya tuşları, Linux'ta MPRIS (D-Bus)
+
+use crate::commander::{Executor, Invocation};
+use serde::Serialize;
+
+/// MPRIS oynatıcılarının D-Bus ad öneki
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+/// MPRIS nesne yolu
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// Sistem medya tuşu
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKey {
+    PlayPause,
+    Next,
+    Previous,
+}
+
+impl MediaKey {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::PlayPause => "Oynat/Duraklat",
+            Self::Next => "Sonraki parça",
+            Self::Previous => "Önceki parça",
+        }
+    }
+
+    /// org.mpris.MediaPlayer2.Player yöntemi
+    fn mpris_method(&self) -> &'static str {
+        match self {
+            Self::PlayPause => "PlayPause",
+            Self::Next => "Next",
+            Self::Previous => "Previous",
+        }
+    }
+}
+
+/// Medya tuşunu etkin oynatıcıya gönder — macOS'ta sistem medya tuşu, Linux'ta MPRIS
+pub fn control(key: MediaKey, exec: &dyn Executor) -> Result<String, String> {
+    exec.desktop().media_key(exec, key)
+}
+
+// ── MPRIS ──
+
+/// Tuşa karşılık gelen yöntemi çalan (yoksa duraklatılmış) oynatıcıda çağır
+pub fn mpris(exec: &dyn Executor, key: MediaKey) -> Result<String, String> {
+    let player = active_player(exec)?;
+    exec.execute(&dbus_send(
+        &player,
+        &format!("{}Player.{}", MPRIS_PREFIX, key.mpris_method()),
+        &[],
+    ))
+    .map_err(|e| format!("{} başarısız: {}", key.label(), e))?;
+    Ok(format!("{} ({})", key.label(), player_name(&player)))
+}
+
+/// Çalan oynatıcı; yoksa duraklatılmış olan, o da yoksa ilk oynatıcı
+fn active_player(exec: &dyn Executor) -> Result<String, String> {
+    let players = players(exec)?;
+    let statuses: Vec<(String, String)> = players
+        .into_iter()
+        .map(|player| {
+            let status = playback_status(exec, &player).unwrap_or_default();
+            (player, status)
+        })
+        .collect();
+    ["Playing", "Paused"]
+        .iter()
+        .find_map(|wanted| statuses.iter().find(|(_, status)| status == wanted))
+        .or_else(|| statuses.first())
+        .map(|(player, _)| player.clone())
+        .ok_or_else(|| "Çalışan bir medya oynatıcısı (MPRIS) bulunamadı".into())
+}
+
+/// Oturum veriyolundaki MPRIS oynatıcıları
+fn players(exec: &dyn Executor) -> Result<Vec<String>, String> {
+    let reply = exec
+        .execute(&dbus_send(
+            "org.freedesktop.DBus",
+            "org.freedesktop.DBus.ListNames",
+            &[],
+        ))
+        .map_err(|e| format!("D-Bus oturum veriyoluna ulaşılamadı: {}", e))?;
+    Ok(dbus_strings(&reply)
+        .into_iter()
+        .filter(|name| name.starts_with(MPRIS_PREFIX))
+        .collect())
+}
+
+/// "Playing", "Paused" ya da "Stopped"
+fn playback_status(exec: &dyn Executor, player: &str) -> Option<String> {
+    let reply = exec
+        .execute(&dbus_send(
+            player,
+            "org.freedesktop.DBus.Properties.Get",
+            &[
+                &format!("string:{}Player", MPRIS_PREFIX),
+                "string:PlaybackStatus",
+            ],
+        ))
+        .ok()?;
+    dbus_strings(&reply).into_iter().next()
+}
+
+/// `dbus-send` ile oturum veriyolunda yöntem çağrısı
+fn dbus_send(dest: &str, method: &str, args: &[&str]) -> Invocation {
+    let path = if dest.starts_with(MPRIS_PREFIX) {
+        MPRIS_PATH
+    } else {
+        "/org/freedesktop/DBus"
+    };
+    let dest = format!("--dest={}", dest);
+    let mut all = vec!["--session", "--print-reply", &dest, path, method];
+    all.extend(args);
+    Invocation::run("dbus-send", &all)
+}
+
+/// `dbus-send --print-reply` çıktısındaki `string "…"` değerleri
+fn dbus_strings(reply: &str) -> Vec<String> {
+    reply
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().split_once("string \"")?.1;
+            rest.strip_suffix('"').map(str::to_string)
+        })
+        .collect()
+}
+
+/// "org.mpris.MediaPlayer2.firefox.instance_1_42" → "firefox"
+fn player_name(bus_name: &str) -> &str {
+    let name = bus_name.trim_start_matches(MPRIS_PREFIX);
+    name.split('.').next().unwrap_or(name)
+}
+
+// ── Sistem Medya Tuşları ──
+
+/// Medya tuşuna bas ve bırak
+pub fn post_key(key: MediaKey) -> Result<(), String> {
+    platform::post_key(key)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::MediaKey;
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::{NSAutoreleasePool, NSInteger, NSPoint, NSUInteger};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::c_void;
+
+    /// NSEventTypeSystemDefined
+    const SYSTEM_DEFINED: NSUInteger = 14;
+    /// Medya tuşu olaylarının alt türü (NX_SUBTYPE_AUX_CONTROL_BUTTONS)
+    const AUX_CONTROL_BUTTONS: i16 = 8;
+    /// kCGHIDEventTap
+    const HID_EVENT_TAP: u32 = 0;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn CGEventPost(tap: u32, event: *mut c_void);
+    }
+
+    /// NX_KEYTYPE_* (IOKit/hidsystem/ev_keymap.h)
+    fn key_type(key: MediaKey) -> NSInteger {
+        match key {
+            MediaKey::PlayPause => 16,
+            MediaKey::Next => 17,
+            MediaKey::Previous => 18,
+        }
+    }
+
+    pub fn post_key(key: MediaKey) -> Result<(), String> {
+        unsafe {
+            let pool = NSAutoreleasePool::new(nil);
+            let result = [true, false]
+                .into_iter()
+                .try_for_each(|down| post(key_type(key), down));
+            pool.drain();
+            result
+        }
+    }
+
+    unsafe fn post(key_type: NSInteger, down: bool) -> Result<(), String> {
+        let state: NSInteger = if down { 0xa } else { 0xb };
+        let event: id = msg_send![class!(NSEvent),
+            otherEventWithType: SYSTEM_DEFINED
+            location: NSPoint::new(0.0, 0.0)
+            modifierFlags: (state << 8) as NSUInteger
+            timestamp: 0.0f64
+            windowNumber: 0 as NSInteger
+            context: nil
+            subtype: AUX_CONTROL_BUTTONS
+            data1: (key_type << 16) | (state << 8)
+            data2: -1 as NSInteger];
+        if event == nil {
+            return Err("Medya tuşu olayı oluşturulamadı".into());
+        }
+        let cg_event: *mut c_void = msg_send![event, CGEvent];
+        if cg_event.is_null() {
+            return Err("Medya tuşu olayı dönüştürülemedi".into());
+        }
+        CGEventPost(HID_EVENT_TAP, cg_event);
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use super::MediaKey;
+
+    pub fn post_key(_: MediaKey) -> Result<(), String> {
+        Err("Sistem medya tuşları bu platformda desteklenmiyor".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::RecordingExecutor;
+    use crate::desktop::{LinuxDesktop, MacDesktop};
+
+    /// `dbus-send --print-reply` biçiminde ad listesi
+    fn list_names(names: &[&str]) -> String {
+        let mut reply =
+            String::from("method return time=1 sender=org.freedesktop.DBus\n   array [\n");
+        for name in names {
+            reply.push_str(&format!("      string \"{}\"\n", name));
+        }
+        reply.push_str("   ]\n");
+        reply
+    }
+
+    /// ListNames ve her oynatıcının PlaybackStatus sorgusunu yanıtlayan yürütücü
+    fn scripted(players: &[(&str, &str)]) -> RecordingExecutor {
+        let mut names = vec![":1.42", "org.freedesktop.Notifications"];
+        names.extend(players.iter().map(|(name, _)| *name));
+        let mut exec = RecordingExecutor::default()
+            .with_desktop(LinuxDesktop { wayland: true })
+            .answer("org.freedesktop.DBus.ListNames", &list_names(&names));
+        for (name, status) in players {
+            exec = exec.answer(
+                &format!(
+                    "--dest={} {} org.freedesktop.DBus.Properties.Get",
+                    name, MPRIS_PATH
+                ),
+                &format!("   variant       string \"{}\"\n", status),
+            );
+        }
+        exec
+    }
+
+    /// Son çağrının hedef oynatıcısı ve yöntemi
+    fn last_call(exec: &RecordingExecutor) -> (String, String) {
+        let calls = exec.take();
+        let Some(Invocation::Run { program, args }) = calls.last() else {
+            panic!("çağrı yok: {:?}", calls);
+        };
+        assert_eq!(program, "dbus-send");
+        (args[2].clone(), args[4].clone())
+    }
+
+    #[test]
+    fn playing_player_wins_over_paused() {
+        let exec = scripted(&[
+            ("org.mpris.MediaPlayer2.vlc", "Paused"),
+            ("org.mpris.MediaPlayer2.spotify", "Playing"),
+        ]);
+        assert_eq!(
+            control(MediaKey::Next, &exec),
+            Ok("Sonraki parça (spotify)".into())
+        );
+        assert_eq!(
+            last_call(&exec),
+            (
+                "--dest=org.mpris.MediaPlayer2.spotify".into(),
+                "org.mpris.MediaPlayer2.Player.Next".into()
+            )
+        );
+    }
+
+    #[test]
+    fn paused_player_wins_over_stopped() {
+        let exec = scripted(&[
+            ("org.mpris.MediaPlayer2.vlc", "Stopped"),
+            ("org.mpris.MediaPlayer2.firefox.instance_1_42", "Paused"),
+        ]);
+        assert_eq!(
+            control(MediaKey::PlayPause, &exec),
+            Ok("Oynat/Duraklat (firefox)".into())
+        );
+        assert_eq!(
+            last_call(&exec).0,
+            "--dest=org.mpris.MediaPlayer2.firefox.instance_1_42"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_player() {
+        let exec = scripted(&[
+            ("org.mpris.MediaPlayer2.mpv", "Stopped"),
+            ("org.mpris.MediaPlayer2.vlc", "Stopped"),
+        ]);
+        assert_eq!(
+            control(MediaKey::Previous, &exec),
+            Ok("Önceki parça (mpv)".into())
+        );
+    }
+
+    #[test]
+    fn errors_without_a_player() {
+        let exec = scripted(&[]);
+        let err = control(MediaKey::PlayPause, &exec).unwrap_err();
+        assert!(err.contains("bulunamadı"), "{}", err);
+        // Yalnızca ListNames sorulur, hiçbir oynatıcıya komut gitmez
+        assert_eq!(exec.take().len(), 1);
+    }
+
+    #[test]
+    fn mac_posts_system_media_key() {
+        let exec = RecordingExecutor::default().with_desktop(MacDesktop);
+        assert_eq!(
+            control(MediaKey::PlayPause, &exec),
+            Ok("Oynat/Duraklat".into())
+        );
+        assert_eq!(
+            exec.take(),
+            [Invocation::MediaKey {
+                key: MediaKey::PlayPause
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_dbus_replies() {
+        assert_eq!(
+            dbus_strings(&list_names(&["a", "b c"])),
+            ["a".to_string(), "b c".to_string()]
+        );
+        assert_eq!(player_name("org.mpris.MediaPlayer2.spotify"), "spotify");
+        assert_eq!(
+            player_name("org.mpris.MediaPlayer2.chromium.instance123"),
+            "chromium"
+        );
+    }
+}
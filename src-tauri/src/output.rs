@@ -0,0 +1,345 @@
+// Millow — Çıktı Yönlendirici
+// Sonucu moda göre bir veya birden çok hedefe gönderir: uygulama, pano, dosya, stdout, webhook
+
+use crate::config::MillowConfig;
+use crate::focus::FocusTarget;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Tek bir çıktı hedefi
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputTarget {
+    /// Kaydın başladığı uygulamaya yapıştır/yaz (varsayılan)
+    App,
+    /// Önce düzenlenebilir önizleme penceresinde göster, Enter ile uygulamaya yaz
+    Preview,
+    /// Yalnızca panoya kopyala
+    Clipboard,
+    /// Zaman damgasıyla dosyaya ekle (.md ise Markdown listesi)
+    File(PathBuf),
+    /// stdout'a JSON satırı yaz (betikler için)
+    Stdout,
+    /// JSON'u yerel webhook'a POST et
+    Webhook(String),
+}
+
+impl OutputTarget {
+    /// "app", "preview", "clipboard", "file", "file:/yol", "stdout", "webhook", "webhook:http://…"
+    /// — yolu/adresi verilmeyen file/webhook config'deki varsayılanı kullanır
+    pub fn parse(spec: &str, config: &MillowConfig) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (kind, arg) = match spec.split_once(':') {
+            Some((kind, arg)) if matches!(kind, "file" | "webhook") => (kind, Some(arg.trim())),
+            _ => (spec, None),
+        };
+        match kind.to_lowercase().as_str() {
+            "app" | "paste" => Ok(Self::App),
+            "preview" | "önizleme" => Ok(Self::Preview),
+            "clipboard" => Ok(Self::Clipboard),
+            "file" => Ok(Self::File(expand_home(arg.unwrap_or(&config.output_file)))),
+            "stdout" => Ok(Self::Stdout),
+            "webhook" => {
+                let url = arg.unwrap_or(&config.output_webhook).to_string();
+                if url.is_empty() {
+                    return Err("Webhook adresi tanımlı değil".into());
+                }
+                Ok(Self::Webhook(url))
+            }
+            other => Err(format!("Bilinmeyen çıktı hedefi: {}", other)),
+        }
+    }
+}
+
+/// Hedeflere gönderilen sonuç
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputEvent {
+    pub text: String,
+    /// "dictation", "translate", …
+    pub mode: String,
+    /// Kaydın başladığı uygulama
+    pub app: Option<String>,
+    /// Unix zamanı (saniye)
+    pub timestamp: u64,
+}
+
+impl OutputEvent {
+    pub fn new(text: &str, mode: &str, app: Option<String>) -> Self {
+        Self {
+            text: text.trim_end().to_string(),
+            mode: mode.to_string(),
+            app,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Mod için hedefler — `output_routes`'ta yoksa yalnızca uygulama.
+/// Hatalı girdiler günlüğe yazılıp atlanır.
+pub fn routes_for(config: &MillowConfig, mode: &str) -> Vec<OutputTarget> {
+    let Some(specs) = config.output_routes.get(mode) else {
+        return vec![OutputTarget::App];
+    };
+    specs
+        .iter()
+        .filter_map(|spec| match OutputTarget::parse(spec, config) {
+            Ok(target) => Some(target),
+            Err(e) => {
+                println!("⚠️ {} ({}), atlanıyor", e, mode);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Hedef uygulama `preview_apps` listesindeyse (ad veya bundle ID) yazmadan önce önizleme göster
+pub fn wants_preview(config: &MillowConfig, app: Option<&FocusTarget>) -> bool {
+    let Some(app) = app else {
+        return false;
+    };
+    config.preview_apps.iter().any(|entry| {
+        let entry = entry.trim();
+        entry.eq_ignore_ascii_case(&app.name)
+            || app.bundle_id.as_deref().is_some_and(|id| entry.eq_ignore_ascii_case(id))
+    })
+}
+
+/// Uygulama dışındaki bir hedefe gönder (App ve Preview çağıran tarafından işlenir)
+pub fn send(target: &OutputTarget, event: &OutputEvent, config: &MillowConfig) -> Result<(), String> {
+    match target {
+        OutputTarget::App => Err("Uygulama hedefi AutoTyper ile yazılır".into()),
+        OutputTarget::Preview => Err("Önizleme hedefi pencereyle gösterilir".into()),
+        OutputTarget::Clipboard => {
+            crate::sink::select_sink(&config.output_backend)?.set_clipboard(&event.text)
+        }
+        OutputTarget::File(path) => append_to_file(path, event),
+        OutputTarget::Stdout => {
+            let line = serde_json::to_string(event).map_err(|e| format!("JSON oluşturulamadı: {}", e))?;
+            let mut out = std::io::stdout().lock();
+            writeln!(out, "{}", line)
+                .and_then(|_| out.flush())
+                .map_err(|e| format!("stdout yazılamadı: {}", e))
+        }
+        OutputTarget::Webhook(url) => post_webhook(url, event),
+    }
+}
+
+/// Zaman damgalı satır ekle — .md dosyalarında Markdown listesi
+fn append_to_file(path: &PathBuf, event: &OutputEvent) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+    }
+    let stamp = local_timestamp(event.timestamp);
+    let markdown = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("md") || e.eq_ignore_ascii_case("markdown"));
+    let entry = if markdown {
+        format!("- **{}** {}\n", stamp, event.text.replace('\n', "\n  "))
+    } else {
+        format!("[{}] {}\n", stamp, event.text)
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{} açılamadı: {}", path.display(), e))?;
+    file.write_all(entry.as_bytes())
+        .map_err(|e| format!("{} yazılamadı: {}", path.display(), e))
+}
+
+fn post_webhook(url: &str, event: &OutputEvent) -> Result<(), String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .map_err(|e| format!("HTTP istemcisi oluşturulamadı: {}", e))?;
+    let response = client
+        .post(url)
+        .json(event)
+        .send()
+        .map_err(|e| format!("Webhook hatası: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Webhook hatası ({})", response.status()))
+    }
+}
+
+/// Olay zamanı yerel saatle "YYYY-MM-DD HH:MM" — saat farkı bulunamazsa UTC
+fn local_timestamp(unix: u64) -> String {
+    format_timestamp(unix, crate::timers::utc_offset())
+}
+
+/// Unix zamanı + saat farkı (saniye) → "YYYY-MM-DD HH:MM"
+fn format_timestamp(unix: u64, offset: i64) -> String {
+    let local = unix as i64 + offset;
+    // Gün sayısından takvim tarihi (Howard Hinnant, civil_from_days)
+    let days = local.div_euclid(86_400);
+    let secs = local.rem_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs / 60) % 60
+    )
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+            PathBuf::from(home).join(rest)
+        }
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    fn event(text: &str) -> OutputEvent {
+        OutputEvent {
+            text: text.into(),
+            mode: "dictation".into(),
+            app: Some("Notes".into()),
+            // 2024-03-01 12:34:56 UTC
+            timestamp: 1_709_296_496,
+        }
+    }
+
+    #[test]
+    fn parses_targets() {
+        let mut config = MillowConfig::default();
+        let parse = |spec: &str, config: &MillowConfig| OutputTarget::parse(spec, config);
+        assert_eq!(parse(" App ", &config), Ok(OutputTarget::App));
+        assert_eq!(parse("paste", &config), Ok(OutputTarget::App));
+        assert_eq!(parse("önizleme", &config), Ok(OutputTarget::Preview));
+        assert_eq!(parse("clipboard", &config), Ok(OutputTarget::Clipboard));
+        assert_eq!(parse("stdout", &config), Ok(OutputTarget::Stdout));
+        assert_eq!(
+            parse("file: /tmp/notlar.txt", &config),
+            Ok(OutputTarget::File("/tmp/notlar.txt".into()))
+        );
+        assert_eq!(
+            parse("file", &config),
+            Ok(OutputTarget::File(expand_home("~/.millow/notes.md")))
+        );
+        assert_eq!(
+            parse("webhook:http://127.0.0.1:9000/hook", &config),
+            Ok(OutputTarget::Webhook("http://127.0.0.1:9000/hook".into()))
+        );
+        assert_eq!(
+            parse("webhook", &config),
+            Err("Webhook adresi tanımlı değil".into())
+        );
+        assert_eq!(
+            parse("webhook:", &config),
+            Err("Webhook adresi tanımlı değil".into())
+        );
+        config.output_webhook = "http://localhost/varsayılan".into();
+        assert_eq!(
+            parse("webhook", &config),
+            Ok(OutputTarget::Webhook("http://localhost/varsayılan".into()))
+        );
+        assert_eq!(
+            parse("printer", &config),
+            Err("Bilinmeyen çıktı hedefi: printer".into())
+        );
+    }
+
+    #[test]
+    fn routes_fall_back_to_app() {
+        let mut config = MillowConfig::default();
+        assert_eq!(routes_for(&config, "dictation"), vec![OutputTarget::App]);
+
+        config.output_routes.insert(
+            "translate".into(),
+            vec!["clipboard".into(), "printer".into(), "stdout".into()],
+        );
+        assert_eq!(
+            routes_for(&config, "translate"),
+            vec![OutputTarget::Clipboard, OutputTarget::Stdout]
+        );
+        assert_eq!(routes_for(&config, "dictation"), vec![OutputTarget::App]);
+    }
+
+    #[test]
+    fn formats_local_timestamps() {
+        assert_eq!(format_timestamp(0, 0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_709_296_496, 0), "2024-03-01 12:34");
+        assert_eq!(
+            format_timestamp(1_709_296_496, 3 * 3600),
+            "2024-03-01 15:34"
+        );
+        // Saat farkı günü ve artık yılın şubatını geri alır
+        assert_eq!(
+            format_timestamp(1_709_251_200, -5 * 3600),
+            "2024-02-29 19:00"
+        );
+        assert_eq!(
+            format_timestamp(1_704_067_199, 5 * 3600 + 1800),
+            "2024-01-01 05:29"
+        );
+    }
+
+    #[test]
+    fn appends_markdown_and_plain_lines() {
+        let dir = std::env::temp_dir().join(format!("millow-output-{}", std::process::id()));
+        let stamp = local_timestamp(event("").timestamp);
+
+        let notes = dir.join("alt").join("notlar.md");
+        append_to_file(&notes, &event("ilk satır\nikinci satır")).unwrap();
+        append_to_file(&notes, &event("sonraki")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&notes).unwrap(),
+            format!(
+                "- **{0}** ilk satır\n  ikinci satır\n- **{0}** sonraki\n",
+                stamp
+            )
+        );
+
+        let log = dir.join("günlük.txt");
+        append_to_file(&log, &event("düz metin")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            format!("[{}] düz metin\n", stamp)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn posts_event_to_webhook() {
+        let server = StubServer::reply(200, "{}");
+        post_webhook(&format!("{}/hook", server.url), &event("merhaba")).unwrap();
+        let request = server.next_request().unwrap();
+        assert!(request.starts_with("POST /hook "), "{}", request);
+        assert!(request.contains("application/json"));
+        let body = request.split("\r\n\r\n").nth(1).unwrap();
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["text"], "merhaba");
+        assert_eq!(json["mode"], "dictation");
+        assert_eq!(json["app"], "Notes");
+        assert_eq!(json["timestamp"], 1_709_296_496);
+
+        let failing = StubServer::reply(500, "hata");
+        let err = post_webhook(&failing.url, &event("x")).unwrap_err();
+        assert!(err.starts_with("Webhook hatası (500"), "{}", err);
+    }
+}
@@ -0,0 +1,344 @@
+// Millow — Komut Güvenliği
+// Komutlar çalışmadan önce izin/yasak listelerini, URL kurallarını ve onay gereksinimini denetler; her denemeyi denetim günlüğüne yazar
+
+use crate::commander::{CommandInfo, Params, SlotKind};
+use crate::config::MillowConfig;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Onay bekleyen komutun geçerlilik süresi
+pub const CONFIRM_WINDOW: Duration = Duration::from_secs(20);
+
+/// Bekleyen komutu onaylayan ifadeler
+const CONFIRM_PHRASES: &[&str] = &["evet", "onayla", "onaylıyorum", "tamam", "yes", "confirm"];
+
+/// Bekleyen komutu iptal eden ifadeler
+const CANCEL_PHRASES: &[&str] = &["hayır", "iptal", "vazgeç", "no", "cancel"];
+
+/// Komutun çalışıp çalışamayacağına dair karar
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Allow,
+    /// Kullanıcı onayından sonra çalışabilir
+    Confirm,
+    Deny(String),
+}
+
+/// Komut güvenlik kuralları
+#[derive(Debug, Clone)]
+pub struct CommandPolicy {
+    /// Doluysa yalnızca bu komutlar
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    pub confirm_destructive: bool,
+    pub url_schemes: Vec<String>,
+    /// Boşsa tüm alan adları
+    pub url_domains: Vec<String>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: Vec::new(),
+            confirm_destructive: true,
+            url_schemes: vec!["https".into(), "http".into()],
+            url_domains: Vec::new(),
+        }
+    }
+}
+
+impl CommandPolicy {
+    pub fn from_config(config: &MillowConfig) -> Self {
+        Self {
+            allow: config.command_allowlist.clone(),
+            deny: config.command_denylist.clone(),
+            confirm_destructive: config.confirm_destructive_commands,
+            url_schemes: config.url_allowed_schemes.clone(),
+            url_domains: config.url_allowed_domains.clone(),
+        }
+    }
+
+    /// Doğrulanmış parametrelerle komutu denetle
+    pub fn check(&self, info: &CommandInfo, params: &Params) -> Verdict {
+        if self.deny.iter().any(|name| name == &info.name) {
+            return Verdict::Deny(format!("'{}' yasaklı komutlar listesinde", info.name));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|name| name == &info.name) {
+            return Verdict::Deny(format!(
+                "'{}' izin verilen komutlar listesinde değil",
+                info.name
+            ));
+        }
+        for (slot, rule) in &info.params {
+            if rule.kind != SlotKind::Url {
+                continue;
+            }
+            if let Some(Err(e)) = params.get(slot).map(|url| self.check_url(url)) {
+                return Verdict::Deny(format!("{}: {}", info.name, e));
+            }
+        }
+        if info.destructive && self.confirm_destructive {
+            Verdict::Confirm
+        } else {
+            Verdict::Allow
+        }
+    }
+
+    /// URL şeması ve alan adı izinli mi — alan adı kendisiyle ya da alt alan adlarıyla eşleşir
+    pub fn check_url(&self, url: &str) -> Result<(), String> {
+        let parsed = reqwest::Url::parse(url).map_err(|e| format!("geçersiz URL: {}", e))?;
+        if !self
+            .url_schemes
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(parsed.scheme()))
+        {
+            return Err(format!(
+                "'{}' şemasına izin verilmiyor: {}",
+                parsed.scheme(),
+                url
+            ));
+        }
+        if self.url_domains.is_empty() {
+            return Ok(());
+        }
+        let host = parsed.host_str().unwrap_or_default().to_lowercase();
+        let allowed = self.url_domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches("*.").to_lowercase();
+            host == domain || host.ends_with(&format!(".{}", domain))
+        });
+        if allowed {
+            Ok(())
+        } else {
+            Err(format!("'{}' alan adına izin verilmiyor", host))
+        }
+    }
+}
+
+// ── Onay ──
+
+/// Onay bekleyen komut
+#[derive(Debug, Clone)]
+pub struct PendingCommand {
+    pub name: String,
+    /// Doğrulanmış parametreler
+    pub params: Params,
+    created: Instant,
+}
+
+impl PendingCommand {
+    pub fn new(name: &str, params: Params) -> Self {
+        Self {
+            name: name.to_string(),
+            params,
+            created: Instant::now(),
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.created.elapsed() > CONFIRM_WINDOW
+    }
+}
+
+/// Onay cevabı mı: Some(true) onay, Some(false) iptal, None başka bir şey
+pub fn is_confirmation(text: &str) -> Option<bool> {
+    let normalized = crate::history::normalize(text);
+    let matches = |phrases: &[&str]| {
+        phrases
+            .iter()
+            .any(|p| crate::history::normalize(p) == normalized)
+    };
+    if normalized.is_empty() {
+        None
+    } else if matches(CONFIRM_PHRASES) {
+        Some(true)
+    } else if matches(CANCEL_PHRASES) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+// ── Denetim Günlüğü ──
+
+/// Denetim günlüğü satırı
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    timestamp: u64,
+    command: &'a str,
+    params: &'a Params,
+    /// "ok", "error", "denied", "pending", "confirmed", "cancelled", "expired"
+    outcome: &'a str,
+    message: &'a str,
+}
+
+fn audit_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+    PathBuf::from(home)
+        .join(".millow")
+        .join("command_audit.jsonl")
+}
+
+/// Komut denemesini ~/.millow/command_audit.jsonl dosyasına ekle
+pub fn audit(command: &str, params: &Params, outcome: &str, message: &str) {
+    let entry = AuditEntry {
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        command,
+        params,
+        outcome,
+        message,
+    };
+    if let Err(e) = append_entry(&audit_path(), &entry) {
+        println!("⚠️  Denetim günlüğü yazılamadı: {}", e);
+    }
+}
+
+fn append_entry(path: &PathBuf, entry: &AuditEntry) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{} açılamadı: {}", path.display(), e))?;
+    file.write_all(line.as_bytes())
+        .map_err(|e| format!("{} yazılamadı: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::SlotRule;
+
+    fn command(name: &str, destructive: bool) -> CommandInfo {
+        CommandInfo {
+            name: name.into(),
+            destructive,
+            ..Default::default()
+        }
+    }
+
+    fn policy(allow: &[&str], deny: &[&str]) -> CommandPolicy {
+        CommandPolicy {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn is_denied(verdict: Verdict) -> bool {
+        matches!(verdict, Verdict::Deny(_))
+    }
+
+    #[test]
+    fn deny_beats_allow() {
+        let policy = policy(&["lock_screen"], &["lock_screen"]);
+        let verdict = policy.check(&command("lock_screen", false), &Params::new());
+        assert_eq!(
+            verdict,
+            Verdict::Deny("'lock_screen' yasaklı komutlar listesinde".into())
+        );
+    }
+
+    #[test]
+    fn allowlist_limits_commands() {
+        let policy = policy(&["volume_up"], &[]);
+        assert_eq!(
+            policy.check(&command("volume_up", false), &Params::new()),
+            Verdict::Allow
+        );
+        assert!(is_denied(
+            policy.check(&command("open_url", false), &Params::new())
+        ));
+        // Boş izin listesi her şeye izin verir
+        let open = CommandPolicy::default();
+        assert_eq!(
+            open.check(&command("open_url", false), &Params::new()),
+            Verdict::Allow
+        );
+    }
+
+    #[test]
+    fn destructive_needs_confirmation() {
+        let mut policy = CommandPolicy::default();
+        let lock = command("lock_screen", true);
+        assert_eq!(policy.check(&lock, &Params::new()), Verdict::Confirm);
+        policy.confirm_destructive = false;
+        assert_eq!(policy.check(&lock, &Params::new()), Verdict::Allow);
+        // Yasak, onaydan önce gelir
+        policy.deny.push("lock_screen".into());
+        policy.confirm_destructive = true;
+        assert!(is_denied(policy.check(&lock, &Params::new())));
+    }
+
+    #[test]
+    fn checks_url_slots() {
+        let policy = CommandPolicy {
+            url_domains: vec!["example.com".into()],
+            ..Default::default()
+        };
+        let mut info = command("open_url", false);
+        info.params.insert(
+            "adres".into(),
+            SlotRule {
+                kind: SlotKind::Url,
+                ..Default::default()
+            },
+        );
+        let params = |url: &str| Params::from([("adres".to_string(), url.to_string())]);
+        assert_eq!(
+            policy.check(&info, &params("https://docs.example.com/a")),
+            Verdict::Allow
+        );
+        assert!(is_denied(policy.check(&info, &params("https://evil.org"))));
+        assert_eq!(policy.check(&info, &Params::new()), Verdict::Allow);
+    }
+
+    #[test]
+    fn matches_domains_and_subdomains() {
+        let policy = CommandPolicy {
+            url_domains: vec!["example.com".into(), "*.Millow.dev".into()],
+            ..Default::default()
+        };
+        assert!(policy.check_url("https://example.com/yol").is_ok());
+        assert!(policy.check_url("https://a.b.example.com").is_ok());
+        assert!(policy.check_url("http://EXAMPLE.com").is_ok());
+        assert!(policy.check_url("https://api.millow.dev").is_ok());
+        assert!(policy.check_url("https://millow.dev").is_ok());
+        assert!(policy.check_url("https://evil-example.com").is_err());
+        assert!(policy.check_url("https://example.com.evil.org").is_err());
+        assert!(policy.check_url("https://notexample.com").is_err());
+    }
+
+    #[test]
+    fn checks_url_schemes() {
+        let policy = CommandPolicy::default();
+        assert!(policy.check_url("https://example.com").is_ok());
+        assert!(policy.check_url("HTTP://example.com").is_ok());
+        assert!(policy.check_url("file:///etc/passwd").is_err());
+        assert!(policy.check_url("javascript:alert(1)").is_err());
+        assert!(policy.check_url("örnek adres").is_err());
+    }
+
+    #[test]
+    fn recognises_confirmations() {
+        assert_eq!(is_confirmation("Evet."), Some(true));
+        assert_eq!(is_confirmation("ONAYLA!"), Some(true));
+        assert_eq!(is_confirmation("Yes"), Some(true));
+        assert_eq!(is_confirmation("Hayır"), Some(false));
+        assert_eq!(is_confirmation("HAYIR"), Some(false));
+        assert_eq!(is_confirmation("iptal"), Some(false));
+        assert_eq!(is_confirmation("Cancel."), Some(false));
+        assert_eq!(is_confirmation("evet ama sonra"), None);
+        assert_eq!(is_confirmation("..."), None);
+        assert_eq!(is_confirmation(""), None);
+    }
+}
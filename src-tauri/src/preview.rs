@@ -0,0 +1,95 @@
+// Millow — Önizleme Penceresi
+// Metni yazmadan önce imlecin yanında düzenlenebilir küçük bir pencerede gösterir: Enter yazar, Esc atar
+
+use crate::focus::FocusTarget;
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Önizleme penceresinin etiketi (ön yüz bu etiketle Preview bileşenini çizer)
+pub const WINDOW_LABEL: &str = "preview";
+
+/// Pencerenin imleçten uzaklığı (piksel)
+const CURSOR_OFFSET: f64 = 16.0;
+
+/// Onay bekleyen metin
+#[derive(Debug, Clone)]
+pub struct PendingPreview {
+    pub text: String,
+    /// Onaylanınca yazılacak uygulama
+    pub app: Option<FocusTarget>,
+}
+
+impl PendingPreview {
+    pub fn new(text: &str, app: Option<FocusTarget>) -> Self {
+        Self {
+            text: text.to_string(),
+            app,
+        }
+    }
+
+    /// Aynı kayıttan gelen yeni segmenti sona ekle
+    pub fn append(&mut self, text: &str) {
+        let joined = self.text.ends_with(char::is_whitespace) || text.starts_with(char::is_whitespace);
+        if !self.text.is_empty() && !joined {
+            self.text.push(' ');
+        }
+        self.text.push_str(text);
+    }
+}
+
+/// Pencereyi (yoksa oluşturup) imlecin yanında göster ve metni gönder
+pub fn show(app: &AppHandle, text: &str) -> Result<(), String> {
+    let window = match app.get_webview_window(WINDOW_LABEL) {
+        Some(window) => window,
+        None => WebviewWindowBuilder::new(app, WINDOW_LABEL, WebviewUrl::default())
+            .title("Millow — Önizleme")
+            .inner_size(440.0, 170.0)
+            .resizable(false)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .visible(false)
+            .build()
+            .map_err(|e| format!("Önizleme penceresi açılamadı: {}", e))?,
+    };
+
+    place_near_cursor(app, &window);
+    // Yeni oluşturulan pencere metni yüklenince get_preview ile alır
+    let _ = app.emit_to(WINDOW_LABEL, "preview-text", text);
+    window
+        .show()
+        .and_then(|_| window.set_focus())
+        .map_err(|e| format!("Önizleme penceresi gösterilemedi: {}", e))
+}
+
+/// Pencereyi gizle (bir sonraki önizleme için kapatılmaz)
+pub fn hide(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Fare imlecinin sağ altına yerleştir, ekrandan taşmasın
+fn place_near_cursor(app: &AppHandle, window: &WebviewWindow) {
+    let Ok(cursor) = app.cursor_position() else {
+        return;
+    };
+    let mut x = cursor.x + CURSOR_OFFSET;
+    let mut y = cursor.y + CURSOR_OFFSET;
+
+    if let (Ok(Some(monitor)), Ok(size)) = (app.monitor_from_point(cursor.x, cursor.y), window.outer_size()) {
+        let origin = monitor.position();
+        let area = monitor.size();
+        let right = (origin.x + area.width as i32) as f64;
+        let bottom = (origin.y + area.height as i32) as f64;
+        if x + size.width as f64 > right {
+            x = cursor.x - CURSOR_OFFSET - size.width as f64;
+        }
+        if y + size.height as f64 > bottom {
+            y = cursor.y - CURSOR_OFFSET - size.height as f64;
+        }
+        x = x.max(origin.x as f64);
+        y = y.max(origin.y as f64);
+    }
+
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+}
@@ -0,0 +1,191 @@
+// Millow — Segment Teslim Kuyruğu
+// Segmentler eşzamanlı transkript edilir, sonuçları yakalanma sırasıyla ve hiçbiri atlanmadan teslim edilir
+
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Teslim adımı (çıktıya yazma, düzeltme uygulama…)
+type Delivery = Box<dyn FnOnce() + Send>;
+
+/// Sıra numaralı teslim kuyruğu
+#[derive(Default)]
+pub struct DeliveryQueue {
+    inner: Mutex<QueueState>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Bir sonraki yakalanan segmentin sıra numarası
+    next_ticket: u64,
+    /// Teslim sırası gelen sıra numarası
+    next_delivery: u64,
+    /// Sırası gelmemiş tamamlanmış işler (None = teslim edilecek bir şey yok)
+    ready: BTreeMap<u64, Option<Delivery>>,
+    /// Bir iş parçacığı şu an teslim ediyor mu
+    delivering: bool,
+}
+
+/// Yakalanmış bir segmentin sırası — teslim edilmeden düşerse (hata, panik) sıra boş geçilir
+pub struct Ticket {
+    seq: u64,
+    queue: Arc<DeliveryQueue>,
+    done: bool,
+}
+
+impl DeliveryQueue {
+    /// Yakalama anında sıra al — transkripsiyondan önce, yakalayan iş parçacığında çağrılmalı
+    pub fn ticket(self: &Arc<Self>) -> Ticket {
+        let mut inner = self.inner.lock();
+        let seq = inner.next_ticket;
+        inner.next_ticket += 1;
+        Ticket {
+            seq,
+            queue: Arc::clone(self),
+            done: false,
+        }
+    }
+
+    /// Transkript edilen veya teslim sırası bekleyen segment sayısı
+    pub fn pending(&self) -> u64 {
+        let inner = self.inner.lock();
+        inner.next_ticket - inner.next_delivery
+    }
+
+    fn complete(&self, seq: u64, delivery: Option<Delivery>) {
+        let mut inner = self.inner.lock();
+        inner.ready.insert(seq, delivery);
+        if inner.delivering {
+            // Teslim eden iş parçacığı bunu da sırası gelince alır
+            return;
+        }
+        inner.delivering = true;
+
+        loop {
+            let next = inner.next_delivery;
+            let Some(delivery) = inner.ready.remove(&next) else {
+                inner.delivering = false;
+                return;
+            };
+            inner.next_delivery += 1;
+            if let Some(delivery) = delivery {
+                // Kilidi bırakarak teslim et — diğer segmentler bu sırada tamamlanabilir
+                drop(inner);
+                if std::panic::catch_unwind(std::panic::AssertUnwindSafe(delivery)).is_err() {
+                    println!(
+                        "❌ Segment {} teslim edilirken hata oluştu, sıradakine geçiliyor",
+                        next
+                    );
+                }
+                inner = self.inner.lock();
+            }
+        }
+    }
+}
+
+impl Ticket {
+    /// Sırası gelince (önceki tüm segmentler teslim edildikten sonra) çalıştır
+    pub fn deliver(mut self, delivery: impl FnOnce() + Send + 'static) {
+        self.done = true;
+        self.queue.complete(self.seq, Some(Box::new(delivery)));
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if !self.done {
+            self.queue.complete(self.seq, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> (Arc<Mutex<Vec<u64>>>, impl Fn(u64) -> Delivery) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&log);
+        let push = move |n: u64| -> Delivery {
+            let sink = Arc::clone(&sink);
+            Box::new(move || sink.lock().push(n))
+        };
+        (log, push)
+    }
+
+    #[test]
+    fn delivers_in_capture_order() {
+        let queue = Arc::new(DeliveryQueue::default());
+        let (log, push) = recorder();
+        let tickets: Vec<_> = (0..4).map(|_| queue.ticket()).collect();
+        assert_eq!(queue.pending(), 4);
+
+        let mut tickets: Vec<_> = tickets.into_iter().map(Some).collect();
+        for i in [2, 3, 1] {
+            tickets[i].take().unwrap().deliver(push(i as u64));
+        }
+        // İlk segment bitmeden hiçbiri teslim edilmez
+        assert!(log.lock().is_empty());
+        assert_eq!(queue.pending(), 4);
+
+        tickets[0].take().unwrap().deliver(push(0));
+        assert_eq!(*log.lock(), vec![0, 1, 2, 3]);
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn dropped_ticket_is_skipped() {
+        let queue = Arc::new(DeliveryQueue::default());
+        let (log, push) = recorder();
+        let first = queue.ticket();
+        let second = queue.ticket();
+        let third = queue.ticket();
+
+        third.deliver(push(3));
+        first.deliver(push(1));
+        assert_eq!(*log.lock(), vec![1]);
+        assert_eq!(queue.pending(), 2);
+
+        drop(second);
+        assert_eq!(*log.lock(), vec![1, 3]);
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn panicking_delivery_does_not_stall() {
+        let queue = Arc::new(DeliveryQueue::default());
+        let (log, push) = recorder();
+        let first = queue.ticket();
+        let second = queue.ticket();
+
+        second.deliver(push(2));
+        first.deliver(|| panic!("teslim hatası"));
+        assert_eq!(*log.lock(), vec![2]);
+
+        queue.ticket().deliver(push(3));
+        assert_eq!(*log.lock(), vec![2, 3]);
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn concurrent_deliveries_keep_order() {
+        let queue = Arc::new(DeliveryQueue::default());
+        let (log, push) = recorder();
+        let push = Arc::new(push);
+        let workers: Vec<_> = (0..16u64)
+            .map(|n| {
+                let ticket = queue.ticket();
+                let push = Arc::clone(&push);
+                std::thread::spawn(move || {
+                    std::thread::sleep(std::time::Duration::from_millis((16 - n) % 5));
+                    ticket.deliver(push(n));
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        assert_eq!(*log.lock(), (0..16).collect::<Vec<_>>());
+        assert_eq!(queue.pending(), 0);
+    }
+}
@@ -0,0 +1,706 @@
+// Millow — Metin Çıkış Arka Uçları
+// macOS (NSPasteboard + CGEvent), Linux X11 (xclip/xsel + XTest) ve Wayland (wl-clipboard + wtype/ydotool)
+
+#[cfg(target_os = "linux")]
+use std::process::Command;
+use std::time::Duration;
+
+/// Metni odaktaki uygulamaya ileten platform arka ucu
+pub trait TextSink: Send {
+    /// Günlükler için kısa ad ("macos", "x11", "wayland")
+    fn name(&self) -> &'static str;
+
+    /// Metni panoya koy, yapıştırma kısayolunu gönder, eski panoyu geri yükle
+    fn paste(&self, text: &str) -> Result<(), String>;
+
+    /// Metni Unicode tuş vuruşları olarak yaz (rate: karakter/sn, 0 = sınırsız)
+    fn type_text(&self, text: &str, rate: f32) -> Result<(), String>;
+
+    /// İmlecin solundaki `count` karakteri Backspace ile sil
+    fn erase(&self, count: usize) -> Result<(), String>;
+
+    /// Uygulamanın geri alma kısayolunu gönder (Cmd+Z / Ctrl+Z)
+    fn undo(&self) -> Result<(), String>;
+
+    /// İmleci ok tuşlarıyla taşı (negatif = sola)
+    fn move_cursor(&self, offset: isize) -> Result<(), String>;
+
+    /// Metni yalnızca panoya koy (yapıştırmadan, geri yüklemeden)
+    fn set_clipboard(&self, text: &str) -> Result<(), String>;
+
+    /// Odaktaki uygulamada seçili metni oku (seçim yoksa boş) — pano korunur
+    fn selected_text(&self) -> Result<String, String>;
+}
+
+/// Çalışma anında arka ucu seç. `backend`: "auto", "macos", "x11" veya "wayland"
+pub fn select_sink(backend: &str) -> Result<Box<dyn TextSink>, String> {
+    let backend = backend.trim().to_lowercase();
+    let backend = if backend.is_empty() || backend == "auto" {
+        detect_backend()?
+    } else {
+        backend
+    };
+
+    match backend.as_str() {
+        #[cfg(target_os = "macos")]
+        "macos" => Ok(Box::new(MacSink)),
+        #[cfg(target_os = "linux")]
+        "x11" => Ok(Box::new(X11Sink::new()?)),
+        #[cfg(target_os = "linux")]
+        "wayland" => Ok(Box::new(WaylandSink::new()?)),
+        other => Err(format!("Bu platformda desteklenmeyen çıkış arka ucu: {}", other)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn detect_backend() -> Result<String, String> {
+    Ok("macos".into())
+}
+
+/// Wayland oturumu önceliklidir — XWayland altında XTest yalnızca X istemcilerine ulaşır
+#[cfg(target_os = "linux")]
+fn detect_backend() -> Result<String, String> {
+    let session = std::env::var("XDG_SESSION_TYPE").unwrap_or_default();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() || session == "wayland" {
+        Ok("wayland".into())
+    } else if std::env::var_os("DISPLAY").is_some() {
+        Ok("x11".into())
+    } else {
+        Err("Görüntü sunucusu bulunamadı (DISPLAY / WAYLAND_DISPLAY tanımlı değil)".into())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn detect_backend() -> Result<String, String> {
+    Err("Bu platform için metin çıkış arka ucu yok".into())
+}
+
+// ── macOS ──
+
+/// NSPasteboard + CGEvent Cmd+V; tuş vuruşları enigo (CGEvent Unicode) ile
+#[cfg(target_os = "macos")]
+pub struct MacSink;
+
+#[cfg(target_os = "macos")]
+impl TextSink for MacSink {
+    fn name(&self) -> &'static str {
+        "macos"
+    }
+
+    fn paste(&self, text: &str) -> Result<(), String> {
+        // 1. Mevcut panoyu tüm türleriyle yedekle (resim, dosya, zengin metin dahil)
+        let old_clipboard = crate::clipboard::snapshot();
+
+        // 2. Metni panoya kopyala
+        let written_count = crate::clipboard::set_text(text, true)?;
+
+        // 3. CGEvent ile Cmd+V yapıştır (V tuşu keycode = 9)
+        post_command_key(9)?;
+
+        // 4. Yapıştırma bekleme
+        std::thread::sleep(Duration::from_millis(250));
+
+        // 5. Eski panoyu geri yükle — kullanıcı bu arada yeni bir şey kopyaladıysa dokunma
+        crate::clipboard::restore(&old_clipboard, written_count);
+
+        Ok(())
+    }
+
+    fn type_text(&self, text: &str, rate: f32) -> Result<(), String> {
+        type_with_enigo(text, rate)
+    }
+
+    fn erase(&self, count: usize) -> Result<(), String> {
+        erase_with_enigo(count)
+    }
+
+    fn undo(&self) -> Result<(), String> {
+        // Z tuşu keycode = 6
+        post_command_key(6)
+    }
+
+    fn move_cursor(&self, offset: isize) -> Result<(), String> {
+        move_cursor_with_enigo(offset)
+    }
+
+    fn set_clipboard(&self, text: &str) -> Result<(), String> {
+        crate::clipboard::set_text(text, false).map(|_| ())
+    }
+
+    fn selected_text(&self) -> Result<String, String> {
+        // Panoyu yedekle, Cmd+C gönder (C tuşu keycode = 8), pano değişene kadar bekle
+        let old_clipboard = crate::clipboard::snapshot();
+        let before = crate::clipboard::change_count();
+        post_command_key(8)?;
+
+        let mut copied = false;
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(50));
+            if crate::clipboard::change_count() != before {
+                copied = true;
+                break;
+            }
+        }
+        if !copied {
+            // Seçim yoksa uygulama panoya dokunmaz
+            return Ok(String::new());
+        }
+
+        let text = crate::clipboard::text().unwrap_or_default();
+        crate::clipboard::restore(&old_clipboard, crate::clipboard::change_count());
+        Ok(text)
+    }
+}
+
+/// CGEvent ile Cmd+<tuş> gönder
+#[cfg(target_os = "macos")]
+fn post_command_key(keycode: u16) -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| "CGEventSource oluşturulamadı".to_string())?;
+
+    let key_down = CGEvent::new_keyboard_event(source.clone(), keycode, true)
+        .map_err(|_| "KeyDown event oluşturulamadı".to_string())?;
+    let key_up = CGEvent::new_keyboard_event(source, keycode, false)
+        .map_err(|_| "KeyUp event oluşturulamadı".to_string())?;
+
+    key_down.set_flags(CGEventFlags::CGEventFlagCommand);
+    key_up.set_flags(CGEventFlags::CGEventFlagCommand);
+
+    key_down.post(CGEventTapLocation::HID);
+    key_up.post(CGEventTapLocation::HID);
+    Ok(())
+}
+
+// ── Linux X11 ──
+
+/// Pano xclip (yoksa xsel) ile, tuş vuruşları XTest (enigo/xdo) ile — Xvfb altında da çalışır
+#[cfg(target_os = "linux")]
+pub struct X11Sink {
+    clipboard: X11Clipboard,
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy)]
+enum X11Clipboard {
+    Xclip,
+    Xsel,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Sink {
+    pub fn new() -> Result<Self, String> {
+        let clipboard = if which("xclip") {
+            X11Clipboard::Xclip
+        } else if which("xsel") {
+            X11Clipboard::Xsel
+        } else {
+            return Err("X11 panosu için xclip veya xsel gerekli".into());
+        };
+        Ok(Self { clipboard })
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        match self.clipboard {
+            X11Clipboard::Xclip => command_output("xclip", &["-selection", "clipboard", "-o"]),
+            X11Clipboard::Xsel => command_output("xsel", &["--clipboard", "--output"]),
+        }
+    }
+
+    fn write_clipboard(&self, text: &str) -> Result<(), String> {
+        match self.clipboard {
+            X11Clipboard::Xclip => pipe_to("xclip", &["-selection", "clipboard", "-i"], text),
+            X11Clipboard::Xsel => pipe_to("xsel", &["--clipboard", "--input"], text),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl TextSink for X11Sink {
+    fn name(&self) -> &'static str {
+        "x11"
+    }
+
+    fn paste(&self, text: &str) -> Result<(), String> {
+        let old_clipboard = self.read_clipboard();
+        self.write_clipboard(text)?;
+
+        ctrl_chord_with_enigo('v')?;
+
+        std::thread::sleep(Duration::from_millis(250));
+
+        // Pano hâlâ bizim metnimizi tutuyorsa eskisini geri koy (yalnızca metin)
+        if let Some(old) = old_clipboard {
+            if self.read_clipboard().as_deref() == Some(text) {
+                let _ = self.write_clipboard(&old);
+            }
+        }
+        Ok(())
+    }
+
+    fn type_text(&self, text: &str, rate: f32) -> Result<(), String> {
+        type_with_enigo(text, rate)
+    }
+
+    fn erase(&self, count: usize) -> Result<(), String> {
+        erase_with_enigo(count)
+    }
+
+    fn undo(&self) -> Result<(), String> {
+        ctrl_chord_with_enigo('z')
+    }
+
+    fn move_cursor(&self, offset: isize) -> Result<(), String> {
+        move_cursor_with_enigo(offset)
+    }
+
+    fn set_clipboard(&self, text: &str) -> Result<(), String> {
+        self.write_clipboard(text)
+    }
+
+    fn selected_text(&self) -> Result<String, String> {
+        // PRIMARY seçimi kaldırılmış bir seçimi de tutabilir — canlı seçimi Ctrl+C ile al
+        copy_selection(
+            || self.read_clipboard(),
+            |text| self.write_clipboard(text.unwrap_or_default()),
+            || ctrl_chord_with_enigo('c'),
+        )
+    }
+}
+
+/// XTest ile Ctrl+<harf> gönder
+#[cfg(target_os = "linux")]
+fn ctrl_chord_with_enigo(letter: char) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Klavye simülasyonu başlatılamadı: {}", e))?;
+    let send = |enigo: &mut Enigo, key: Key, direction: Direction| {
+        enigo
+            .key(key, direction)
+            .map_err(|e| format!("Ctrl+{} gönderilemedi: {}", letter.to_ascii_uppercase(), e))
+    };
+    send(&mut enigo, Key::Control, Direction::Press)?;
+    let sent = send(&mut enigo, Key::Unicode(letter), Direction::Click);
+    send(&mut enigo, Key::Control, Direction::Release)?;
+    sent
+}
+
+// ── Linux Wayland ──
+
+/// Pano wl-clipboard ile; tuş vuruşları wtype (virtual-keyboard protokolü), yoksa ydotool (uinput)
+#[cfg(target_os = "linux")]
+pub struct WaylandSink {
+    keyboard: WaylandKeyboard,
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, PartialEq)]
+enum WaylandKeyboard {
+    Wtype,
+    Ydotool,
+}
+
+#[cfg(target_os = "linux")]
+impl WaylandSink {
+    pub fn new() -> Result<Self, String> {
+        if !which("wl-copy") || !which("wl-paste") {
+            return Err("Wayland panosu için wl-clipboard (wl-copy/wl-paste) gerekli".into());
+        }
+        let keyboard = if which("wtype") {
+            WaylandKeyboard::Wtype
+        } else if which("ydotool") {
+            WaylandKeyboard::Ydotool
+        } else {
+            return Err("Wayland tuş simülasyonu için wtype veya ydotool gerekli".into());
+        };
+        Ok(Self { keyboard })
+    }
+
+    /// Tek bir tuşa `count` kez bas (wtype tuş adı, ydotool Linux keycode)
+    fn repeat_key(&self, keysym: &str, keycode: u16, count: usize) -> Result<(), String> {
+        let down = format!("{}:1", keycode);
+        let up = format!("{}:0", keycode);
+        let mut wtype_args = Vec::with_capacity(count * 2);
+        let mut ydotool_args = Vec::with_capacity(count * 2);
+        for _ in 0..count {
+            wtype_args.extend(["-k", keysym]);
+            ydotool_args.extend([down.as_str(), up.as_str()]);
+        }
+        self.send_keys(&wtype_args, &ydotool_args)
+    }
+
+    /// Tuş dizisini wtype ya da `ydotool key` ile gönder
+    fn send_keys(&self, wtype_args: &[&str], ydotool_args: &[&str]) -> Result<(), String> {
+        if wtype_args.is_empty() {
+            return Ok(());
+        }
+        let status = match self.keyboard {
+            WaylandKeyboard::Wtype => Command::new("wtype").args(wtype_args).status(),
+            WaylandKeyboard::Ydotool => Command::new("ydotool").arg("key").args(ydotool_args).status(),
+        }
+        .map_err(|e| format!("Tuş vuruşu gönderilemedi: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err("Tuş vuruşu gönderilemedi".into())
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl TextSink for WaylandSink {
+    fn name(&self) -> &'static str {
+        "wayland"
+    }
+
+    fn paste(&self, text: &str) -> Result<(), String> {
+        let old_clipboard = command_output("wl-paste", &["--no-newline"]);
+        pipe_to("wl-copy", &[], text)?;
+
+        // Ctrl+V — ydotool Linux keycode kullanır: 29 = LEFTCTRL, 47 = V
+        self.send_keys(&["-M", "ctrl", "v", "-m", "ctrl"], &["29:1", "47:1", "47:0", "29:0"])?;
+
+        std::thread::sleep(Duration::from_millis(250));
+
+        if command_output("wl-paste", &["--no-newline"]).as_deref() == Some(text) {
+            match old_clipboard {
+                Some(old) => {
+                    let _ = pipe_to("wl-copy", &[], &old);
+                }
+                None => {
+                    let _ = Command::new("wl-copy").arg("--clear").status();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn type_text(&self, text: &str, rate: f32) -> Result<(), String> {
+        let delay_ms = if rate > 0.0 { (1000.0 / rate).round() as u32 } else { 0 };
+        let delay = delay_ms.to_string();
+        // wtype her karakter için geçici keymap yükler — Türkçe karakterler düzenden bağımsız.
+        // ydotool yalnızca aktif düzende bulunan karakterleri yazabilir.
+        let status = match self.keyboard {
+            WaylandKeyboard::Wtype => Command::new("wtype")
+                .args(["-d", &delay, "--", text])
+                .status(),
+            WaylandKeyboard::Ydotool => Command::new("ydotool")
+                .args(["type", "--key-delay", &delay, "--", text])
+                .status(),
+        }
+        .map_err(|e| format!("Tuş vuruşu gönderilemedi: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err("Tuş vuruşu gönderilemedi".into())
+        }
+    }
+
+    fn erase(&self, count: usize) -> Result<(), String> {
+        // 14 = BACKSPACE
+        self.repeat_key("BackSpace", 14, count)
+    }
+
+    fn undo(&self) -> Result<(), String> {
+        // 44 = Z
+        self.send_keys(&["-M", "ctrl", "z", "-m", "ctrl"], &["29:1", "44:1", "44:0", "29:0"])
+    }
+
+    fn move_cursor(&self, offset: isize) -> Result<(), String> {
+        // 105 = LEFT, 106 = RIGHT
+        if offset < 0 {
+            self.repeat_key("Left", 105, offset.unsigned_abs())
+        } else {
+            self.repeat_key("Right", 106, offset as usize)
+        }
+    }
+
+    fn set_clipboard(&self, text: &str) -> Result<(), String> {
+        pipe_to("wl-copy", &[], text)
+    }
+
+    fn selected_text(&self) -> Result<String, String> {
+        copy_selection(
+            || command_output("wl-paste", &["--no-newline"]),
+            |text| match text {
+                Some(text) => pipe_to("wl-copy", &[], text),
+                None => pipe_to("wl-copy", &["--clear"], ""),
+            },
+            // 46 = C
+            || self.send_keys(&["-M", "ctrl", "c", "-m", "ctrl"], &["29:1", "46:1", "46:0", "29:0"]),
+        )
+    }
+}
+
+// ── Ortak yardımcılar ──
+
+/// Seçimi kopyalatıp panodan oku (Linux'ta değişim sayacı yok) — pano önce bir işaretle doldurulur,
+/// işaret yerinde kalırsa seçim yok sayılır. Eski pano metni (yoksa boş pano) geri yüklenir
+#[cfg(target_os = "linux")]
+fn copy_selection(
+    read: impl Fn() -> Option<String>,
+    write: impl Fn(Option<&str>) -> Result<(), String>,
+    copy: impl FnOnce() -> Result<(), String>,
+) -> Result<String, String> {
+    let old_clipboard = read();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let marker = format!("millow-seçim-{}-{}", std::process::id(), nanos);
+    write(Some(&marker))?;
+
+    let sent = copy();
+    let mut selection = None;
+    if sent.is_ok() {
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(50));
+            if let Some(text) = read().filter(|text| *text != marker) {
+                selection = Some(text);
+                break;
+            }
+        }
+    }
+
+    let _ = write(old_clipboard.as_deref());
+    sent?;
+    Ok(selection.unwrap_or_default())
+}
+
+/// Metni enigo ile Unicode tuş vuruşları olarak gönder.
+/// Karakterler klavye düzeninden bağımsızdır (ğ, ü, ş, ı, İ, ö, ç dahil);
+/// satır sonu ve sekme gerçek Return/Tab tuşlarıdır.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn type_with_enigo(text: &str, rate: f32) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Klavye simülasyonu başlatılamadı: {}", e))?;
+    let delay = (rate > 0.0).then(|| Duration::from_secs_f32(1.0 / rate));
+
+    let mut pending = String::new();
+    let flush = |enigo: &mut Enigo, pending: &mut String| -> Result<(), String> {
+        if !pending.is_empty() {
+            enigo
+                .text(pending)
+                .map_err(|e| format!("Tuş vuruşu gönderilemedi: {}", e))?;
+            pending.clear();
+        }
+        Ok(())
+    };
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => continue,
+            '\n' | '\t' => {
+                flush(&mut enigo, &mut pending)?;
+                let key = if c == '\n' { Key::Return } else { Key::Tab };
+                enigo
+                    .key(key, Direction::Click)
+                    .map_err(|e| format!("Tuş vuruşu gönderilemedi: {}", e))?;
+            }
+            _ => pending.push(c),
+        }
+
+        // Hız sınırı varsa her karakteri ayrı gönder — birleşik işaretleri (ör. I + U+0307 = İ)
+        // taban harfinden ayırma
+        if let Some(delay) = delay {
+            if chars.peek().is_some_and(|&next| crate::history::is_combining_mark(next)) {
+                continue;
+            }
+            flush(&mut enigo, &mut pending)?;
+            std::thread::sleep(delay);
+        }
+    }
+    flush(&mut enigo, &mut pending)
+}
+
+/// `count` kez Backspace gönder
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn erase_with_enigo(count: usize) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Klavye simülasyonu başlatılamadı: {}", e))?;
+    for _ in 0..count {
+        enigo
+            .key(Key::Backspace, Direction::Click)
+            .map_err(|e| format!("Backspace gönderilemedi: {}", e))?;
+        // Uygulama olay kuyruğunu yetiştirebilsin
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    Ok(())
+}
+
+/// Ok tuşlarıyla imleci taşı (negatif = sola)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn move_cursor_with_enigo(offset: isize) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Klavye simülasyonu başlatılamadı: {}", e))?;
+    let key = if offset < 0 { Key::LeftArrow } else { Key::RightArrow };
+    for _ in 0..offset.unsigned_abs() {
+        enigo
+            .key(key, Direction::Click)
+            .map_err(|e| format!("Ok tuşu gönderilemedi: {}", e))?;
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    Ok(())
+}
+
+/// Komut PATH'te var mı
+#[cfg(target_os = "linux")]
+pub(crate) fn which(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+        .unwrap_or(false)
+}
+
+/// Komutu çalıştır, başarılıysa stdout'u döndür
+#[cfg(target_os = "linux")]
+pub(crate) fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Metni komutun stdin'ine yaz
+#[cfg(target_os = "linux")]
+fn pipe_to(cmd: &str, args: &[&str], input: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{} çalıştırılamadı: {}", cmd, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| format!("{} stdin yazılamadı: {}", cmd, e))?;
+    }
+    let status = child
+        .wait()
+        .map_err(|e| format!("{} beklenemedi: {}", cmd, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} başarısız oldu", cmd))
+    }
+}
+
+/// X sunucusu gerektiren testler — `xvfb-run -a cargo test x11 -- --ignored` (xclip/xsel ve xterm gerekir)
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn x11_sink() -> Option<X11Sink> {
+        if std::env::var_os("DISPLAY").is_none() || !which("xterm") {
+            println!("⏭️  DISPLAY ya da xterm yok — X11 testi atlandı");
+            return None;
+        }
+        Some(X11Sink::new().expect("xclip veya xsel gerekli"))
+    }
+
+    /// Sahte panoda seçimi oku: `copied` Ctrl+C'nin sonucu (Ok(Some) seçim panoya gelir).
+    /// Dönüş: okunan seçim ve sonunda panoda kalan
+    fn fake_copy(
+        old: Option<&str>,
+        copied: Result<Option<&str>, &str>,
+    ) -> (Result<String, String>, Option<String>) {
+        let clipboard = std::cell::RefCell::new(old.map(str::to_string));
+        let result = copy_selection(
+            || clipboard.borrow().clone(),
+            |text| {
+                *clipboard.borrow_mut() = text.map(str::to_string);
+                Ok(())
+            },
+            || {
+                if let Some(text) = copied? {
+                    *clipboard.borrow_mut() = Some(text.into());
+                }
+                Ok(())
+            },
+        );
+        (result, clipboard.into_inner())
+    }
+
+    #[test]
+    fn copied_selection_restores_clipboard() {
+        assert_eq!(
+            fake_copy(Some("eski"), Ok(Some("seçili metin"))),
+            (Ok("seçili metin".into()), Some("eski".into()))
+        );
+        // Seçim panodakiyle aynı olsa da bulunur
+        assert_eq!(
+            fake_copy(Some("aynı"), Ok(Some("aynı"))),
+            (Ok("aynı".into()), Some("aynı".into()))
+        );
+        assert_eq!(fake_copy(None, Ok(Some("x"))), (Ok("x".into()), None));
+    }
+
+    #[test]
+    fn no_selection_reads_empty() {
+        assert_eq!(
+            fake_copy(Some("eski"), Ok(None)),
+            (Ok(String::new()), Some("eski".into()))
+        );
+        assert_eq!(
+            fake_copy(Some("eski"), Err("Ctrl+C gönderilemedi")),
+            (Err("Ctrl+C gönderilemedi".into()), Some("eski".into()))
+        );
+    }
+
+    #[test]
+    #[ignore = "X sunucusu gerekir (xvfb-run)"]
+    fn x11_paste_restores_clipboard() {
+        let Some(sink) = x11_sink() else { return };
+        sink.set_clipboard("eski pano").unwrap();
+        sink.paste("yapıştırılan metin").unwrap();
+        assert_eq!(sink.read_clipboard().as_deref(), Some("eski pano"));
+    }
+
+    #[test]
+    #[ignore = "X sunucusu gerekir (xvfb-run)"]
+    fn x11_typed_text_reaches_focused_window() {
+        use enigo::{Coordinate, Enigo, Mouse, Settings};
+
+        let Some(sink) = x11_sink() else { return };
+        let out = std::env::temp_dir().join(format!("millow-x11-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&out);
+        let mut term = Command::new("xterm")
+            .args(["-geometry", "80x5+0+0", "-e", "sh", "-c"])
+            .arg(format!("head -n 1 > '{}'", out.display()))
+            .spawn()
+            .expect("xterm başlatılamadı");
+        std::thread::sleep(Duration::from_millis(1500));
+
+        // Pencere yöneticisi yok — klavye odağı imlecin altındaki penceredir
+        Enigo::new(&Settings::default())
+            .unwrap()
+            .move_mouse(40, 40, Coordinate::Abs)
+            .unwrap();
+        sink.type_text("millow x11 testi\n", 0.0).unwrap();
+
+        // Satır tamamlanınca head çıkar, xterm kapanır
+        let started = Instant::now();
+        while term.try_wait().unwrap().is_none() && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        let _ = term.kill();
+        let typed = std::fs::read_to_string(&out).unwrap_or_default();
+        let _ = std::fs::remove_file(&out);
+        assert_eq!(typed.trim_end(), "millow x11 testi");
+    }
+}
@@ -0,0 +1,404 @@
+// Millow — Akıllı Boşluk ve Büyük Harf
+// İmlecin çevresindeki metne göre baştaki boşluğu, ilk harfi ve sondaki noktalamayı düzeltir
+
+/// Cümle bitiren noktalama
+const SENTENCE_END: &[char] = &['.', '!', '?', '…'];
+/// Önüne boşluk konmayan karakterler
+const CLOSING: &[char] = &[
+    ',', '.', ';', ':', '!', '?', '…', ')', ']', '}', '”', '’', '»', '%',
+];
+/// Arkasına boşluk konmayan karakterler
+const OPENING: &[char] = &['(', '[', '{', '“', '‘', '«', '/', '@', '#', '-'];
+/// Whisper'ın her segmentte büyük yazdığı sıradan cümle başı kelimeleri —
+/// cümle ortasında yalnızca bunlar küçültülür, özel adlar ("Ankara'ya", "Mehmet") korunur
+const COMMON_STARTERS: &[&str] = &[
+    "ama", "ancak", "bana", "ben", "bence", "bir", "biz", "bu", "bunu", "çok", "da", "daha", "de",
+    "evet", "fakat", "hayır", "her", "hem", "ile", "ise", "işte", "iyi", "ki", "mesela", "ne",
+    "neden", "o", "onu", "sen", "sonra", "şimdi", "şu", "tabii", "tamam", "ve", "veya", "ya", "a",
+    "also", "and", "but", "he", "it", "maybe", "or", "she", "so", "that", "the", "then", "there",
+    "they", "this", "we", "yes", "you",
+];
+
+/// İmlecin çevresindeki metin
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CursorContext {
+    /// İmleçten (seçimden) önceki metin
+    pub before: String,
+    /// İmleçten sonraki metin — yalnızca erişilebilirlik API'sinden okunabildiyse
+    pub after: Option<String>,
+}
+
+impl CursorContext {
+    /// Yalnızca önceki metin biliniyor (Millow'un kendi eklemelerinden)
+    pub fn before_only(before: &str) -> Self {
+        Self {
+            before: before.to_string(),
+            after: None,
+        }
+    }
+}
+
+/// Odaktaki metin alanından imlecin çevresini oku (macOS Erişilebilirlik API'si)
+pub fn read_focused() -> Option<CursorContext> {
+    platform::read_focused()
+}
+
+/// Metni imlecin çevresine uydur: gerekirse başa boşluk ekle, cümle ortasında
+/// ilk harfi küçült / cümle başında büyüt, sonraki metne göre sondaki noktayı at
+pub fn adjust(text: &str, context: &CursorContext, turkish: bool) -> String {
+    let body = text.trim_start_matches([' ', '\t']);
+    let Some(first) = body.chars().next() else {
+        return text.to_string();
+    };
+    if first == '\n' || first == '\r' {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(body.len() + 2);
+    if needs_leading_space(&context.before, first) {
+        out.push(' ');
+    }
+    out.push_str(&recase(body, is_sentence_start(&context.before), turkish));
+    if let Some(after) = &context.after {
+        fit_trailing(&mut out, after);
+    }
+    out
+}
+
+/// Önceki metin boşsa, satır sonuyla veya cümle sonu noktalamasıyla bitiyorsa cümle başıdır
+fn is_sentence_start(before: &str) -> bool {
+    if before.ends_with('\n') {
+        return true;
+    }
+    let trimmed =
+        before.trim_end_matches([' ', '\t', '"', '\'', '”', '’', '“', '‘', '(', ')', '«', '»']);
+    trimmed.is_empty() || trimmed.ends_with('\n') || trimmed.ends_with(SENTENCE_END)
+}
+
+fn needs_leading_space(before: &str, first: char) -> bool {
+    let mut rev = before.chars().rev();
+    let Some(prev) = rev.next() else {
+        return false;
+    };
+    if prev.is_whitespace() || OPENING.contains(&prev) || CLOSING.contains(&first) {
+        return false;
+    }
+    // Düz tırnak: boşluktan veya açılış işaretinden sonra geliyorsa açılış tırnağıdır
+    if prev == '"' || prev == '\'' {
+        return rev
+            .next()
+            .is_some_and(|c| !c.is_whitespace() && !OPENING.contains(&c));
+    }
+    true
+}
+
+/// Cümle başında ilk harfi büyüt. Cümle ortasında yalnızca büyük harf Whisper'ın
+/// cümle başı alışkanlığıysa küçült: segment cümle sonu noktalamasıyla biter ve sıradan
+/// bir kelimeyle başlar. Kısaltmalar ("NASA"), "I" ve özel adlar olduğu gibi kalır.
+fn recase(body: &str, sentence_start: bool, turkish: bool) -> String {
+    let mut chars = body.chars();
+    let Some(first) = chars.next() else {
+        return String::new();
+    };
+    let rest = chars.as_str();
+
+    if sentence_start {
+        if !first.is_lowercase() {
+            return body.to_string();
+        }
+        let upper: String = match first {
+            'i' if turkish => "İ".into(),
+            c => c.to_uppercase().collect(),
+        };
+        return upper + rest;
+    }
+
+    let acronym = rest.chars().next().is_some_and(char::is_uppercase);
+    if !first.is_uppercase() || first == 'I' || acronym || !is_capitalised_sentence(body) {
+        return body.to_string();
+    }
+    lowercase_first(first) + rest
+}
+
+fn lowercase_first(first: char) -> String {
+    match first {
+        'İ' => "i".into(),
+        c => c.to_lowercase().collect(),
+    }
+}
+
+/// Segment Whisper'ın büyük harfle başlattığı tam bir cümle mi: cümle sonu noktalamasıyla
+/// biter ve ilk kelime ek almamış sıradan bir kelimedir
+fn is_capitalised_sentence(body: &str) -> bool {
+    let ends_sentence = body
+        .trim_end()
+        .trim_end_matches(['"', '\'', '”', '’', ')', '»'])
+        .ends_with(SENTENCE_END);
+    let mut chars = body.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let rest = chars.as_str();
+    let word_len = rest
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(rest.len());
+    let suffixed = rest[word_len..].starts_with(['\'', '’']);
+    let word = lowercase_first(first) + &rest[..word_len];
+    ends_sentence && !suffixed && COMMON_STARTERS.contains(&word.as_str())
+}
+
+/// Sonraki metne göre sonu düzelt: noktalama veya küçük harfle devam ediyorsa
+/// sondaki noktayı at, bir kelimeyle devam ediyorsa araya boşluk koy
+fn fit_trailing(out: &mut String, after: &str) {
+    let Some(next) = after.chars().next() else {
+        return;
+    };
+    if next.is_whitespace() {
+        if next == ' ' {
+            let len = out.trim_end_matches(' ').len();
+            out.truncate(len);
+        }
+        return;
+    }
+    if out.ends_with(char::is_whitespace) && !out.ends_with(' ') {
+        return;
+    }
+
+    let continues_sentence = CLOSING.contains(&next) || next.is_lowercase();
+    let len = out.trim_end_matches(' ').len();
+    out.truncate(len);
+    if continues_sentence && out.ends_with('.') && !out.ends_with("..") {
+        out.pop();
+    }
+    if next.is_alphanumeric() {
+        out.push(' ');
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::CursorContext;
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+
+    /// kAXValueCFRangeType
+    const AX_VALUE_CF_RANGE_TYPE: u32 = 4;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CFRange {
+        location: isize,
+        length: isize,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> CFTypeRef;
+        fn AXUIElementCopyAttributeValue(
+            element: CFTypeRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> u8;
+    }
+
+    fn copy_attribute(element: &CFType, name: &str) -> Option<CFType> {
+        let attribute = CFString::new(name);
+        let mut value: CFTypeRef = std::ptr::null();
+        let err = unsafe {
+            AXUIElementCopyAttributeValue(
+                element.as_CFTypeRef(),
+                attribute.as_concrete_TypeRef(),
+                &mut value,
+            )
+        };
+        (err == 0 && !value.is_null()).then(|| unsafe { CFType::wrap_under_create_rule(value) })
+    }
+
+    /// Odaktaki öğenin değeri ve seçim aralığı — şifre alanları ve
+    /// erişilebilirliği desteklemeyen uygulamalar (bazı Electron uygulamaları) None döner
+    pub fn read_focused() -> Option<CursorContext> {
+        let system = unsafe { CFType::wrap_under_create_rule(AXUIElementCreateSystemWide()) };
+        let focused = copy_attribute(&system, "AXFocusedUIElement")?;
+        let value = copy_attribute(&focused, "AXValue")?
+            .downcast::<CFString>()?
+            .to_string();
+        let range = copy_attribute(&focused, "AXSelectedTextRange")?;
+
+        let mut selection = CFRange::default();
+        let ok = unsafe {
+            AXValueGetValue(
+                range.as_CFTypeRef(),
+                AX_VALUE_CF_RANGE_TYPE,
+                &mut selection as *mut CFRange as *mut c_void,
+            )
+        };
+        if ok == 0 || selection.location < 0 {
+            return None;
+        }
+
+        // Aralık UTF-16 birimleriyle verilir
+        let units: Vec<u16> = value.encode_utf16().collect();
+        let start = (selection.location as usize).min(units.len());
+        let end = (start + selection.length.max(0) as usize).min(units.len());
+        Some(CursorContext {
+            before: String::from_utf16_lossy(&units[..start]),
+            after: Some(String::from_utf16_lossy(&units[end..])),
+        })
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use super::CursorContext;
+
+    /// AT-SPI desteği yok — yalnızca Millow'un kendi eklemeleri kullanılır
+    pub fn read_focused() -> Option<CursorContext> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn before(text: &str) -> CursorContext {
+        CursorContext::before_only(text)
+    }
+
+    fn around(before: &str, after: &str) -> CursorContext {
+        CursorContext {
+            before: before.into(),
+            after: Some(after.into()),
+        }
+    }
+
+    #[test]
+    fn adjusts_to_context() {
+        let cases = [
+            // Baştaki boşluk
+            (
+                "Bugün hava güzel.",
+                before("Merhaba dünya."),
+                " Bugün hava güzel.",
+            ),
+            ("merhaba", before(""), "Merhaba"),
+            ("  tamam", before("Dedi ki "), "tamam"),
+            (", ve", before("elma"), ", ve"),
+            ("Merhaba", before("(\""), "Merhaba"),
+            ("\nYeni satır", before("son"), "\nYeni satır"),
+            // Tırnaklar
+            ("Tamam.", before("Dedi ki \"Evet.\""), " Tamam."),
+            ("evet", before("Bitti. \""), "Evet"),
+            // Türkçe i → İ
+            (" istanbul güzel.", before(""), "İstanbul güzel."),
+            ("istanbul", before("Bitti.\n"), "İstanbul"),
+            // Cümle ortası: yalnızca sıradan cümle başları küçülür
+            (
+                "Ve sonra gittik.",
+                before("Eve geldik"),
+                " ve sonra gittik.",
+            ),
+            ("İşte bu.", before("Bak,"), " işte bu."),
+            ("The end.", before("And"), " the end."),
+            ("Ankara'ya gittik.", before("Dün"), " Ankara'ya gittik."),
+            ("Mehmet geldi.", before("Sonra"), " Mehmet geldi."),
+            ("Ve sonra", before("Eve geldik"), " Ve sonra"),
+            ("Bu'nu yaz.", before("Sonra"), " Bu'nu yaz."),
+            // Kısaltmalar ve "I"
+            ("NASA açıkladı", before("Dün"), " NASA açıkladı"),
+            ("I think so.", before("Well,"), " I think so."),
+            // Sondaki nokta
+            ("Bir de şu.", around("Kedi ", "köpek koştu."), "bir de şu "),
+            ("Bu.", around("Bak ", ", dedi."), "bu"),
+            ("Büyük.", around("Bu ", ", dedi."), "Büyük"),
+            ("Yeni cümle.", around("Bitti. ", "Sonra"), "Yeni cümle. "),
+            ("Bekle...", around("", "sonra"), "Bekle... "),
+            ("Tamam. ", around("", " devam"), "Tamam."),
+        ];
+        for (text, context, want) in cases {
+            assert_eq!(
+                adjust(text, &context, true),
+                want,
+                "{:?} {:?}",
+                text,
+                context
+            );
+        }
+    }
+
+    #[test]
+    fn dotted_i_only_in_turkish() {
+        assert_eq!(adjust("istanbul", &before(""), false), "Istanbul");
+        assert_eq!(adjust("istanbul", &before(""), true), "İstanbul");
+    }
+
+    #[test]
+    fn detects_sentence_start() {
+        let cases = [
+            ("", true),
+            ("   ", true),
+            ("Bitti.", true),
+            ("Gerçekten mi? ", true),
+            ("Dedi ki \"Evet!\" ", true),
+            ("(Not.) ", true),
+            ("satır\n", true),
+            ("satır\n  ", true),
+            ("Eve geldik", false),
+            ("Elma, ", false),
+            ("Dedi ki: ", false),
+        ];
+        for (text, want) in cases {
+            assert_eq!(is_sentence_start(text), want, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn decides_leading_space() {
+        let cases = [
+            ("", 'a', false),
+            ("kelime", 'a', true),
+            ("kelime ", 'a', false),
+            ("satır\n", 'a', false),
+            ("kelime", ',', false),
+            ("kelime", ')', false),
+            ("(", 'a', false),
+            ("#", 'a', false),
+            ("Dedi ki \"", 'a', false),
+            ("\"Evet\"", 'a', true),
+            ("'", 'a', false),
+            ("bitti.", 'a', true),
+        ];
+        for (before, first, want) in cases {
+            assert_eq!(
+                needs_leading_space(before, first),
+                want,
+                "{:?} {:?}",
+                before,
+                first
+            );
+        }
+    }
+
+    #[test]
+    fn fits_trailing_text() {
+        let cases = [
+            ("bitti.", "", "bitti."),
+            ("bitti.", ", dedi", "bitti"),
+            ("bitti.", "sonra", "bitti "),
+            ("bitti.", "Sonra", "bitti. "),
+            ("bitti...", "sonra", "bitti... "),
+            ("bitti. ", " sonra", "bitti."),
+            ("bitti.\n", "Sonra", "bitti.\n"),
+            ("bitti", "\n", "bitti"),
+            ("bitti.", "(not)", "bitti."),
+            ("yüzde 5", "%", "yüzde 5"),
+        ];
+        for (out, after, want) in cases {
+            let mut out = out.to_string();
+            fit_trailing(&mut out, after);
+            assert_eq!(out, want, "{:?}", after);
+        }
+    }
+}
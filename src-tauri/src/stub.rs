@@ -0,0 +1,86 @@
+// Millow — Test Sağlayıcısı
+// Groq/Gemini/LLM uçlarını taklit eden yerel HTTP sunucusu; yalnızca testlerde derlenir
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// Gelen her isteğe sıradaki hazır yanıtı veren sunucu
+pub struct StubServer {
+    /// "http://127.0.0.1:<port>"
+    pub url: String,
+    requests: Receiver<String>,
+}
+
+impl StubServer {
+    /// Yanıtlar (durum kodu, JSON gövde) sırayla verilir; biterse sonuncusu tekrarlanır
+    pub fn start(responses: Vec<(u16, String)>) -> Self {
+        assert!(!responses.is_empty(), "en az bir yanıt gerekli");
+        let listener = TcpListener::bind("127.0.0.1:0").expect("yerel port açılamadı");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, requests) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (n, stream) in listener.incoming().enumerate() {
+                let Ok(mut stream) = stream else { break };
+                // Yanıttan önce kaydet — istemci döndüğünde istek görünür olsun
+                if tx.send(read_request(&mut stream)).is_err() {
+                    break;
+                }
+                let (status, body) = &responses[n.min(responses.len() - 1)];
+                let reply = format!(
+                    "HTTP/1.1 {} STUB\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(reply.as_bytes());
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// Tek yanıtlı sunucu
+    pub fn reply(status: u16, body: &str) -> Self {
+        Self::start(vec![(status, body.to_string())])
+    }
+
+    /// Şimdiye kadar gelen istekler (istek satırı, başlıklar ve gövde)
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.try_iter().collect()
+    }
+
+    /// Sıradaki istek — 5 sn içinde gelmezse None
+    pub fn next_request(&self) -> Option<String> {
+        self.requests.recv_timeout(Duration::from_secs(5)).ok()
+    }
+}
+
+/// Başlıkları ve Content-Length kadar gövdeyi oku
+fn read_request(stream: &mut TcpStream) -> String {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let mut data = Vec::new();
+    let mut buf = [0u8; 16 * 1024];
+    loop {
+        let n = match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        data.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&data);
+        if let Some(head_end) = text.find("\r\n\r\n") {
+            let length = text[..head_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if data.len() >= head_end + 4 + length {
+                break;
+            }
+        }
+    }
+    String::from_utf8_lossy(&data).into_owned()
+}
@@ -0,0 +1,867 @@
+// Millow — Zamanlayıcılar
+// Etiketli zamanlayıcı ve hatırlatıcıları ~/.millow/timers.json'da tutar; süresi dolanı bildirir, listeler, iptal eder, erteler
+
+use crate::commander::{CommandHandler, CommandInfo, Executor, Invocation, Params, SlotRule};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Süresi dolan zamanlayıcılar için kontrol aralığı
+const TICK: Duration = Duration::from_secs(1);
+
+/// Bundan daha geç çalan zamanlayıcı için kurulduğu saat de yazılır (Millow kapalıyken dolmuş)
+const LATE_AFTER_SECS: u64 = 60;
+
+/// Kurulu zamanlayıcı ya da hatırlatıcı
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Timer {
+    pub id: u64,
+    /// Boşsa etiketsiz zamanlayıcı
+    #[serde(default)]
+    pub label: String,
+    /// Dolacağı an (Unix saniye)
+    pub due: u64,
+    /// Hatırlatıcı mı ("toplantıyı hatırlat"), zamanlayıcı mı
+    #[serde(default)]
+    pub reminder: bool,
+}
+
+impl Timer {
+    fn title(&self) -> &'static str {
+        if self.reminder {
+            "⏰ Hatırlatıcı"
+        } else {
+            "⏰ Zamanlayıcı"
+        }
+    }
+
+    fn name(&self) -> String {
+        if self.label.is_empty() {
+            format!("#{}", self.id)
+        } else {
+            format!("'{}'", self.label)
+        }
+    }
+}
+
+/// Zamanlayıcı işlemi — yürütücü tarafından ortak kayıtta uygulanır
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TimerOp {
+    Add {
+        label: String,
+        due: u64,
+        reminder: bool,
+    },
+    List,
+    /// "son", "tümü", kimlik ya da etiket parçası
+    Cancel {
+        query: String,
+    },
+    /// En son dolan zamanlayıcıyı yeniden kur
+    Snooze {
+        secs: u64,
+    },
+}
+
+impl fmt::Display for TimerOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add { label, due, .. } => {
+                write!(f, "zamanlayıcı kur '{}' → {}", label, clock(*due))
+            }
+            Self::List => write!(f, "zamanlayıcıları listele"),
+            Self::Cancel { query } => write!(f, "zamanlayıcı iptal '{}'", query),
+            Self::Snooze { secs } => write!(f, "ertele {}", format_duration(*secs)),
+        }
+    }
+}
+
+// ── Kayıt ──
+
+/// Diskte saklanan zamanlayıcılar
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TimerStore {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    timers: Vec<Timer>,
+    /// Ertelenebilecek en son dolan zamanlayıcı
+    #[serde(default)]
+    last_fired: Option<Timer>,
+}
+
+impl TimerStore {
+    /// Dosyadan oku — yoksa ya da bozuksa boş kayıt
+    pub fn load(path: PathBuf) -> Self {
+        let mut store: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| match serde_json::from_str(&json) {
+                Ok(store) => Some(store),
+                Err(e) => {
+                    println!("⚠️  {} okunamadı: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        store.path = path;
+        store.next_id = store
+            .timers
+            .iter()
+            .map(|t| t.id + 1)
+            .max()
+            .unwrap_or(1)
+            .max(store.next_id);
+        store
+    }
+
+    fn save(&self) {
+        let result = (|| {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+            }
+            let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+            std::fs::write(&self.path, json)
+                .map_err(|e| format!("{} yazılamadı: {}", self.path.display(), e))
+        })();
+        if let Err(e) = result {
+            println!("⚠️  Zamanlayıcılar kaydedilemedi: {}", e);
+        }
+    }
+
+    pub fn add(&mut self, label: &str, due: u64, reminder: bool) -> Timer {
+        let timer = Timer {
+            id: self.next_id,
+            label: label.trim().to_string(),
+            due,
+            reminder,
+        };
+        self.next_id += 1;
+        self.timers.push(timer.clone());
+        self.timers.sort_by_key(|t| t.due);
+        self.save();
+        timer
+    }
+
+    /// Dolacak sırayla kurulu zamanlayıcılar
+    pub fn list(&self) -> &[Timer] {
+        &self.timers
+    }
+
+    /// Sorguya uyan zamanlayıcıları kaldır
+    pub fn cancel(&mut self, query: &str) -> Result<Vec<Timer>, String> {
+        if self.timers.is_empty() {
+            return Err("Kurulu zamanlayıcı yok".into());
+        }
+        let query = crate::history::normalize(query);
+        let ids: Vec<u64> = match query.as_str() {
+            "tüm" | "tümü" | "tümünü" | "hepsi" | "hepsini" | "all" => {
+                self.timers.iter().map(|t| t.id).collect()
+            }
+            // En son kurulan
+            "" | "son" | "last" => self.timers.iter().map(|t| t.id).max().into_iter().collect(),
+            _ => match query.parse::<u64>() {
+                Ok(id) => vec![id],
+                Err(_) => self
+                    .timers
+                    .iter()
+                    .filter(|t| crate::history::normalize(&t.label).contains(&query))
+                    .map(|t| t.id)
+                    .collect(),
+            },
+        };
+        let (cancelled, kept) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|t| ids.contains(&t.id));
+        self.timers = kept;
+        if cancelled.is_empty() {
+            return Err(format!("'{}' ile eşleşen zamanlayıcı yok", query));
+        }
+        self.save();
+        Ok(cancelled)
+    }
+
+    /// En son dolan zamanlayıcıyı `secs` sonra yeniden kur
+    pub fn snooze(&mut self, secs: u64, now: u64) -> Result<Timer, String> {
+        let fired = self
+            .last_fired
+            .take()
+            .ok_or("Ertelenecek zamanlayıcı yok")?;
+        Ok(self.add(&fired.label, now + secs, fired.reminder))
+    }
+
+    /// Süresi dolanları çıkar; sonuncusu ertelenebilir olarak saklanır
+    pub fn take_due(&mut self, now: u64) -> Vec<Timer> {
+        if !self.timers.iter().any(|t| t.due <= now) {
+            return Vec::new();
+        }
+        let (due, pending): (Vec<Timer>, Vec<Timer>) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|t| t.due <= now);
+        self.timers = pending;
+        self.last_fired = due.last().cloned();
+        self.save();
+        due
+    }
+}
+
+fn store_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+    PathBuf::from(home).join(".millow").join("timers.json")
+}
+
+/// Uygulama genelindeki kayıt
+fn store() -> &'static Mutex<TimerStore> {
+    static STORE: OnceLock<Mutex<TimerStore>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(TimerStore::load(store_path())))
+}
+
+/// İşlemi ortak kayıtta uygula ve sonucu anlat
+pub fn apply(op: &TimerOp) -> Result<String, String> {
+    let mut store = store().lock();
+    match op {
+        TimerOp::Add {
+            label,
+            due,
+            reminder,
+        } => {
+            let timer = store.add(label, *due, *reminder);
+            Ok(describe(&timer, now()))
+        }
+        TimerOp::List => {
+            let now = now();
+            let timers = store.list();
+            if timers.is_empty() {
+                return Ok("Kurulu zamanlayıcı yok".into());
+            }
+            let lines: Vec<String> = timers.iter().map(|t| describe(t, now)).collect();
+            Ok(format!(
+                "{} zamanlayıcı: {}",
+                timers.len(),
+                lines.join("; ")
+            ))
+        }
+        TimerOp::Cancel { query } => {
+            let cancelled = store.cancel(query)?;
+            let names: Vec<String> = cancelled.iter().map(Timer::name).collect();
+            Ok(format!("İptal edildi: {}", names.join(", ")))
+        }
+        TimerOp::Snooze { secs } => {
+            let timer = store.snooze(*secs, now())?;
+            Ok(format!("Ertelendi: {}", describe(&timer, now())))
+        }
+    }
+}
+
+/// Süresi dolanları izleyip bildiren arka plan döngüsü — Millow kapalıyken dolanlar açılışta bildirilir
+pub fn start() {
+    let pending = store().lock().list().len();
+    if pending > 0 {
+        println!("⏰ {} zamanlayıcı yüklendi", pending);
+    }
+    std::thread::spawn(|| loop {
+        let now = now();
+        let due = store().lock().take_due(now);
+        for timer in due {
+            fire(&timer, now);
+        }
+        std::thread::sleep(TICK);
+    });
+}
+
+fn fire(timer: &Timer, now: u64) {
+    let mut message = if timer.label.is_empty() {
+        "Süre doldu!".to_string()
+    } else {
+        timer.label.clone()
+    };
+    if now.saturating_sub(timer.due) > LATE_AFTER_SECS {
+        message.push_str(&format!(" ({} için kurulmuştu)", clock(timer.due)));
+    }
+    message.push_str(" — ertelemek için \"ertele\" deyin");
+    println!("{} {}", timer.title(), message);
+    crate::notify(timer.title(), &message);
+    #[cfg(target_os = "macos")]
+    let _ = std::process::Command::new("afplay")
+        .arg("/System/Library/Sounds/Glass.aiff")
+        .spawn();
+}
+
+/// "'çay' → 15:30 (12 dk sonra)"
+fn describe(timer: &Timer, now: u64) -> String {
+    format!(
+        "{} → {} ({} sonra)",
+        timer.name(),
+        clock(timer.due),
+        format_duration(timer.due.saturating_sub(now))
+    )
+}
+
+// ── Komutlar ──
+
+/// Zamanlayıcı komutu türü
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimerCommand {
+    Set,
+    Remind,
+    List,
+    Cancel,
+    Snooze,
+}
+
+/// Zamanlayıcı kayıtlarını yöneten sesli komut
+pub struct TimerHandler {
+    info: CommandInfo,
+    command: TimerCommand,
+}
+
+impl TimerHandler {
+    fn new(name: &str, description: &str, triggers: &[&str], command: TimerCommand) -> Self {
+        Self {
+            info: CommandInfo {
+                name: name.into(),
+                description: description.into(),
+                triggers: triggers.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            },
+            command,
+        }
+    }
+
+    fn slot(mut self, name: &str, default: Option<&str>) -> Self {
+        let rule = SlotRule {
+            default: default.map(str::to_string),
+            ..Default::default()
+        };
+        self.info.params.insert(name.into(), rule);
+        self
+    }
+}
+
+impl CommandHandler for TimerHandler {
+    fn info(&self) -> &CommandInfo {
+        &self.info
+    }
+
+    fn execute(&self, params: &Params, exec: &dyn Executor) -> Result<String, String> {
+        let param = |name: &str| params.get(name).map(String::as_str).unwrap_or_default();
+        let now = now();
+        let op = match self.command {
+            TimerCommand::Set | TimerCommand::Remind => {
+                let (when, label) = if self.command == TimerCommand::Remind {
+                    split_reminder(param("when"), param("label"))
+                } else {
+                    (param("when").to_string(), param("label").to_string())
+                };
+                let due = parse_when(&when, now, utc_offset())
+                    .ok_or_else(|| format!("Süre anlaşılamadı: {}", when))?;
+                TimerOp::Add {
+                    label,
+                    due,
+                    reminder: self.command == TimerCommand::Remind,
+                }
+            }
+            TimerCommand::List => TimerOp::List,
+            TimerCommand::Cancel => TimerOp::Cancel {
+                query: param("timer").to_string(),
+            },
+            TimerCommand::Snooze => {
+                let duration = param("duration");
+                TimerOp::Snooze {
+                    secs: parse_duration(duration)
+                        .ok_or_else(|| format!("Süre anlaşılamadı: {}", duration))?,
+                }
+            }
+        };
+        let message = exec.execute(&Invocation::Timer { op: op.clone() })?;
+        // Deneme modunda kayıt değişmez, yürütücü boş döner
+        Ok(if message.is_empty() {
+            op.to_string()
+        } else {
+            message
+        })
+    }
+}
+
+/// "{when} {label} hatırlat" eşleşmesinde zaman ilk kelimede kesilir ("1 saat sonra su iç" → "1") —
+/// zamanı, anlaşılabilen en uzun baş kısım olarak yeniden ayır
+fn split_reminder(when: &str, label: &str) -> (String, String) {
+    let words: Vec<&str> = when
+        .split_whitespace()
+        .chain(label.split_whitespace())
+        .collect();
+    let longest = if label.trim().is_empty() {
+        words.len()
+    } else {
+        words.len() - 1
+    };
+    (1..=longest)
+        .rev()
+        .find(|&n| parse_when(&words[..n].join(" "), 0, 0).is_some())
+        .map(|n| (words[..n].join(" "), words[n..].join(" ")))
+        .unwrap_or_else(|| (when.to_string(), label.to_string()))
+}
+
+/// Zamanlayıcı ve hatırlatıcı komutları
+pub fn handlers() -> Vec<TimerHandler> {
+    vec![
+        TimerHandler::new(
+            "set_timer",
+            "Zamanlayıcı kur (\"1 saat 20 dakika\", \"15:30\")",
+            &[
+                "{when} zamanlayıcı kur",
+                "{when} zamanlayıcı",
+                "{label} için {when} zamanlayıcı kur",
+                "set a timer for {when}",
+                "set a {label} timer for {when}",
+                "set a {when} timer",
+            ],
+            TimerCommand::Set,
+        )
+        .slot("when", Some("5 dakika"))
+        .slot("label", Some("Zamanlayıcı")),
+        TimerHandler::new(
+            "set_reminder",
+            "Belirli bir sürede ya da saatte hatırlat",
+            &[
+                "{when} bana {label} hatırlat",
+                "{when} {label} hatırlat",
+                "remind me to {label} in {when}",
+                "remind me to {label} at {when}",
+                "remind me in {when} to {label}",
+                "remind me at {when} to {label}",
+            ],
+            TimerCommand::Remind,
+        )
+        .slot("when", None)
+        .slot("label", None),
+        TimerHandler::new(
+            "list_timers",
+            "Kurulu zamanlayıcıları listele",
+            &[
+                "zamanlayıcıları listele",
+                "zamanlayıcılar",
+                "hatırlatıcıları listele",
+                "list timers",
+                "list reminders",
+            ],
+            TimerCommand::List,
+        ),
+        TimerHandler::new(
+            "cancel_timer",
+            "Zamanlayıcıyı iptal et (etiket, numara, \"son\" ya da \"tümü\")",
+            &[
+                "zamanlayıcıyı iptal et",
+                "{timer} zamanlayıcısını iptal et",
+                "{timer} hatırlatıcısını iptal et",
+                "{timer} zamanlayıcıları iptal et",
+                "cancel the timer",
+                "cancel timer",
+                "cancel {timer} timer",
+                "cancel {timer} timers",
+                "cancel {timer} reminder",
+            ],
+            TimerCommand::Cancel,
+        )
+        .slot("timer", Some("son")),
+        TimerHandler::new(
+            "snooze_timer",
+            "Son dolan zamanlayıcıyı ertele",
+            &[
+                "ertele",
+                "{duration} ertele",
+                "snooze",
+                "snooze {duration}",
+                "snooze for {duration}",
+            ],
+            TimerCommand::Snooze,
+        )
+        .slot("duration", Some("5 dakika")),
+    ]
+}
+
+// ── Süre ve Saat ──
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Yerel saat farkı (saniye) — `date +%z` yoksa UTC
+pub(crate) fn utc_offset() -> i64 {
+    std::process::Command::new("date")
+        .arg("+%z")
+        .output()
+        .ok()
+        .and_then(|out| parse_offset(String::from_utf8_lossy(&out.stdout).trim()))
+        .unwrap_or(0)
+}
+
+/// "+0300" → 10800
+fn parse_offset(text: &str) -> Option<i64> {
+    let sign = match text.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let hours: i64 = text.get(1..3)?.parse().ok()?;
+    let minutes: i64 = text.get(3..5)?.parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Yerel "HH:MM"
+fn clock(unix: u64) -> String {
+    let local = (unix as i64 + utc_offset()).rem_euclid(86_400);
+    format!("{:02}:{:02}", local / 3600, (local / 60) % 60)
+}
+
+/// "1 sa 20 dk", "45 sn"
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    let mut parts = Vec::new();
+    if hours > 0 {
+        parts.push(format!("{} sa", hours));
+    }
+    if minutes > 0 {
+        parts.push(format!("{} dk", minutes));
+    }
+    if seconds > 0 && hours == 0 {
+        parts.push(format!("{} sn", seconds));
+    }
+    if parts.is_empty() {
+        "0 sn".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Süre ("1 saat 20 dakika", "90 saniye") ya da saat ("15:30", "saat 9'da", "5'te", "at 3 pm") → dolacağı an
+pub fn parse_when(text: &str, now: u64, offset: i64) -> Option<u64> {
+    match parse_clock(text) {
+        Some(minute_of_day) => {
+            let local_now = (now as i64 + offset).rem_euclid(86_400);
+            let mut wait = i64::from(minute_of_day) * 60 - local_now;
+            if wait <= 0 {
+                wait += 86_400;
+            }
+            Some(now + wait as u64)
+        }
+        None => parse_duration(text).map(|secs| now + secs),
+    }
+}
+
+/// Saat ifadesinde sayıların yanında geçebilen kelimeler ve ekler
+const CLOCK_WORDS: &[&str] = &["saat", "at", "pm", "am", "ös", "öö", "de", "da", "te", "ta"];
+
+/// Süre ifadesinde anlamı olmayan bağlaçlar ("1 saat ve 20 dakika", "10 dakika sonra")
+const DURATION_FILLERS: &[&str] = &["ve", "and", "sonra", "later", "in", "for"];
+
+/// Bulunma eki: "5'te", "9'da"
+fn is_locative(word: &str) -> bool {
+    matches!(word, "de" | "da" | "te" | "ta")
+}
+
+/// Metindeki günün saati (dakika) — "15:30", "15.30", "saat 9", "5'te", "at 3 pm", "3pm"
+fn parse_clock(text: &str) -> Option<u32> {
+    let tokens = tokenize(text);
+    let meridiem = |token: &str| match token {
+        "pm" | "ös" => Some(true),
+        "am" | "öö" => Some(false),
+        _ => None,
+    };
+    let is_number = |token: &str| !split_number(token).0.is_empty() || number_word(token).is_some();
+    // Saat ifadesinde yalnızca sayılar ve saat kelimeleri olabilir ("yarın 5'te" saat değildir)
+    if !tokens.iter().all(|token| {
+        let (digits, suffix) = split_number(token);
+        CLOCK_WORDS.contains(&suffix) || (!digits.is_empty() && suffix.is_empty())
+    }) {
+        return None;
+    }
+    for (i, token) in tokens.iter().enumerate() {
+        let (digits, suffix) = split_number(token);
+        let pm = meridiem(suffix).or_else(|| tokens.get(i + 1).and_then(|t| meridiem(t)));
+        // "5'te", "9'da" saattir
+        let locative = is_locative(suffix) || tokens.get(i + 1).is_some_and(|t| is_locative(t));
+        // "saat 9" saattir, "1 saat 20" süredir
+        let after_marker = i > 0
+            && matches!(tokens[i - 1].as_str(), "saat" | "at")
+            && (i < 2 || !is_number(&tokens[i - 2]));
+        let next_is_unit = tokens.get(i + 1).is_some_and(|t| unit_secs(t).is_some());
+        let marked = after_marker || pm.is_some() || locative;
+
+        let (hour, minute) = if let Some((h, m)) = digits.split_once([':', '.']) {
+            // "1.5 saat" süredir, "15.30" saattir
+            if m.len() != 2 || next_is_unit {
+                continue;
+            }
+            (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?)
+        } else if !digits.is_empty() && marked && !next_is_unit {
+            (digits.parse::<u32>().ok()?, 0)
+        } else {
+            continue;
+        };
+        let hour = match pm {
+            Some(true) if hour < 12 => hour + 12,
+            Some(false) if hour == 12 => 0,
+            _ => hour,
+        };
+        if hour < 24 && minute < 60 {
+            return Some(hour * 60 + minute);
+        }
+    }
+    None
+}
+
+/// Doğal süre → saniye: "1 saat 20 dakika", "1 saat 20", "bir buçuk saat", "yarım saat", "half an hour",
+/// "1h20m", "90 sn". Birimsiz sayı yalnızca bir birimden sonra gelirse bir alt birim sayılır;
+/// tanınmayan kelime varsa (yarın, pazartesi…) None
+pub fn parse_duration(text: &str) -> Option<u64> {
+    let tokens = tokenize(text);
+    let mut total = 0.0;
+    let mut amount: Option<f64> = None;
+    // Son söylenen birim (saniye) — "1 saat 20" → 20 dakika
+    let mut last_unit: Option<f64> = None;
+
+    for token in &tokens {
+        if let Some(unit) = unit_secs(token) {
+            total += amount.take().unwrap_or(1.0) * unit;
+            last_unit = Some(unit);
+            continue;
+        }
+        if matches!(token.as_str(), "buçuk" | "buçuğu") {
+            // "bir buçuk saat" → 1.5
+            if let Some(value) = amount.as_mut() {
+                *value += 0.5;
+            }
+            continue;
+        }
+        if matches!(token.as_str(), "a" | "an") {
+            // "an hour" → 1 saat, "half an hour" → yarım saat
+            amount = amount.or(Some(1.0));
+            continue;
+        }
+        if DURATION_FILLERS.contains(&token.as_str()) {
+            continue;
+        }
+        if let Some(value) = number_word(token) {
+            // "yirmi beş" → 25
+            amount = Some(amount.unwrap_or(0.0) + value);
+            continue;
+        }
+        // "1h20m", "10dk", "1.5saat" — sayıyla başlamayan ya da bilinmeyen birimli kelime anlaşılamaz
+        let mut rest = token.as_str();
+        while !rest.is_empty() {
+            let (digits, tail) = split_number(rest);
+            let value = digits.replace(',', ".").parse::<f64>().ok()?;
+            let unit_end = tail
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(tail.len());
+            if unit_end == 0 {
+                amount = Some(amount.unwrap_or(0.0) + value);
+                break;
+            }
+            let unit = unit_secs(&tail[..unit_end])?;
+            total += value * unit;
+            last_unit = Some(unit);
+            rest = &tail[unit_end..];
+        }
+    }
+    if let Some(value) = amount {
+        let unit = match last_unit? {
+            unit if unit >= 3600.0 => 60.0,
+            unit if unit >= 60.0 => 1.0,
+            _ => return None,
+        };
+        total += value * unit;
+    }
+    let secs = total.round() as u64;
+    (secs > 0).then_some(secs)
+}
+
+/// Küçük harf, noktalama atılmış kelimeler (":" ve "." sayı içinde korunur)
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '\'' || c == '’')
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Baştaki sayı kısmı ve kalanı: "15:30da" → ("15:30", "da")
+fn split_number(token: &str) -> (&str, &str) {
+    let end = token
+        .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.' || c == ','))
+        .unwrap_or(token.len());
+    (&token[..end], &token[end..])
+}
+
+/// Birim kelimesinin saniyesi ("dakikalık", "hours", "dk")
+fn unit_secs(word: &str) -> Option<f64> {
+    const HOUR: &[&str] = &["saat", "hour"];
+    const MINUTE: &[&str] = &["dakika", "minute", "min"];
+    const SECOND: &[&str] = &["saniye", "second", "sec"];
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| word.starts_with(p));
+    match word {
+        "h" | "hr" | "hrs" | "sa" => Some(3600.0),
+        "m" | "dk" | "dak" => Some(60.0),
+        "s" | "sn" => Some(1.0),
+        _ if starts(HOUR) => Some(3600.0),
+        _ if starts(MINUTE) => Some(60.0),
+        _ if starts(SECOND) => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Yazıyla sayı ("yirmi", "half", "bir")
+fn number_word(word: &str) -> Option<f64> {
+    let value = match word {
+        "yarım" | "half" => 0.5,
+        "çeyrek" | "quarter" => 0.25,
+        "bir" | "one" => 1.0,
+        "iki" | "two" => 2.0,
+        "üç" | "three" => 3.0,
+        "dört" | "four" => 4.0,
+        "beş" | "five" => 5.0,
+        "altı" | "six" => 6.0,
+        "yedi" | "seven" => 7.0,
+        "sekiz" | "eight" => 8.0,
+        "dokuz" | "nine" => 9.0,
+        "on" | "ten" => 10.0,
+        "fifteen" => 15.0,
+        "yirmi" | "twenty" => 20.0,
+        "otuz" | "thirty" => 30.0,
+        "kırk" | "forty" => 40.0,
+        "elli" | "fifty" => 50.0,
+        "altmış" | "sixty" => 60.0,
+        "doksan" | "ninety" => 90.0,
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-10-06 12:00 UTC
+    const NOON: u64 = 1_728_216_000;
+
+    fn due_in(text: &str) -> Option<u64> {
+        parse_when(text, NOON, 0).map(|due| due - NOON)
+    }
+
+    #[test]
+    fn parses_durations() {
+        let cases = [
+            ("5 dakika", 300),
+            ("1 saat 20 dakika", 4800),
+            ("1 saat ve 20 dakika", 4800),
+            ("1 saat 20", 4800),
+            ("2 dakika 30", 150),
+            ("bir buçuk saat", 5400),
+            ("yarım saat", 1800),
+            ("yirmi beş dakika", 1500),
+            ("half an hour", 1800),
+            ("an hour", 3600),
+            ("1h20m", 4800),
+            ("1.5saat", 5400),
+            ("90 sn", 90),
+            ("10 dakika sonra", 600),
+        ];
+        for (text, secs) in cases {
+            assert_eq!(parse_duration(text), Some(secs), "{}", text);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_words_and_bare_numbers() {
+        for text in [
+            "5",
+            "on",
+            "yarın",
+            "yarın 5",
+            "pazartesi 10 dakika",
+            "tomorrow",
+            "5 dakika falan",
+            "10xyz",
+            "30 saniye 5",
+            "",
+        ] {
+            assert_eq!(parse_duration(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn parses_clock_times() {
+        let cases = [
+            ("15:30", 3 * 3600 + 1800),
+            ("15.30", 3 * 3600 + 1800),
+            ("saat 9'da", 21 * 3600),
+            ("5'te", 17 * 3600),
+            ("17'de", 5 * 3600),
+            ("14:00'te", 2 * 3600),
+            ("at 3 pm", 3 * 3600),
+            ("3pm", 3 * 3600),
+            // Geçmiş saat ertesi gün
+            ("11:00", 23 * 3600),
+        ];
+        for (text, secs) in cases {
+            assert_eq!(due_in(text), Some(secs), "{}", text);
+        }
+        assert_eq!(due_in("1 saat 20"), Some(4800));
+        assert_eq!(parse_when("15:30", NOON, 3 * 3600), Some(NOON + 1800));
+    }
+
+    #[test]
+    fn rejects_days_and_dates() {
+        for text in [
+            "yarın 5'te",
+            "tomorrow at 3 pm",
+            "pazartesi 15:30",
+            "cuma",
+            "5",
+        ] {
+            assert_eq!(due_in(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn reminder_time_is_longest_parsable_prefix() {
+        let split = split_reminder;
+        assert_eq!(
+            split("1", "saat sonra su iç"),
+            ("1 saat sonra".into(), "su iç".into())
+        );
+        assert_eq!(
+            split("5'te", "toplantıyı"),
+            ("5'te".into(), "toplantıyı".into())
+        );
+        assert_eq!(
+            split("10 minutes", "call mom"),
+            ("10 minutes".into(), "call mom".into())
+        );
+        // Anlaşılamazsa olduğu gibi kalır — hata süre ile bildirilir
+        assert_eq!(split("yarın", "sunumu"), ("yarın".into(), "sunumu".into()));
+    }
+
+    #[test]
+    fn formats_durations_and_offsets() {
+        assert_eq!(format_duration(4800), "1 sa 20 dk");
+        assert_eq!(format_duration(45), "45 sn");
+        assert_eq!(format_duration(0), "0 sn");
+        assert_eq!(parse_offset("+0300"), Some(10_800));
+        assert_eq!(parse_offset("-0130"), Some(-5400));
+        assert_eq!(parse_offset("0300"), None);
+    }
+}
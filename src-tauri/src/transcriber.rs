@@ -2,6 +2,7 @@
 // Groq Whisper large-v3-turbo ile direkt transcription ~0.5-0.7s
 // AI düzeltme YOK — Whisper zaten yeterince iyi
 
+use crate::dsp::DspSettings;
 use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,15 @@ use serde::{Deserialize, Serialize};
 #[derive(Deserialize)]
 struct GroqResponse {
     text: Option<String>,
+    /// verbose_json ile gelen zamanlı parçalar
+    segments: Option<Vec<GroqSegment>>,
+}
+
+#[derive(Deserialize)]
+struct GroqSegment {
+    start: f64,
+    end: f64,
+    text: String,
 }
 
 // ── Gemini API formatları (fallback) ──
@@ -65,6 +75,17 @@ pub enum TranscribeMode {
     Command,
 }
 
+impl TranscribeMode {
+    /// Ayarlarda kullanılan mod adı ("dictation", "translate", "command")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dictation => "dictation",
+            Self::Translate { .. } => "translate",
+            Self::Command => "command",
+        }
+    }
+}
+
 /// Transkripsiyon sonucu
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscribeResult {
@@ -72,6 +93,25 @@ pub struct TranscribeResult {
     pub text: String,
     pub action: Option<String>,
     pub params: Option<String>,
+    /// Sağlayıcının döndürdüğü zamanlı parçalar (gönderilen sese göre, saniye)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// Zaman damgalı transkript parçası (saniye)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Zaman damgalı tam transkript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub text: String,
+    pub duration: f64,
+    pub segments: Vec<TranscriptSegment>,
 }
 
 /// Transkripsiyon bağlamı
@@ -83,6 +123,9 @@ pub struct TranscribeContext {
     pub writing_style: String,
     pub active_app: Option<String>,
     pub whisper_mode: bool,
+    pub dsp: DspSettings,
+    /// Komut modunda modele bildirilen komut listesi
+    pub commands: String,
 }
 
 /// Transkripsiyon motoru
@@ -91,18 +134,20 @@ pub struct GeminiTranscriber {
     proxy_endpoint: String,
     model: String,
     groq_api_key: Option<String>,
+    groq_endpoint: String,
     client: reqwest::blocking::Client,
 }
 
 impl GeminiTranscriber {
     pub fn new(api_key: &str, proxy_endpoint: &str, model: &str) -> Self {
-        let groq_key = crate::config::MillowConfig::load().groq_api_key;
+        let config = crate::config::MillowConfig::load();
 
         Self {
             api_key: api_key.to_string(),
             proxy_endpoint: proxy_endpoint.to_string(),
             model: model.to_string(),
-            groq_api_key: groq_key,
+            groq_api_key: config.groq_api_key,
+            groq_endpoint: config.groq_endpoint.trim_end_matches('/').to_string(),
             client: reqwest::blocking::Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .pool_max_idle_per_host(2)
@@ -126,6 +171,95 @@ impl GeminiTranscriber {
         self.single_stage_gemini(wav_bytes, mode, ctx)
     }
 
+    /// Ham örneklerden transkripsiyon — uzun kayıtlar parçalanıp tek metinde birleştirilir
+    pub fn transcribe_samples(
+        &self,
+        samples: &[i16],
+        source_rate: u32,
+        chunk_secs: f32,
+        mode: &TranscribeMode,
+        ctx: &TranscribeContext,
+    ) -> Result<TranscribeResult, String> {
+        // Komutlar kısa olur, parçalamaya gerek yok
+        if matches!(mode, TranscribeMode::Command) {
+            let processed = crate::dsp::process(samples, source_rate, &ctx.dsp);
+            let wav_bytes = crate::audio::AudioEngine::samples_to_wav(&processed, source_rate)?;
+            return self.transcribe(&wav_bytes, mode, ctx);
+        }
+
+        let transcript = self.transcribe_timed(samples, source_rate, chunk_secs, mode, ctx)?;
+        Ok(TranscribeResult {
+            result_type: "dictation".into(),
+            text: transcript.text,
+            action: None,
+            params: None,
+            segments: transcript.segments,
+        })
+    }
+
+    /// Zaman damgalı transkripsiyon — örnekleri sessizliklerden parçalara bölüp
+    /// her parçayı ayrı transkript eder, parça başlangıç/bitişini saniye olarak saklar
+    pub fn transcribe_timed(
+        &self,
+        samples: &[i16],
+        source_rate: u32,
+        chunk_secs: f32,
+        mode: &TranscribeMode,
+        ctx: &TranscribeContext,
+    ) -> Result<Transcript, String> {
+        use crate::audio::AudioEngine;
+
+        if source_rate == 0 {
+            return Err("Geçersiz örnekleme hızı".into());
+        }
+        let processed = crate::dsp::process(samples, source_rate, &ctx.dsp);
+        let ranges = AudioEngine::pause_ranges(&processed, source_rate, chunk_secs);
+        if ranges.len() > 1 {
+            println!("✂️  Uzun kayıt {} parçaya bölündü", ranges.len());
+        }
+
+        let to_secs = |i: usize| i as f64 / source_rate as f64;
+        let mut texts: Vec<String> = Vec::new();
+        let mut segments = Vec::new();
+        for (i, range) in ranges.iter().enumerate() {
+            // Bir parça bile eksikse metinde sessiz bir boşluk kalır — tüm kaydı hata say
+            let r = AudioEngine::samples_to_wav(&processed[range.clone()], source_rate)
+                .and_then(|wav| self.transcribe(&wav, mode, ctx))
+                .map_err(|e| {
+                    if ranges.len() > 1 {
+                        format!("Parça {}/{} transkript edilemedi: {}", i + 1, ranges.len(), e)
+                    } else {
+                        e
+                    }
+                })?;
+
+            let (offset, chunk_end) = (to_secs(range.start), to_secs(range.end));
+            if !r.segments.is_empty() {
+                // Sağlayıcı zamanları parçaya göre — kayıt başına kaydır
+                segments.extend(r.segments.into_iter().map(|seg| TranscriptSegment {
+                    start: (offset + seg.start).min(chunk_end),
+                    end: (offset + seg.end).min(chunk_end),
+                    text: seg.text,
+                }));
+            } else if !r.text.is_empty() {
+                segments.push(TranscriptSegment {
+                    start: offset,
+                    end: chunk_end,
+                    text: r.text.clone(),
+                });
+            }
+            if !r.text.is_empty() {
+                texts.push(r.text);
+            }
+        }
+
+        Ok(Transcript {
+            text: texts.join(" "),
+            duration: to_secs(processed.len()),
+            segments,
+        })
+    }
+
     /// ⚡ Groq Whisper — direkt transcription, AI düzeltme yok
     fn groq_transcribe(
         &self,
@@ -139,16 +273,16 @@ impl GeminiTranscriber {
         // Çeviri modunda Groq translate endpoint kullan
         let (url, lang) = match mode {
             TranscribeMode::Translate { .. } => {
-                ("https://api.groq.com/openai/v1/audio/translations".to_string(), None)
+                (format!("{}/audio/translations", self.groq_endpoint), None)
             }
             _ => {
-                ("https://api.groq.com/openai/v1/audio/transcriptions".to_string(), Some("tr"))
+                (format!("{}/audio/transcriptions", self.groq_endpoint), Some("tr"))
             }
         };
 
         let mut form = reqwest::blocking::multipart::Form::new()
             .text("model", "whisper-large-v3-turbo")
-            .text("response_format", "json")
+            .text("response_format", "verbose_json")
             .part("file", reqwest::blocking::multipart::Part::bytes(wav_bytes.to_vec())
                 .file_name("audio.wav")
                 .mime_str("audio/wav")
@@ -195,6 +329,23 @@ impl GeminiTranscriber {
             }
             cleaned
         };
+        // Zamanlı parçalar — hallucination olanları at
+        let segments: Vec<TranscriptSegment> = if text.is_empty() {
+            Vec::new()
+        } else {
+            groq_resp
+                .segments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|seg| TranscriptSegment {
+                    start: seg.start,
+                    end: seg.end,
+                    text: seg.text.trim().to_string(),
+                })
+                .filter(|seg| !seg.text.is_empty() && !hallucinations.iter().any(|h| seg.text == *h))
+                .collect()
+        };
+
         let elapsed = t0.elapsed().as_secs_f64();
         println!("⚡ Groq Whisper: {:.1}s → \"{}...\"", elapsed,
             &text.chars().take(60).collect::<String>());
@@ -204,6 +355,7 @@ impl GeminiTranscriber {
             text,
             action: None,
             params: None,
+            segments,
         })
     }
 
@@ -222,7 +374,14 @@ impl GeminiTranscriber {
                 format!("Transkript et ve {} diline çevir. SADECE sonucu döndür.", target_lang)
             }
             TranscribeMode::Command => {
-                r#"Sesi analiz et. SADECE JSON döndür:{"result_type":"dictation"|"command"|"wakeword"|"sleep","text":"...","action":"...","params":"..."}"#.to_string()
+                let mut prompt = r#"Sesi analiz et. SADECE JSON döndür:{"result_type":"dictation"|"command"|"wakeword"|"sleep","text":"...","action":"...","params":"..."} Kullanıcı son dikteyi geri almak isterse action "undo_dictation" olsun. Seçili metnin düzenlenmesini isterse ("bunu daha resmi yap", "dilbilgisini düzelt") action "edit_selection", params talimatın kendisi olsun."#.to_string();
+                if !ctx.commands.is_empty() {
+                    prompt.push_str(&format!(
+                        " Kullanılabilir action'lar: {}. Birden fazla parametre varsa params bir JSON nesnesi olsun.",
+                        ctx.commands
+                    ));
+                }
+                prompt
             }
         };
 
@@ -291,6 +450,7 @@ impl GeminiTranscriber {
             text,
             action: None,
             params: None,
+            segments: Vec::new(),
         })
     }
 
@@ -302,6 +462,9 @@ impl GeminiTranscriber {
         if ctx.format_commands {
             prompt.push_str("Sesli komutları uygula. ");
         }
+        if ctx.whisper_mode {
+            prompt.push_str("Konuşma fısıltı veya çok alçak sesle yapılmış olabilir, dikkatle dinle. ");
+        }
         if !ctx.dictionary.is_empty() {
             prompt.push_str(&format!("Terimler: {}. ", ctx.dictionary.join(", ")));
         }
@@ -309,3 +472,109 @@ impl GeminiTranscriber {
         prompt
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    const CHUNK_REPLY: &str =
+        r#"{"text":"parça metni","segments":[{"start":0.25,"end":0.75,"text":" parça metni"}]}"#;
+
+    /// Yerel sahte Groq ucuna bağlı motor — kullanıcının config'indeki anahtarlara dokunmaz
+    fn stub_transcriber(server: &StubServer) -> GeminiTranscriber {
+        GeminiTranscriber {
+            api_key: String::new(),
+            proxy_endpoint: server.url.clone(),
+            model: "test".into(),
+            groq_api_key: Some("test-key".into()),
+            groq_endpoint: server.url.clone(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// transcribe_file ile aynı yol: diskten çöz, sessizliklerden böl, parça parça gönder
+    fn transcribe_fixture(server: &StubServer, mode: &TranscribeMode) -> Result<Transcript, String> {
+        let path = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/noisy_speech.wav"));
+        let (samples, rate) = crate::decoder::decode_file(path)?;
+        stub_transcriber(server).transcribe_timed(&samples, rate, 1.0, mode, &TranscribeContext::default())
+    }
+
+    #[test]
+    fn file_transcript_stitches_chunks_with_offsets() {
+        let server = StubServer::reply(200, CHUNK_REPLY);
+        let transcript = transcribe_fixture(&server, &TranscribeMode::Dictation).unwrap();
+        let requests = server.requests();
+
+        // 3 sn'lik kayıt, en fazla 1 sn'lik parçalar
+        assert!(requests.len() >= 3, "{} istek", requests.len());
+        assert_eq!(transcript.segments.len(), requests.len());
+        assert!((transcript.duration - 3.0).abs() < 0.01);
+        assert_eq!(transcript.text, vec!["parça metni"; requests.len()].join(" "));
+        for request in &requests {
+            assert!(request.starts_with("POST /audio/transcriptions "));
+            assert!(request.contains("Bearer test-key"));
+            assert!(request.contains("verbose_json"));
+            assert!(request.contains("name=\"language\"\r\n\r\ntr"));
+        }
+        // Parça zamanları kayıt başına kaydırılmış, sıralı ve kayıt içinde
+        for pair in transcript.segments.windows(2) {
+            assert!(pair[1].start >= pair[0].end, "{:?}", transcript.segments);
+        }
+        assert_eq!(transcript.segments[0].start, 0.25);
+        assert!(transcript.segments.last().unwrap().end <= transcript.duration);
+        assert_eq!(transcript.segments[0].text, "parça metni");
+    }
+
+    #[test]
+    fn chunk_offsets_reach_exported_timestamps() {
+        let server = StubServer::reply(200, CHUNK_REPLY);
+        let transcript = transcribe_fixture(&server, &TranscribeMode::Dictation).unwrap();
+        let srt = crate::export::export(&transcript, crate::export::ExportFormat::Srt).unwrap();
+
+        // "00:00:02,250 --> …" satırlarındaki başlangıçları saniyeye çevir
+        let starts: Vec<f64> = srt
+            .lines()
+            .filter_map(|line| line.split_once(" --> "))
+            .map(|(start, _)| {
+                let (hms, ms) = start.split_once(',').unwrap();
+                let parts: Vec<f64> = hms.split(':').map(|p| p.parse().unwrap()).collect();
+                parts[0] * 3600.0 + parts[1] * 60.0 + parts[2] + ms.parse::<f64>().unwrap() / 1000.0
+            })
+            .collect();
+        assert_eq!(starts.len(), transcript.segments.len());
+        for (start, seg) in starts.iter().zip(&transcript.segments) {
+            assert!((start - seg.start).abs() < 0.001, "{} != {}", start, seg.start);
+        }
+        // Parçalar en fazla 1 sn — son parça kaydın üçüncü saniyesinde, 0.25 sn kaydırılmış olarak başlar
+        assert!(starts.windows(2).all(|pair| pair[1] > pair[0]), "{:?}", starts);
+        assert!(*starts.last().unwrap() >= 2.25, "{:?}", starts);
+    }
+
+    #[test]
+    fn translate_mode_uses_translation_endpoint() {
+        let server = StubServer::reply(200, CHUNK_REPLY);
+        transcribe_fixture(&server, &TranscribeMode::Translate { target_lang: "en".into() }).unwrap();
+        let request = server.next_request().unwrap();
+        assert!(request.starts_with("POST /audio/translations "));
+        assert!(!request.contains("name=\"language\""));
+    }
+
+    #[test]
+    fn failed_chunk_fails_whole_transcript() {
+        let server = StubServer::start(vec![
+            (200, CHUNK_REPLY.to_string()),
+            (500, r#"{"error":"sunucu hatası"}"#.to_string()),
+        ]);
+        let err = transcribe_fixture(&server, &TranscribeMode::Dictation).unwrap_err();
+        assert!(err.starts_with("Parça 2/"), "{}", err);
+        assert!(err.contains("500"), "{}", err);
+        // Hatadan sonra kalan parçalar gönderilmez
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        assert!(crate::decoder::decode_file(std::path::Path::new("/yok/kayıt.mp3")).is_err());
+    }
+}
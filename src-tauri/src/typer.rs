@@ -1,102 +1,191 @@
 // Millow — Otomatik Yazıcı
-// pbcopy + CGEvent Cmd+V ile aktif uygulamaya metin yapıştırma
+// Çıktı yöntemini (yapıştır / tuş vuruşu) seçer, metni platform arka ucuna (TextSink) iletir
+
+use crate::config::MillowConfig;
+use crate::correction::Edit;
+use crate::focus::FocusTarget;
+use crate::history::Insertion;
+use crate::sink::TextSink;
+use crate::spacing::CursorContext;
+use std::collections::HashMap;
+
+/// Metnin hedef uygulamaya aktarılma yöntemi
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputMethod {
+    /// Pano + Cmd+V (hızlı, varsayılan)
+    Paste,
+    /// Unicode tuş vuruşları (yapıştırmayı reddeden terminal, uzak masaüstü, VM, şifre alanları)
+    Type,
+}
 
-use std::process::Command;
+impl OutputMethod {
+    /// "paste" veya "type"
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "paste" | "yapıştır" => Ok(Self::Paste),
+            "type" | "yaz" => Ok(Self::Type),
+            other => Err(format!("Bilinmeyen çıktı yöntemi: {}", other)),
+        }
+    }
+}
 
 /// Otomatik metin yazıcı
-pub struct AutoTyper;
+pub struct AutoTyper {
+    method: OutputMethod,
+    /// Saniyedeki karakter sayısı (0 = sınırsız)
+    typing_rate: f32,
+    /// Küçük harfli uygulama adı veya bundle ID → yöntem
+    app_methods: HashMap<String, OutputMethod>,
+    /// Platform arka ucu (macOS, X11, Wayland)
+    sink: Box<dyn TextSink>,
+    /// Yapıştırılan metni geri alırken uygulamanın geri alma kısayolu kullanılsın mı
+    undo_with_shortcut: bool,
+    /// İmlecin çevresine göre boşluk ve büyük harf düzeltmesi
+    smart_spacing: bool,
+    /// Cümle başında "i" → "İ"
+    turkish: bool,
+}
 
 impl AutoTyper {
     pub fn new() -> Result<Self, String> {
-        Ok(Self)
+        let config = MillowConfig::load();
+        let method = OutputMethod::parse(&config.output_method).unwrap_or_else(|e| {
+            println!("⚠️ {}, yapıştırma kullanılacak", e);
+            OutputMethod::Paste
+        });
+
+        let mut app_methods = HashMap::new();
+        for (app, name) in &config.app_output_methods {
+            match OutputMethod::parse(name) {
+                Ok(m) => {
+                    app_methods.insert(app.trim().to_lowercase(), m);
+                }
+                Err(e) => println!("⚠️ {} ({}), atlanıyor", e, app),
+            }
+        }
+
+        let sink = crate::sink::select_sink(&config.output_backend)?;
+
+        Ok(Self {
+            method,
+            typing_rate: config.typing_rate.max(0.0),
+            app_methods,
+            sink,
+            undo_with_shortcut: config.undo_method.trim().eq_ignore_ascii_case("undo"),
+            smart_spacing: config.smart_spacing,
+            turkish: config.default_language.trim().to_lowercase().starts_with("tr"),
+        })
     }
 
     /// Metni belirtilen uygulamaya yapıştır
     /// target_app: kayıt başlamadan önceki aktif uygulama adı
     pub fn type_text(&self, text: &str) -> Result<(), String> {
-        self.type_text_to_app(text, None)
+        self.type_text_to_app(text, None, None).map(|_| ())
     }
 
-    /// Metni belirtilen uygulamaya yapıştır
-    pub fn type_text_to_app(&self, text: &str, target_app: Option<&str>) -> Result<(), String> {
-        // 1. Mevcut clipboard'ı yedekle
-        let old_clipboard = Command::new("pbpaste")
-            .env("LANG", "en_US.UTF-8")
-            .output()
-            .ok()
-            .and_then(|o| String::from_utf8(o.stdout).ok());
-        
-        println!("⌨️ AutoTyper: Yazılıyor (hedef: {:?})", target_app);
-
-        // 2. Metni clipboard'a kopyala
-        let mut child = Command::new("pbcopy")
-            .env("LANG", "en_US.UTF-8")
-            .stdin(std::process::Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("pbcopy hatası: {}", e))?;
-
-        if let Some(mut stdin) = child.stdin.take() {
-            use std::io::Write;
-            stdin.write_all(text.as_bytes())
-                .map_err(|e| format!("Clipboard yazma hatası: {}", e))?;
-        }
-        child.wait().map_err(|e| format!("pbcopy bekleme hatası: {}", e))?;
-
-        // 3. Hedef uygulamaya focus ver
-        if let Some(app_name) = target_app {
-            println!("🔄 Focus veriliyor: {}", app_name);
-            let script = format!(
-                "tell application \"{}\" to activate",
-                app_name.replace('"', "\\\"")
-            );
-            let _ = Command::new("osascript")
-                .arg("-e")
-                .arg(&script)
-                .output();
-            // Uygulama focus alana kadar bekle
-            std::thread::sleep(std::time::Duration::from_millis(300));
-        }
+    /// Hedef uygulama için geçerli yöntem — uygulamaya özel ayar yoksa varsayılan
+    pub fn method_for(&self, target_app: Option<&FocusTarget>) -> OutputMethod {
+        target_app
+            .and_then(|app| {
+                std::iter::once(app.name.as_str())
+                    .chain(app.bundle_id.as_deref())
+                    .find_map(|key| self.app_methods.get(&key.trim().to_lowercase()))
+            })
+            .copied()
+            .unwrap_or(self.method)
+    }
 
-        // 4. CGEvent ile Cmd+V yapıştır
-        unsafe {
-            use core_graphics::event::{CGEvent, CGEventFlags, CGKeyCode};
-            use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+    /// Hedef uygulamayı öne getirip metni yapıştır veya tuş vuruşu olarak yaz, yazılan metni döndür.
+    /// Hedef öne gelmezse başka bir uygulamaya yazmamak için hiçbir şey yazılmaz.
+    /// `previous`: imlecin çevresi okunamazsa kullanılacak, bu uygulamaya daha önce yazılan metin
+    pub fn type_text_to_app(
+        &self,
+        text: &str,
+        target_app: Option<&FocusTarget>,
+        previous: Option<&str>,
+    ) -> Result<String, String> {
+        let method = self.method_for(target_app);
+        println!(
+            "⌨️ AutoTyper: Yazılıyor (hedef: {:?}, {:?}, {})",
+            target_app.map(|app| app.name.as_str()),
+            method,
+            self.sink.name()
+        );
+
+        if let Some(app) = target_app {
+            crate::focus::restore(app)?;
+        }
 
-            let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
-                .map_err(|_| "CGEventSource oluşturulamadı".to_string())?;
+        let text = if self.smart_spacing {
+            self.fit_to_cursor(text, previous)
+        } else {
+            text.to_string()
+        };
+        match method {
+            OutputMethod::Paste => self.sink.paste(&text)?,
+            OutputMethod::Type => self.sink.type_text(&text, self.typing_rate)?,
+        }
+        Ok(text)
+    }
 
-            // V tuşu keycode = 9
-            let key_down = CGEvent::new_keyboard_event(source.clone(), 9, true)
-                .map_err(|_| "KeyDown event oluşturulamadı".to_string())?;
-            let key_up = CGEvent::new_keyboard_event(source, 9, false)
-                .map_err(|_| "KeyUp event oluşturulamadı".to_string())?;
+    /// Metni imlecin çevresine uydur — önce erişilebilirlik API'si, yoksa kendi eklemelerimiz
+    fn fit_to_cursor(&self, text: &str, previous: Option<&str>) -> String {
+        let context = crate::spacing::read_focused().or_else(|| previous.map(CursorContext::before_only));
+        let Some(context) = context else {
+            return text.to_string();
+        };
+        let adjusted = crate::spacing::adjust(text, &context, self.turkish);
+        if adjusted != text {
+            println!("✨ Akıllı boşluk: {:?} → {:?}", text, adjusted);
+        }
+        adjusted
+    }
 
-            key_down.set_flags(CGEventFlags::CGEventFlagCommand);
-            key_up.set_flags(CGEventFlags::CGEventFlagCommand);
+    /// Hedef uygulamayı öne getirip seçili metni oku
+    pub fn selected_text(&self, target_app: Option<&FocusTarget>) -> Result<String, String> {
+        if let Some(app) = target_app {
+            crate::focus::restore(app)?;
+        }
+        self.sink.selected_text()
+    }
 
-            key_down.post(core_graphics::event::CGEventTapLocation::HID);
-            key_up.post(core_graphics::event::CGEventTapLocation::HID);
+    /// Seçimi metinle değiştir — seçim varken yapıştırmak ya da yazmak onun yerine geçer.
+    /// Akıllı boşluk uygulanmaz: metin seçimin yerine olduğu gibi girer
+    pub fn replace_selection(&self, text: &str, target_app: Option<&FocusTarget>) -> Result<(), String> {
+        if let Some(app) = target_app {
+            crate::focus::restore(app)?;
         }
+        match self.method_for(target_app) {
+            OutputMethod::Paste => self.sink.paste(text),
+            OutputMethod::Type => self.sink.type_text(text, self.typing_rate),
+        }
+    }
 
-        // 5. Yapıştırma bekleme
-        let wait_ms = if target_app.is_some() { 250 } else { 150 };
-        std::thread::sleep(std::time::Duration::from_millis(wait_ms));
-
-        // 6. Eski clipboard'ı geri yükle
-        if let Some(old) = old_clipboard {
-            if let Ok(mut restore) = Command::new("pbcopy")
-                .env("LANG", "en_US.UTF-8")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-            {
-                if let Some(mut stdin) = restore.stdin.take() {
-                    use std::io::Write;
-                    let _ = stdin.write_all(old.as_bytes());
-                }
-                let _ = restore.wait();
-            }
+    /// Önceki bir eklemeyi kaldır — odak kontrolü çağıranın sorumluluğundadır.
+    /// Tuş vuruşuyla yazılanlar her zaman karakter sayısı kadar Backspace ile silinir;
+    /// yapıştırılanlar için `undo_method = "undo"` ise uygulamanın geri alması kullanılır.
+    pub fn remove(&self, insertion: &Insertion) -> Result<(), String> {
+        if self.undo_with_shortcut && insertion.method == OutputMethod::Paste {
+            println!("↩️ AutoTyper: Geri alma kısayolu gönderiliyor ({})", self.sink.name());
+            self.sink.undo()
+        } else {
+            let count = insertion.char_count();
+            println!("↩️ AutoTyper: {} karakter siliniyor ({})", count, self.sink.name());
+            self.sink.erase(count)
         }
+    }
 
-        Ok(())
+    /// Sesli düzeltmeyi en az tuş vuruşuyla uygula — imlecin son eklemenin sonunda olduğu varsayılır
+    pub fn apply_edit(&self, edit: &Edit) -> Result<(), String> {
+        println!(
+            "✏️ AutoTyper: Düzeltme (←{} ⌫{} +{:?} →{})",
+            edit.back, edit.erase, edit.insert, edit.forward
+        );
+        self.sink.move_cursor(-(edit.back as isize))?;
+        self.sink.erase(edit.erase)?;
+        if !edit.insert.is_empty() {
+            self.sink.type_text(&edit.insert, 0.0)?;
+        }
+        self.sink.move_cursor(edit.forward as isize)
     }
 }
@@ -42,6 +42,12 @@ const CommandIcon = ({ size = 20, className = "" }) => (
   </SFIcon>
 );
 
+const PencilIcon = ({ size = 20, className = "" }) => (
+  <SFIcon size={size} className={className}>
+    <path d="M16.47 3.53a2.25 2.25 0 0 1 3.18 0l.82.82a2.25 2.25 0 0 1 0 3.18L9.06 18.94a1 1 0 0 1-.46.26l-4.35 1.09a.75.75 0 0 1-.91-.91l1.09-4.35a1 1 0 0 1 .26-.46ZM15.41 6.7l-8.9 8.9-.62 2.5 2.5-.62 8.9-8.9Z" fill="currentColor" />
+  </SFIcon>
+);
+
 // ── Tipler ──
 
 interface MillowConfig {
@@ -70,10 +76,61 @@ interface MillowConfig {
   silence_duration: number;
   auto_stop_duration: number;
   newline_after_segment: boolean;
+  smart_spacing: boolean;
+  output_method: string;
+  typing_rate: number;
+  app_output_methods: Record<string, string>;
+  command_allowlist: string[];
+  command_denylist: string[];
+  confirm_destructive_commands: boolean;
+  command_dry_run: boolean;
+  ai_endpoint: string;
+  ai_api_key: string | null;
+  ai_model: string;
+  ai_output: string;
+  url_allowed_schemes: string[];
+  url_allowed_domains: string[];
   hallucination_filters: string[];
 }
 
-type AppMode = "dictation" | "translate" | "command";
+interface CommandInfo {
+  name: string;
+  description: string;
+  triggers: string[];
+  platforms: string[];
+  destructive: boolean;
+  custom: boolean;
+  available: boolean;
+}
+
+interface DryRun {
+  command: string;
+  params: Record<string, string>;
+  commands: string[];
+  message: string;
+  needs_confirmation: boolean;
+}
+
+interface InputLevel {
+  rms: number;
+  peak: number;
+  clipping: boolean;
+  recording: boolean;
+}
+
+interface MicTestReport {
+  device: string;
+  sample_rate: number;
+  duration: number;
+  rms_db: number;
+  peak_db: number;
+  clipped_ratio: number;
+  clipping: boolean;
+  verdict: "silent" | "low" | "clipping" | "ok";
+  message: string;
+}
+
+type AppMode = "dictation" | "translate" | "command" | "edit";
 type AppStatus = "idle" | "recording" | "processing";
 
 // ── Ana Uygulama ──
@@ -85,10 +142,16 @@ function App() {
   const [config, setConfig] = useState<MillowConfig | null>(null);
   const [showSettings, setShowSettings] = useState(false);
   const [notification, setNotification] = useState("");
-  const [settingsTab, setSettingsTab] = useState<"general" | "audio" | "filters">("general");
+  const [settingsTab, setSettingsTab] = useState<"general" | "audio" | "filters" | "commands">("general");
+  const [commands, setCommands] = useState<CommandInfo[]>([]);
+  const [dryRunInput, setDryRunInput] = useState("");
+  const [dryRun, setDryRun] = useState<DryRun | null>(null);
   const [dictInput, setDictInput] = useState(""); // custom dictionary textarea
   const [halInput, setHalInput] = useState(""); // hallucination filters textarea
   const [autoLaunch, setAutoLaunch] = useState(false);
+  const [level, setLevel] = useState<InputLevel | null>(null);
+  const [micTest, setMicTest] = useState<MicTestReport | null>(null);
+  const [micTesting, setMicTesting] = useState(false);
 
   useEffect(() => {
     invoke<MillowConfig>("get_config").then((c) => {
@@ -100,6 +163,15 @@ function App() {
     invoke<boolean>("get_auto_launch").then((v) => setAutoLaunch(v));
   }, []);
 
+  useEffect(() => {
+    const unlisten = listen<InputLevel>("input-level", (event) => {
+      setLevel(event.payload.recording ? event.payload : null);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   useEffect(() => {
     const unlisten1 = listen("toggle-recording", async () => {
       if (status === "recording") {
@@ -125,6 +197,7 @@ function App() {
     dictation: { label: "Dikte", icon: <MicIcon size={16} />, desc: "Konuşmanız düzenlenip metne dönüştürülür" },
     translate: { label: "Çeviri", icon: <GlobeIcon size={16} />, desc: "Konuşmanız çevrilir" },
     command: { label: "Komut", icon: <CommandIcon size={16} />, desc: "Sesli komut çalıştırır" },
+    edit: { label: "Düzenle", icon: <PencilIcon size={16} />, desc: "Seçili metin söylediğiniz talimata göre düzenlenir" },
   };
 
   const showNotif = (msg: string) => {
@@ -153,6 +226,35 @@ function App() {
     setStatus("idle");
   };
 
+  const runDryRun = async () => {
+    setDryRun(null);
+    try {
+      setDryRun(await invoke<DryRun>("dry_run_command", { input: dryRunInput }));
+    } catch (e) {
+      showNotif(`Hata: ${e}`);
+    }
+  };
+
+  const runMicTest = async () => {
+    setMicTesting(true);
+    setMicTest(null);
+    try {
+      setMicTest(await invoke<MicTestReport>("mic_test"));
+    } catch (e) {
+      showNotif(`Hata: ${e}`);
+    }
+    setMicTesting(false);
+  };
+
+  const openCommandsTab = async () => {
+    setSettingsTab("commands");
+    try {
+      setCommands(await invoke<CommandInfo[]>("list_commands"));
+    } catch (e) {
+      showNotif(`Hata: ${e}`);
+    }
+  };
+
   const updateConfig = (partial: Partial<MillowConfig>) => {
     if (config) setConfig({ ...config, ...partial });
   };
@@ -172,7 +274,12 @@ function App() {
           return;
         }
       }
-      await invoke("save_config", { newConfig: finalConfig });
+      try {
+        await invoke("save_config", { newConfig: finalConfig });
+      } catch (e) {
+        showNotif("Ayar hatası: " + e);
+        return;
+      }
       setConfig(finalConfig);
       showNotif("Ayarlar kaydedildi");
       setShowSettings(false);
@@ -196,6 +303,7 @@ function App() {
           <button className={"tab-btn " + (settingsTab === "general" ? "active" : "")} onClick={() => setSettingsTab("general")}>Genel</button>
           <button className={"tab-btn " + (settingsTab === "audio" ? "active" : "")} onClick={() => setSettingsTab("audio")}>Ses</button>
           <button className={"tab-btn " + (settingsTab === "filters" ? "active" : "")} onClick={() => setSettingsTab("filters")}>Filtreler</button>
+          <button className={"tab-btn " + (settingsTab === "commands" ? "active" : "")} onClick={openCommandsTab}>Komutlar</button>
         </div>
 
         <div className="settings-scroll">
@@ -291,6 +399,20 @@ function App() {
               </label>
             </div>
 
+            <div className="settings-group">
+              <div className="settings-group-title">Mikrofon Testi</div>
+              <div className="setting-row">
+                <span>{micTesting ? "3 saniye konuşun…" : (micTest ? micTest.device || "Varsayılan mikrofon" : "Seviye ve kırpılma kontrolü")}</span>
+                <button className="toolbar-btn" onClick={runMicTest} disabled={micTesting}>Test Et</button>
+              </div>
+              {micTest && (
+                <div className={`setting-row mic-test ${micTest.verdict}`}>
+                  <span>{micTest.message}</span>
+                  <span className="slider-val">{micTest.rms_db.toFixed(0)} / {micTest.peak_db.toFixed(0)} dB</span>
+                </div>
+              )}
+            </div>
+
             <div className="settings-group">
               <div className="settings-group-title">Davranış</div>
               <label className="setting-row toggle">
@@ -301,6 +423,26 @@ function App() {
                 <span>Segment Sonrası Satır Sonu</span>
                 <input type="checkbox" checked={config.newline_after_segment} onChange={(e) => updateConfig({ newline_after_segment: e.target.checked })} />
               </label>
+              <label className="setting-row toggle">
+                <span>Akıllı Boşluk ve Büyük Harf</span>
+                <input type="checkbox" checked={config.smart_spacing} onChange={(e) => updateConfig({ smart_spacing: e.target.checked })} />
+              </label>
+              <label className="setting-row">
+                <span>Çıktı Yöntemi</span>
+                <select value={config.output_method} onChange={(e) => updateConfig({ output_method: e.target.value })}>
+                  <option value="paste">Yapıştır (⌘V)</option>
+                  <option value="type">Tuş Vuruşu</option>
+                </select>
+              </label>
+              {config.output_method === "type" && (
+                <label className="setting-row">
+                  <span>Yazma Hızı</span>
+                  <div className="slider-wrap">
+                    <input type="range" min="0" max="500" step="10" value={config.typing_rate} onChange={(e) => updateConfig({ typing_rate: parseFloat(e.target.value) })} />
+                    <span className="slider-val">{config.typing_rate === 0 ? "∞" : `${config.typing_rate}/sn`}</span>
+                  </div>
+                </label>
+              )}
             </div>
           </>)}
 
@@ -329,6 +471,59 @@ function App() {
               </div>
             </div>
           </>)}
+
+          {settingsTab === "commands" && (<>
+            <div className="settings-group">
+              <div className="settings-group-title">Sesli Komutlar</div>
+              <label className="setting-row toggle">
+                <span>Tehlikeli Komutlarda Onay İste</span>
+                <input type="checkbox" checked={config.confirm_destructive_commands} onChange={(e) => updateConfig({ confirm_destructive_commands: e.target.checked })} />
+              </label>
+              <label className="setting-row toggle">
+                <span>Deneme Modu (çalıştırma)</span>
+                <input type="checkbox" checked={config.command_dry_run} onChange={(e) => updateConfig({ command_dry_run: e.target.checked })} />
+              </label>
+              <div className="setting-row">
+                <input type="text" value={dryRunInput} onChange={(e) => setDryRunInput(e.target.value)} onKeyDown={(e) => e.key === "Enter" && runDryRun()} placeholder="5 dakikalık zamanlayıcı kur" />
+                <button className="toolbar-btn" onClick={runDryRun} disabled={!dryRunInput.trim()}>Dene</button>
+              </div>
+              {dryRun && (
+                <div className="setting-row dry-run">
+                  <span>{dryRun.needs_confirmation ? "⚠️ " : ""}{dryRun.command} → {dryRun.message}</span>
+                  {dryRun.commands.map((line, i) => <code key={i}>{line}</code>)}
+                </div>
+              )}
+              {commands.map((cmd) => (
+                <div key={cmd.name} className={"setting-row command-row" + (cmd.available ? "" : " unavailable")} title={cmd.description}>
+                  <span>{cmd.destructive ? "⚠️ " : ""}{cmd.name}{cmd.custom ? " ✦" : ""}</span>
+                  <span className="command-trigger">{cmd.triggers[0] ?? ""}</span>
+                </div>
+              ))}
+            </div>
+
+            <div className="settings-group">
+              <div className="settings-group-title">AI Pano İşlemleri</div>
+              <label className="setting-row">
+                <span>API Adresi</span>
+                <input type="text" value={config.ai_endpoint} onChange={(e) => updateConfig({ ai_endpoint: e.target.value })} placeholder="https://api.groq.com/openai/v1" />
+              </label>
+              <label className="setting-row">
+                <span>API Key</span>
+                <input type="password" value={config.ai_api_key || ""} onChange={(e) => updateConfig({ ai_api_key: e.target.value || null })} placeholder="Groq adresinde Groq anahtarı kullanılır" />
+              </label>
+              <label className="setting-row">
+                <span>Model</span>
+                <input type="text" value={config.ai_model} onChange={(e) => updateConfig({ ai_model: e.target.value })} />
+              </label>
+              <label className="setting-row">
+                <span>Sonuç</span>
+                <select value={config.ai_output} onChange={(e) => updateConfig({ ai_output: e.target.value })}>
+                  <option value="clipboard">Panoya kopyala</option>
+                  <option value="paste">Kopyala ve yapıştır</option>
+                </select>
+              </label>
+            </div>
+          </>)}
         </div>
       </div>
     );
@@ -363,6 +558,11 @@ function App() {
             {status === "recording" && "Dinliyor…"}
             {status === "processing" && "İşleniyor…"}
           </div>
+          {status === "recording" && level && (
+            <div className={`level-meter ${level.clipping ? "clipping" : ""}`}>
+              <div className="level-meter-fill" style={{ width: `${Math.min(100, Math.sqrt(level.rms) * 250)}%` }} />
+            </div>
+          )}
           <div className="status-hint">
             {status === "idle" && (config?.hold_to_talk ? "⌥ Space basılı tutun" : "⌥ Space veya \"Millow\" deyin")}
             {status === "recording" && (config?.hold_to_talk ? "Konuşun, bırakınca durdurulur" : "Konuşun, bitince tekrar basın")}
@@ -0,0 +1,70 @@
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import "./App.css";
+import "./Preview.css";
+
+// ── Önizleme Penceresi: Enter yazar, Shift+Enter satır ekler, Esc atar ──
+
+export default function Preview() {
+  const [text, setText] = useState("");
+  const textRef = useRef<HTMLTextAreaElement>(null);
+
+  const focusEnd = () => {
+    const el = textRef.current;
+    if (!el) return;
+    el.focus();
+    el.setSelectionRange(el.value.length, el.value.length);
+  };
+
+  useEffect(() => {
+    // Pencere ilk açıldığında olay kaçmış olabilir — bekleyen metni iste
+    invoke<string | null>("get_preview").then((pending) => {
+      if (pending) {
+        setText(pending);
+        requestAnimationFrame(focusEnd);
+      }
+    });
+
+    const unlisten = listen<string>("preview-text", (event) => {
+      setText(event.payload);
+      requestAnimationFrame(focusEnd);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const onKeyDown = (e: React.KeyboardEvent<HTMLTextAreaElement>) => {
+    if (e.key === "Enter" && !e.shiftKey) {
+      e.preventDefault();
+      invoke("confirm_preview", { text });
+      setText("");
+    } else if (e.key === "Escape") {
+      e.preventDefault();
+      invoke("cancel_preview");
+      setText("");
+    }
+  };
+
+  return (
+    <div className="preview">
+      <textarea
+        ref={textRef}
+        className="preview-text"
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        onKeyDown={onKeyDown}
+        spellCheck
+        autoFocus
+      />
+      <div className="preview-hint">
+        <span className="shortcut-badge">↵</span> Yaz
+        <span className="footer-sep">·</span>
+        <span className="shortcut-badge">⇧↵</span> Satır
+        <span className="footer-sep">·</span>
+        <span className="shortcut-badge">Esc</span> Vazgeç
+      </div>
+    </div>
+  );
+}
@@ -1,9 +1,14 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import Preview from "./Preview";
+
+// Önizleme penceresi aynı sayfayı yükler, etiketine göre bileşen seçilir
+const isPreview = getCurrentWindow().label === "preview";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isPreview ? <Preview /> : <App />}
   </React.StrictMode>,
 );